@@ -7,49 +7,96 @@
 
 #![allow(clippy::await_holding_refcell_ref)]
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
 use std::rc::Rc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::task::spawn_local;
 use tokio::time::Duration;
 
+use crate::application::traits::{StreamingTranscriptionClient, TranscriptionStream};
 use crate::application::{
-    MediaControlService, RecordingOptions, RecordingService, StackService, TranscriptionService,
-    UserFeedback,
+    MediaControlService, RecordingOptions, RecordingService, StackService, Transcript,
+    TranscriptionOptions, TranscriptionService, UserFeedback, retry_queued,
 };
+use crate::domain::usage::UsageReport;
 use crate::error::{Result, VoiceInputError};
 use crate::infrastructure::{
-    audio::{AudioBackend, CpalAudioBackend},
+    audio::{
+        AudioBackend, AudioData, AudioError, CpalAudioBackend, PcmChunk,
+        decoder::{TRANSCRIPTION_SAMPLE_RATE, decode_file},
+        device::DeviceReport,
+        vad::VoiceActivityDetector,
+    },
     external::{
         clipboard::get_selected_text,
-        sound::{play_start_sound, play_stop_sound},
+        openai::{OpenAiClient, OpenAiConfig},
+        sound::{play_start_sound, play_stop_sound, resume_apple_music},
+        stt_provider::SttProvider,
         text_input,
     },
+    queue::QueueRetryReport,
     ui::{UiNotification, UiProcessManager},
+    usage::UsageConfig,
 };
 use crate::ipc::{IpcCmd, IpcResp, RecordingResult};
 use crate::shortcut::ShortcutService;
 
+/// 連続音声入力でマイクバッファを確認する間隔
+const CONTINUOUS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// `usage` で日別に集計する日数の既定値
+const DEFAULT_USAGE_DAYS: u32 = 7;
+
+/// ストリーミング転写の確定結果（失敗時は録音全体を通常の転写に回す）
+pub type StreamedTranscript = oneshot::Receiver<Result<Transcript>>;
+
 /// 転写メッセージ
 pub type TranscriptionMessage = (
     RecordingResult,
+    TranscriptionOptions,
     bool, // paste
     bool, // resume_music
     bool, // direct_input
+    // スタックモードでなくても渡す（信頼度の低い結果の保存先）
     Option<Rc<RefCell<StackService>>>,
     Option<Rc<RefCell<UiProcessManager>>>,
+    Option<StreamedTranscript>,
 );
 
+/// ストリーミング転写ループから停止時に受け取る録音
+struct StreamedRecording {
+    /// 録音中に送った音声（ストリーミング失敗時の転写用）
+    samples: Vec<i16>,
+    sample_rate: u32,
+    channels: u16,
+    transcript: StreamedTranscript,
+}
+
+/// ストリーミング転写ループの停止用（応答として録音を受け取る）
+type StreamStop = oneshot::Sender<oneshot::Sender<StreamedRecording>>;
+
 /// コマンドハンドラー
 pub struct CommandHandler<T: AudioBackend> {
     recording: Rc<RefCell<RecordingService<T>>>,
-    #[allow(dead_code)]
     transcription: Rc<RefCell<TranscriptionService>>,
     stack: Rc<RefCell<StackService>>,
     media_control: Rc<RefCell<MediaControlService>>,
     ui_manager: Rc<RefCell<UiProcessManager>>,
     shortcut_service: Rc<RefCell<ShortcutService>>,
     transcription_tx: mpsc::UnboundedSender<TranscriptionMessage>,
+    /// 連続音声入力ループの停止用（応答として転写キューに送ったセグメント数を受け取る）
+    continuous_stop: Rc<RefCell<Option<oneshot::Sender<oneshot::Sender<usize>>>>>,
+    /// ストリーミング転写クライアント（設定時のみ録音中に音声を逐次送る）
+    streaming: Option<Rc<dyn StreamingTranscriptionClient>>,
+    /// 録音中のストリーミング転写ループの停止用
+    active_stream: Rc<RefCell<Option<StreamStop>>>,
+    /// キューの再実行中か（自動再実行と `queue retry` の重複を防ぐ）
+    queue_retry_active: Rc<Cell<bool>>,
+    /// push-to-talk で開始した録音の最中か（キーを離したときに他の録音を止めないため）
+    ptt_active: Rc<Cell<bool>>,
+    /// 利用記録・料金表・月間予算の設定
+    usage: UsageConfig,
 }
 
 impl<T: AudioBackend + 'static> CommandHandler<T> {
@@ -71,9 +118,27 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
             ui_manager,
             shortcut_service,
             transcription_tx,
+            continuous_stop: Rc::new(RefCell::new(None)),
+            streaming: None,
+            active_stream: Rc::new(RefCell::new(None)),
+            queue_retry_active: Rc::new(Cell::new(false)),
+            ptt_active: Rc::new(Cell::new(false)),
+            usage: UsageConfig::default(),
         }
     }
 
+    /// 録音中に音声を逐次送るストリーミング転写を有効化
+    pub fn with_streaming(mut self, client: Box<dyn StreamingTranscriptionClient>) -> Self {
+        self.streaming = Some(Rc::from(client));
+        self
+    }
+
+    /// 利用状況の集計に使う設定を指定
+    pub fn with_usage(mut self, usage: UsageConfig) -> Self {
+        self.usage = usage;
+        self
+    }
+
     /// IPCコマンドを処理
     pub async fn handle(&self, cmd: IpcCmd) -> Result<IpcResp> {
         match cmd {
@@ -81,27 +146,99 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
                 paste,
                 prompt,
                 direct_input,
-            } => self.handle_start(paste, prompt, direct_input).await,
+                language,
+                translate,
+                profile,
+            } => {
+                self.handle_start(paste, prompt, direct_input, language, translate, profile)
+                    .await
+            }
+            IpcCmd::Stop if self.is_continuous_active() => self.handle_stop_continuous().await,
             IpcCmd::Stop => self.handle_stop().await,
             IpcCmd::Toggle {
                 paste,
                 prompt,
                 direct_input,
+                language,
+                translate,
+                profile,
             } => {
-                if self.recording.borrow().is_recording() {
+                if self.is_continuous_active() {
+                    self.handle_stop_continuous().await
+                } else if self.recording.borrow().is_recording() {
                     self.handle_stop().await
                 } else {
-                    self.handle_start(paste, prompt, direct_input).await
+                    self.handle_start(paste, prompt, direct_input, language, translate, profile)
+                        .await
                 }
             }
             IpcCmd::Status => self.handle_status(),
             IpcCmd::ListDevices => self.handle_list_devices(),
+            IpcCmd::ListDevicesVerbose => self.handle_list_devices_verbose(),
             IpcCmd::Health => self.handle_health().await,
             IpcCmd::EnableStackMode => self.handle_enable_stack_mode().await,
             IpcCmd::DisableStackMode => self.handle_disable_stack_mode().await,
             IpcCmd::PasteStack { number } => self.handle_paste_stack(number).await,
             IpcCmd::ListStacks => self.handle_list_stacks(),
             IpcCmd::ClearStacks => self.handle_clear_stacks(),
+            IpcCmd::StartPtt {
+                paste,
+                prompt,
+                direct_input,
+                language,
+                translate,
+                profile,
+            } => {
+                // 連続音声入力・CLI の録音中は音楽の状態などを上書きせずに断る
+                if self.is_continuous_active() || self.recording.borrow().is_recording() {
+                    return Err(VoiceInputError::RecordingAlreadyActive);
+                }
+                let resp = self
+                    .handle_start(paste, prompt, direct_input, language, translate, profile)
+                    .await?;
+                self.ptt_active.set(true);
+                Ok(resp)
+            }
+            IpcCmd::StopPtt => self.handle_stop_ptt().await,
+            IpcCmd::StartContinuous {
+                paste,
+                prompt,
+                direct_input,
+                language,
+                translate,
+                profile,
+            } => {
+                self.handle_start_continuous(
+                    paste,
+                    prompt,
+                    direct_input,
+                    language,
+                    translate,
+                    profile,
+                )
+                .await
+            }
+            IpcCmd::StopContinuous => self.handle_stop_continuous().await,
+            IpcCmd::TranscribeFile {
+                path,
+                language,
+                translate,
+                profile,
+            } => {
+                self.handle_transcribe_file(path, language, translate, profile)
+                    .await
+            }
+            IpcCmd::QueueRetry { id } => self.handle_queue_retry(id).await,
+            IpcCmd::Usage { days } => self.handle_usage(days),
+            IpcCmd::PipelineTest {
+                text,
+                language,
+                translate,
+                profile,
+            } => {
+                self.handle_pipeline_test(text, language, translate, profile)
+                    .await
+            }
         }
     }
 
@@ -111,7 +248,12 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
         paste: bool,
         prompt: Option<String>,
         direct_input: bool,
+        language: Option<String>,
+        translate: Option<String>,
+        profile: Option<String>,
     ) -> Result<IpcResp> {
+        self.check_profile(profile.as_deref())?;
+
         // プロンプトの決定（引数優先、なければ選択テキスト）
         let final_prompt = prompt.or_else(|| get_selected_text().ok());
 
@@ -126,6 +268,9 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
         // 録音オプションを構築
         let options = RecordingOptions {
             prompt: final_prompt,
+            language,
+            translate,
+            profile,
             paste,
             direct_input,
         };
@@ -137,6 +282,9 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
         // 自動停止タイマーを設定
         self.setup_auto_stop_timer();
 
+        // 録音中の音声を逐次送信
+        self.spawn_stream_loop();
+
         let max_secs = self.recording.borrow().config().max_duration_secs;
         Ok(IpcResp {
             ok: true,
@@ -146,33 +294,36 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
 
     /// 録音停止処理
     async fn handle_stop(&self) -> Result<IpcResp> {
+        self.ptt_active.set(false);
+
         // 停止音を再生
         play_stop_sound();
 
-        // 録音を停止
+        // ストリーミング中なら残りの音声を送らせてから録音を停止
+        let streamed = Self::stop_stream(&self.active_stream).await;
         let recording = self.recording.clone();
-        let result = recording.borrow().stop_recording().await?;
+        let mut result = recording.borrow().stop_recording().await?;
+        let streamed = Self::attach_streamed(&mut result, streamed);
 
         // コンテキスト情報を取得
         let (_start_prompt, paste, direct_input, music_was_playing) =
             self.recording.borrow().get_context_info()?;
+        let options = self.recording.borrow().transcription_options()?;
 
-        // スタックモードが有効な場合はサービスを渡す
-        let stack_for_transcription = if self.stack.borrow().is_stack_mode_enabled() {
-            Some(self.stack.clone())
-        } else {
-            None
-        };
+        // スタックモードでなくても、信頼度の低い結果を保存するためサービスを渡す
+        let stack_for_transcription = Some(self.stack.clone());
 
         // 転写キューに送信
         self.transcription_tx
             .send((
                 result,
+                options,
                 paste,
                 music_was_playing,
                 direct_input,
                 stack_for_transcription,
                 Some(self.ui_manager.clone()),
+                streamed,
             ))
             .map_err(|e| {
                 VoiceInputError::SystemError(format!(
@@ -187,9 +338,211 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
         })
     }
 
+    /// push-to-talk 停止処理
+    ///
+    /// 押下時間が `ptt_min_hold_ms` 未満の場合は誤タップとみなし、録音を破棄する。
+    /// push-to-talk で開始した録音がなければ何もしない（連続音声入力・CLI の録音は止めない）。
+    async fn handle_stop_ptt(&self) -> Result<IpcResp> {
+        if !self.ptt_active.replace(false) {
+            return Ok(IpcResp {
+                ok: true,
+                msg: "no push-to-talk recording; ignored".to_string(),
+            });
+        }
+
+        let elapsed = self
+            .recording
+            .borrow()
+            .elapsed()
+            .ok_or(VoiceInputError::RecordingNotStarted)?;
+        let min_hold_ms = self.recording.borrow().config().ptt_min_hold_ms;
+
+        if elapsed < Duration::from_millis(min_hold_ms) {
+            let recording = self.recording.clone();
+            recording.borrow().cancel_recording().await?;
+
+            // 一時停止した音楽を再開
+            let (_, _, _, music_was_playing) = self.recording.borrow().get_context_info()?;
+            if music_was_playing {
+                resume_apple_music();
+            }
+
+            return Ok(IpcResp {
+                ok: true,
+                msg: format!(
+                    "recording discarded (held {}ms < {}ms)",
+                    elapsed.as_millis(),
+                    min_hold_ms
+                ),
+            });
+        }
+
+        self.handle_stop().await
+    }
+
+    /// 連続音声入力開始処理
+    ///
+    /// マイクを開いたまま VAD で発話を区切り、セグメントごとに転写キューへ送る。
+    /// 自動停止タイマーは設定せず、`StopContinuous` まで録音を続ける。
+    async fn handle_start_continuous(
+        &self,
+        paste: bool,
+        prompt: Option<String>,
+        direct_input: bool,
+        language: Option<String>,
+        translate: Option<String>,
+        profile: Option<String>,
+    ) -> Result<IpcResp> {
+        if self.is_continuous_active() || self.recording.borrow().is_recording() {
+            return Err(VoiceInputError::RecordingAlreadyActive);
+        }
+        self.check_profile(profile.as_deref())?;
+
+        let final_prompt = prompt.or_else(|| get_selected_text().ok());
+
+        // Apple Musicを一時停止（再開は停止時にまとめて行う）
+        let media_control = self.media_control.clone();
+        let was_playing = media_control.borrow().pause_if_playing().await?;
+        self.recording.borrow().set_music_was_playing(was_playing)?;
+
+        play_start_sound();
+
+        let options = RecordingOptions {
+            prompt: final_prompt,
+            language,
+            translate,
+            profile,
+            paste,
+            direct_input,
+        };
+        let recording = self.recording.clone();
+        recording.borrow().start_recording(options).await?;
+
+        let (stop_tx, stop_rx) = oneshot::channel();
+        *self.continuous_stop.borrow_mut() = Some(stop_tx);
+        self.spawn_continuous_loop(stop_rx);
+
+        // インジケーター表示のためUIを起動
+        let ui_manager = self.ui_manager.clone();
+        if let Ok(mut manager) = ui_manager.try_borrow_mut() {
+            if let Err(e) = manager.start_ui().await {
+                eprintln!("UI process start failed (continuing without UI): {}", e);
+            } else {
+                drop(manager);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                if let Ok(manager) = ui_manager.try_borrow() {
+                    let _ = manager.notify(UiNotification::ContinuousModeChanged(true));
+                }
+            }
+        }
+
+        Ok(IpcResp {
+            ok: true,
+            msg: "continuous dictation started".to_string(),
+        })
+    }
+
+    /// 連続音声入力停止処理
+    async fn handle_stop_continuous(&self) -> Result<IpcResp> {
+        let not_active = IpcResp {
+            ok: false,
+            msg: "continuous dictation is not active".to_string(),
+        };
+        let Some(stop_tx) = self.continuous_stop.borrow_mut().take() else {
+            return Ok(not_active);
+        };
+
+        play_stop_sound();
+
+        // ループに停止を依頼し、残りの発話を送り終えるのを待つ
+        let (reply_tx, reply_rx) = oneshot::channel();
+        if stop_tx.send(reply_tx).is_err() {
+            return Ok(not_active);
+        }
+        let segments = reply_rx.await.unwrap_or(0);
+
+        Ok(IpcResp {
+            ok: true,
+            msg: format!(
+                "continuous dictation stopped; {} segment(s) queued",
+                segments
+            ),
+        })
+    }
+
+    /// 未定義のプロファイルが指定されていないか確認（録音前に弾く）
+    fn check_profile(&self, profile: Option<&str>) -> Result<()> {
+        match profile {
+            Some(name) if self.transcription.borrow().profile(name).is_none() => {
+                Err(VoiceInputError::UnknownProfile(name.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 連続音声入力ループが動作中か
+    fn is_continuous_active(&self) -> bool {
+        self.continuous_stop
+            .borrow()
+            .as_ref()
+            .is_some_and(|tx| !tx.is_closed())
+    }
+
+    /// 音声ファイルの文字起こし
+    ///
+    /// デコード後に 16kHz モノラルへリサンプルしてから転写し、テキストをそのまま返す。
+    async fn handle_transcribe_file(
+        &self,
+        path: String,
+        language: Option<String>,
+        translate: Option<String>,
+        profile: Option<String>,
+    ) -> Result<IpcResp> {
+        self.check_profile(profile.as_deref())?;
+
+        let path = PathBuf::from(path);
+        if !path.exists() {
+            return Err(VoiceInputError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        // デコードとリサンプルはCPU負荷が高いためブロッキングスレッドで実行
+        let (info, audio) = tokio::task::spawn_blocking(move || {
+            let decoded = decode_file(&path)?;
+            let audio = decoded.to_wav(TRANSCRIPTION_SAMPLE_RATE)?;
+            Ok::<_, AudioError>((decoded.info, audio))
+        })
+        .await
+        .map_err(|e| VoiceInputError::SystemError(format!("Decode task failed: {}", e)))??;
+        println!("Transcribing file ({})", info);
+
+        let transcription = self.transcription.clone();
+        let options = TranscriptionOptions {
+            language,
+            translate,
+            profile,
+            ..Default::default()
+        };
+        let transcript = transcription.borrow().transcribe(audio, options).await?;
+        if let Some(language) = &transcript.language {
+            println!("Detected language: {}", language);
+        }
+        if let Some(provider) = &transcript.provider {
+            println!("Transcribed by: {}", provider);
+        }
+
+        Ok(IpcResp {
+            ok: true,
+            msg: transcript.text,
+        })
+    }
+
     /// ステータス取得
     fn handle_status(&self) -> Result<IpcResp> {
-        let state = if self.recording.borrow().is_recording() {
+        let state = if self.is_continuous_active() {
+            "Continuous"
+        } else if self.recording.borrow().is_recording() {
             "Recording"
         } else {
             "Idle"
@@ -214,6 +567,79 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
         })
     }
 
+    /// キューに保存された転写を再実行（`QueueRetryReport` を JSON で返す）
+    async fn handle_queue_retry(&self, id: Option<String>) -> Result<IpcResp> {
+        let report = self.retry_queue(id.as_deref(), None).await?;
+        Ok(IpcResp::with_payload(&report)?)
+    }
+
+    /// キューの自動再実行（一時的なエラーで失敗し、再実行の時期が来たジョブだけ）
+    pub async fn auto_retry_queue(&self, interval: Duration) -> Result<QueueRetryReport> {
+        self.retry_queue(None, Some(interval)).await
+    }
+
+    async fn retry_queue(
+        &self,
+        id: Option<&str>,
+        auto_interval: Option<Duration>,
+    ) -> Result<QueueRetryReport> {
+        if self.queue_retry_active.replace(true) {
+            return Err(VoiceInputError::SystemError(
+                "Queue retry is already running".to_string(),
+            ));
+        }
+        let _reset = scopeguard::guard(self.queue_retry_active.clone(), |active| active.set(false));
+
+        retry_queued(
+            self.transcription.clone(),
+            Some(self.stack.clone()),
+            Some(self.ui_manager.clone()),
+            id,
+            auto_interval,
+        )
+        .await
+    }
+
+    /// 利用状況の集計（`UsageReport` を JSON で返す）
+    fn handle_usage(&self, days: Option<u32>) -> Result<IpcResp> {
+        let records = self.usage.open().load()?;
+        let report = UsageReport::build(
+            &records,
+            &self.usage.prices,
+            self.usage.budget,
+            chrono::Local::now().date_naive(),
+            days.unwrap_or(DEFAULT_USAGE_DAYS),
+        );
+        Ok(IpcResp::with_payload(&report)?)
+    }
+
+    /// テキストをパイプラインに通す（`PipelineReport` を JSON で返す）
+    async fn handle_pipeline_test(
+        &self,
+        text: String,
+        language: Option<String>,
+        translate: Option<String>,
+        profile: Option<String>,
+    ) -> Result<IpcResp> {
+        let options = TranscriptionOptions {
+            language,
+            translate,
+            profile,
+            ..Default::default()
+        };
+        let report = self
+            .transcription
+            .borrow()
+            .test_pipeline(&text, options)
+            .await?;
+        Ok(IpcResp::with_payload(&report)?)
+    }
+
+    /// デバイス詳細取得（`DeviceReport` を JSON で返す）
+    fn handle_list_devices_verbose(&self) -> Result<IpcResp> {
+        Ok(IpcResp::with_payload(&DeviceReport::query())?)
+    }
+
     /// ヘルスチェック
     async fn handle_health(&self) -> Result<IpcResp> {
         let mut ok = true;
@@ -227,34 +653,54 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
             lines.push("Input device: OK".to_string());
         }
 
-        // OpenAI APIチェック
-        match std::env::var("OPENAI_API_KEY") {
-            Ok(key) => {
-                lines.push("OPENAI_API_KEY: present".to_string());
-                let client = reqwest::Client::new();
-                match client
-                    .get("https://api.openai.com/v1/models")
-                    .bearer_auth(key)
-                    .send()
-                    .await
-                {
-                    Ok(resp) if resp.status().is_success() => {
-                        lines.push("OpenAI API: reachable".to_string());
-                    }
-                    Ok(resp) => {
-                        lines.push(format!("OpenAI API: fail({})", resp.status()));
-                        ok = false;
-                    }
-                    Err(e) => {
-                        lines.push(format!("OpenAI API: error({})", e));
-                        ok = false;
-                    }
+        // ローカル STT コマンドの場合は実行ファイルとモデルのみ確認
+        let provider = match SttProvider::from_env() {
+            Ok(provider) => provider,
+            Err(e) => {
+                lines.push(format!("STT provider: {}", e));
+                return Ok(IpcResp {
+                    ok: false,
+                    msg: lines.join("\n"),
+                });
+            }
+        };
+        if let Some(check) = provider.check_command() {
+            lines.push(format!("STT provider: {}", provider));
+            match check {
+                Ok(program) => lines.push(format!("STT command: {} (OK)", program)),
+                Err(e) => {
+                    lines.push(format!("STT command: {}", e));
+                    ok = false;
                 }
             }
-            Err(_) => {
-                lines.push("OPENAI_API_KEY: missing".to_string());
-                ok = false;
+            return Ok(IpcResp {
+                ok,
+                msg: lines.join("\n"),
+            });
+        }
+
+        // OpenAI APIチェック（OPENAI_BASE_URL 指定時は互換サーバー）
+        let config = OpenAiConfig::from_env();
+        lines.push(format!(
+            "OPENAI_API_KEY: {}",
+            match (&config.api_key, config.is_official()) {
+                (Some(_), _) => "present",
+                (None, true) => "missing",
+                (None, false) => "not set (custom endpoint)",
             }
+        ));
+        if !config.is_official() {
+            lines.push(format!("OPENAI_BASE_URL: {}", config.base_url));
+        }
+        match OpenAiClient::with_config(config) {
+            Ok(client) => match client.check_connection().await {
+                Ok(()) => lines.push("OpenAI API: reachable".to_string()),
+                Err(e) => {
+                    lines.push(format!("OpenAI API: {}", e));
+                    ok = false;
+                }
+            },
+            Err(_) => ok = false,
         }
 
         Ok(IpcResp {
@@ -374,12 +820,279 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
         })
     }
 
+    /// 連続音声入力ループを起動
+    ///
+    /// 一定間隔で録音バッファを取り出して VAD に通し、確定した発話を転写キューへ送る。
+    /// 停止要求を受けると残りの音声を処理してから録音を終了し、送ったセグメント数を返す。
+    fn spawn_continuous_loop(&self, mut stop_rx: oneshot::Receiver<oneshot::Sender<usize>>) {
+        let recording = self.recording.clone();
+        let stack = self.stack.clone();
+        let ui_manager = self.ui_manager.clone();
+        let tx = self.transcription_tx.clone();
+        let vad_config = recording.borrow().config().vad.clone();
+
+        spawn_local(async move {
+            let mut vad: Option<VoiceActivityDetector> = None;
+            let mut queued = 0usize;
+            let mut ticker = tokio::time::interval(CONTINUOUS_POLL_INTERVAL);
+
+            let reply = loop {
+                tokio::select! {
+                    reply = &mut stop_rx => break reply.ok(),
+                    _ = ticker.tick() => {
+                        // 録音が他の経路で止められた場合は終了
+                        let Some(chunk) = recording.borrow().drain_samples() else {
+                            break None;
+                        };
+                        let vad = vad.get_or_insert_with(|| {
+                            VoiceActivityDetector::new(vad_config.clone(), chunk.sample_rate, chunk.channels)
+                        });
+                        for segment in vad.push(&chunk.samples) {
+                            queued += Self::queue_segment(&recording, &stack, &ui_manager, &tx, &segment, chunk.sample_rate, chunk.channels);
+                        }
+                    }
+                }
+            };
+
+            // 残りの音声を処理し、話し途中の発話も送る
+            if let Some(chunk) = recording.borrow().drain_samples() {
+                let vad = vad.get_or_insert_with(|| {
+                    VoiceActivityDetector::new(
+                        vad_config.clone(),
+                        chunk.sample_rate,
+                        chunk.channels,
+                    )
+                });
+                let mut segments = vad.push(&chunk.samples);
+                segments.extend(vad.flush());
+                for segment in segments {
+                    queued += Self::queue_segment(
+                        &recording,
+                        &stack,
+                        &ui_manager,
+                        &tx,
+                        &segment,
+                        chunk.sample_rate,
+                        chunk.channels,
+                    );
+                }
+            }
+
+            // セグメント化済みなので残りのバッファは破棄
+            if recording.borrow().is_recording() {
+                if let Err(e) = recording.borrow().cancel_recording().await {
+                    eprintln!("Failed to stop continuous recording: {}", e);
+                }
+            }
+            let (_, _, _, music_was_playing) = recording
+                .borrow()
+                .get_context_info()
+                .unwrap_or((None, false, false, false));
+            if music_was_playing {
+                resume_apple_music();
+            }
+
+            if let Ok(mut manager) = ui_manager.try_borrow_mut() {
+                let _ = manager.notify(UiNotification::ContinuousModeChanged(false));
+                if !stack.borrow().is_stack_mode_enabled() {
+                    if let Err(e) = manager.stop_ui() {
+                        eprintln!("UI process stop failed: {}", e);
+                    }
+                }
+            }
+
+            println!(
+                "Continuous dictation finished ({} segment(s) queued)",
+                queued
+            );
+            if let Some(reply) = reply {
+                let _ = reply.send(queued);
+            }
+        });
+    }
+
+    /// 発話セグメントをWAVにして転写キューへ送る（送れた数を返す）
+    fn queue_segment(
+        recording: &Rc<RefCell<RecordingService<T>>>,
+        stack: &Rc<RefCell<StackService>>,
+        ui_manager: &Rc<RefCell<UiProcessManager>>,
+        tx: &mpsc::UnboundedSender<TranscriptionMessage>,
+        samples: &[i16],
+        sample_rate: u32,
+        channels: u16,
+    ) -> usize {
+        let wav = match CpalAudioBackend::combine_wav_data(samples, sample_rate, channels) {
+            Ok(wav) => wav,
+            Err(e) => {
+                eprintln!("Failed to encode continuous segment: {}", e);
+                return 0;
+            }
+        };
+        let samples_per_sec = (sample_rate as u64 * channels as u64).max(1);
+        let result = RecordingResult {
+            audio_data: AudioData(wav).into(),
+            duration_ms: samples.len() as u64 * 1000 / samples_per_sec,
+        };
+
+        let (_, paste, direct_input, _) = recording
+            .borrow()
+            .get_context_info()
+            .unwrap_or((None, false, false, false));
+        let options = recording
+            .borrow()
+            .transcription_options()
+            .unwrap_or_default();
+        let stack_for_transcription = Some(stack.clone());
+
+        // 音楽の再開は連続音声入力の終了時に行うため resume_music は false
+        match tx.send((
+            result,
+            options,
+            paste,
+            false,
+            direct_input,
+            stack_for_transcription,
+            Some(ui_manager.clone()),
+            None,
+        )) {
+            Ok(()) => 1,
+            Err(e) => {
+                eprintln!("Failed to send to transcription queue: {}", e);
+                0
+            }
+        }
+    }
+
+    /// ストリーミング転写ループを起動
+    ///
+    /// 一定間隔で録音バッファを取り出してストリーミング転写に送り、途中結果を UI に表示する。
+    /// 接続中に取り出した音声は保持しておき、接続後にまとめて送る。
+    /// 停止要求を受けると残りの音声を送って録音全体を返し、その後に確定結果を待つ。
+    fn spawn_stream_loop(&self) {
+        let Some(client) = self.streaming.clone() else {
+            return;
+        };
+        let recording = self.recording.clone();
+        let ui_manager = self.ui_manager.clone();
+        let options = recording
+            .borrow()
+            .transcription_options()
+            .unwrap_or_default();
+        // プロファイル・既定の言語をストリーミングのセッションにも反映
+        let options = self
+            .transcription
+            .borrow()
+            .resolve_options(options.clone())
+            .unwrap_or(options);
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        *self.active_stream.borrow_mut() = Some(stop_tx);
+
+        spawn_local(async move {
+            let (partial_tx, mut partial_rx) = mpsc::unbounded_channel();
+            let open = client.open(&options, partial_tx);
+            tokio::pin!(open);
+            let mut opening = true;
+            let mut stream: Option<Box<dyn TranscriptionStream>> = None;
+            let mut buffer = StreamBuffer::default();
+            let mut ticker = tokio::time::interval(CONTINUOUS_POLL_INTERVAL);
+
+            let reply = loop {
+                tokio::select! {
+                    reply = &mut stop_rx => break reply.ok(),
+                    opened = &mut open, if opening => {
+                        opening = false;
+                        match opened {
+                            Ok(opened) => stream = Some(opened),
+                            Err(e) => eprintln!("Streaming transcription unavailable: {}", e),
+                        }
+                    }
+                    Some(partial) = partial_rx.recv() => {
+                        if let Ok(manager) = ui_manager.try_borrow() {
+                            let _ = manager.notify(UiNotification::PartialTranscript(partial));
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        // 録音が破棄された場合は終了
+                        let Some(chunk) = recording.borrow().drain_samples() else {
+                            break None;
+                        };
+                        buffer.push(chunk);
+                        buffer.flush(&mut stream).await;
+                    }
+                }
+            };
+
+            if let Some(reply) = reply {
+                if let Some(chunk) = recording.borrow().drain_samples() {
+                    buffer.push(chunk);
+                }
+                let (result_tx, result_rx) = oneshot::channel();
+                let _ = reply.send(StreamedRecording {
+                    samples: buffer.samples.clone(),
+                    sample_rate: buffer.sample_rate,
+                    channels: buffer.channels,
+                    transcript: result_rx,
+                });
+
+                if opening {
+                    match open.await {
+                        Ok(opened) => stream = Some(opened),
+                        Err(e) => eprintln!("Streaming transcription unavailable: {}", e),
+                    }
+                }
+                buffer.flush(&mut stream).await;
+                let result = match stream {
+                    Some(stream) => stream.finish().await,
+                    None => Err(VoiceInputError::TranscriptionFailed(
+                        "streaming session is not available".to_string(),
+                    )),
+                };
+                let _ = result_tx.send(result);
+            }
+
+            if let Ok(manager) = ui_manager.try_borrow() {
+                let _ = manager.notify(UiNotification::PartialTranscript(String::new()));
+            }
+        });
+    }
+
+    /// ストリーミング転写ループに停止を要求し、録音中に送った音声を受け取る
+    async fn stop_stream(
+        active_stream: &Rc<RefCell<Option<StreamStop>>>,
+    ) -> Option<StreamedRecording> {
+        let stop_tx = active_stream.borrow_mut().take()?;
+        let (reply_tx, reply_rx) = oneshot::channel();
+        stop_tx.send(reply_tx).ok()?;
+        reply_rx.await.ok()
+    }
+
+    /// ストリーミングで送った音声を転写キュー用の録音結果に反映し、確定結果の受信側を返す
+    fn attach_streamed(
+        result: &mut RecordingResult,
+        streamed: Option<StreamedRecording>,
+    ) -> Option<StreamedTranscript> {
+        let streamed = streamed?;
+        // 録音バッファは逐次取り出しているため、停止時の音声は送信済みの音声で置き換える
+        if !streamed.samples.is_empty() {
+            match CpalAudioBackend::combine_wav_data(
+                &streamed.samples,
+                streamed.sample_rate,
+                streamed.channels,
+            ) {
+                Ok(wav) => result.audio_data = AudioData(wav).into(),
+                Err(e) => eprintln!("Failed to encode streamed recording: {}", e),
+            }
+        }
+        Some(streamed.transcript)
+    }
+
     /// 自動停止タイマーをセットアップ
     fn setup_auto_stop_timer(&self) {
         let recording = self.recording.clone();
         let stack = self.stack.clone();
         let ui_manager = self.ui_manager.clone();
         let tx = self.transcription_tx.clone();
+        let active_stream = self.active_stream.clone();
         let max_secs = recording.borrow().config().max_duration_secs;
 
         spawn_local(async move {
@@ -394,23 +1107,24 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
                             println!("Auto-stop timer triggered after {}s", max_secs);
                             play_stop_sound();
 
-                            if let Ok(result) = recording.borrow().stop_recording().await {
+                            let streamed = Self::stop_stream(&active_stream).await;
+                            if let Ok(mut result) = recording.borrow().stop_recording().await {
+                                let streamed = Self::attach_streamed(&mut result, streamed);
                                 let (_, paste, direct_input, music_was_playing) =
                                     recording.borrow().get_context_info().unwrap_or((None, false, false, false));
+                                let options = recording.borrow().transcription_options().unwrap_or_default();
 
-                                let stack_for_transcription = if stack.borrow().is_stack_mode_enabled() {
-                                    Some(stack.clone())
-                                } else {
-                                    None
-                                };
+                                let stack_for_transcription = Some(stack.clone());
 
                                 let _ = tx.send((
                                     result,
+                                    options,
                                     paste,
                                     music_was_playing,
                                     direct_input,
                                     stack_for_transcription,
                                     Some(ui_manager.clone()),
+                                    streamed,
                                 ));
                             }
                         }
@@ -426,3 +1140,114 @@ impl<T: AudioBackend + 'static> CommandHandler<T> {
         });
     }
 }
+
+/// ストリーミング転写に送る音声のバッファ
+///
+/// 録音全体を保持し、未送信の部分だけをストリームに送る。
+#[derive(Default)]
+struct StreamBuffer {
+    samples: Vec<i16>,
+    sample_rate: u32,
+    channels: u16,
+    /// 送信済みのサンプル数
+    sent: usize,
+}
+
+impl StreamBuffer {
+    fn push(&mut self, chunk: PcmChunk) {
+        self.sample_rate = chunk.sample_rate;
+        self.channels = chunk.channels;
+        self.samples.extend(chunk.samples);
+    }
+
+    /// 未送信の音声を送る（送信に失敗したストリームは破棄し、通常の転写に任せる）
+    async fn flush(&mut self, stream: &mut Option<Box<dyn TranscriptionStream>>) {
+        let Some(active) = stream.as_mut() else {
+            return;
+        };
+        if self.sent == self.samples.len() {
+            return;
+        }
+        let chunk = PcmChunk {
+            samples: self.samples[self.sent..].to_vec(),
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+        };
+        match active.send_audio(&chunk).await {
+            Ok(()) => self.sent = self.samples.len(),
+            Err(e) => {
+                eprintln!("Streaming transcription interrupted: {}", e);
+                *stream = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::service_container::test_helpers::TestServiceContainerBuilder;
+
+    fn ptt_start() -> IpcCmd {
+        IpcCmd::StartPtt {
+            paste: false,
+            prompt: Some(String::new()),
+            direct_input: false,
+            language: None,
+            translate: None,
+            profile: None,
+        }
+    }
+
+    /// push-to-talk 以外（CLI・連続音声入力）で録音を開始
+    async fn start_recording<T: AudioBackend + 'static>(handler: &CommandHandler<T>) {
+        let options = RecordingOptions {
+            prompt: None,
+            language: None,
+            translate: None,
+            profile: None,
+            paste: false,
+            direct_input: false,
+        };
+        handler
+            .recording
+            .borrow()
+            .start_recording(options)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ptt_release_does_not_stop_continuous_dictation() {
+        let _ = crate::utils::config::EnvConfig::init();
+        let container = TestServiceContainerBuilder::new().build().await.unwrap();
+        let handler = container.command_handler.borrow();
+
+        // 連続音声入力中の状態を再現
+        start_recording(&handler).await;
+        let (stop_tx, _stop_rx) = oneshot::channel();
+        *handler.continuous_stop.borrow_mut() = Some(stop_tx);
+
+        // キーを押しても開始できず、離しても連続音声入力の録音は続く
+        assert!(matches!(
+            handler.handle(ptt_start()).await,
+            Err(VoiceInputError::RecordingAlreadyActive)
+        ));
+        let resp = handler.handle(IpcCmd::StopPtt).await.unwrap();
+        assert_eq!(resp.msg, "no push-to-talk recording; ignored");
+        assert!(handler.recording.borrow().is_recording());
+        assert!(handler.is_continuous_active());
+    }
+
+    #[tokio::test]
+    async fn test_ptt_release_does_not_stop_cli_recording() {
+        let _ = crate::utils::config::EnvConfig::init();
+        let container = TestServiceContainerBuilder::new().build().await.unwrap();
+        let handler = container.command_handler.borrow();
+
+        start_recording(&handler).await;
+
+        handler.handle(IpcCmd::StopPtt).await.unwrap();
+        assert!(handler.recording.borrow().is_recording());
+    }
+}
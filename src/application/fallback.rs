@@ -0,0 +1,218 @@
+//! 転写プロバイダーのフォールバックチェーン
+//!
+//! # 責任
+//! - 複数の転写クライアントを優先順に試行
+//! - `VoiceInputError::is_provider_failure` に該当する失敗時のみ次のプロバイダーへ切り替え
+//! - 転写結果にどのプロバイダーが使われたかを記録
+
+use async_trait::async_trait;
+
+use crate::application::traits::TranscriptionClient;
+use crate::application::{Transcript, TranscriptionOptions};
+use crate::error::{Result, VoiceInputError};
+use crate::infrastructure::audio::cpal_backend::AudioData;
+
+/// 名前付きの転写プロバイダー
+struct Provider {
+    name: String,
+    client: Box<dyn TranscriptionClient>,
+}
+
+/// 優先順に転写プロバイダーを試すクライアント
+///
+/// 先頭のプロバイダーが障害などで失敗した場合、録音を失わないよう
+/// 多少精度が落ちても次のプロバイダーで転写する。
+#[derive(Default)]
+pub struct FallbackTranscriptionClient {
+    providers: Vec<Provider>,
+}
+
+impl FallbackTranscriptionClient {
+    /// 空のチェーンを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// チェーンの末尾にプロバイダーを追加
+    pub fn with_provider(
+        mut self,
+        name: impl Into<String>,
+        client: Box<dyn TranscriptionClient>,
+    ) -> Self {
+        self.providers.push(Provider {
+            name: name.into(),
+            client,
+        });
+        self
+    }
+
+    /// プロバイダー名の一覧（優先順）
+    pub fn provider_names(&self) -> Vec<&str> {
+        self.providers.iter().map(|p| p.name.as_str()).collect()
+    }
+}
+
+#[async_trait]
+impl TranscriptionClient for FallbackTranscriptionClient {
+    async fn transcribe(
+        &self,
+        audio: AudioData,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcript> {
+        let mut last_error = None;
+
+        for (index, provider) in self.providers.iter().enumerate() {
+            match provider.client.transcribe(audio.clone(), options).await {
+                Ok(transcript) => {
+                    let mut transcript = transcript.with_provider(provider.name.clone());
+                    if index > 0 {
+                        println!("✅ transcribed with fallback provider {}", provider.name);
+                        transcript.fallback = true;
+                    }
+                    return Ok(transcript);
+                }
+                Err(error) if error.is_provider_failure() => {
+                    if let Some(next) = self.providers.get(index + 1) {
+                        eprintln!(
+                            "⚠️  provider {} failed ({}), falling back to {}",
+                            provider.name, error, next.name
+                        );
+                    }
+                    last_error = Some(error);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            VoiceInputError::TranscriptionFailed("no transcription provider configured".to_string())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// 固定の結果を返し、呼び出し回数を記録するモック
+    struct StubClient {
+        result: fn() -> Result<Transcript>,
+        calls: Arc<Mutex<usize>>,
+    }
+
+    impl StubClient {
+        fn boxed(result: fn() -> Result<Transcript>) -> (Box<Self>, Arc<Mutex<usize>>) {
+            let calls = Arc::new(Mutex::new(0));
+            (
+                Box::new(Self {
+                    result,
+                    calls: calls.clone(),
+                }),
+                calls,
+            )
+        }
+    }
+
+    #[async_trait]
+    impl TranscriptionClient for StubClient {
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            _options: &TranscriptionOptions,
+        ) -> Result<Transcript> {
+            *self.calls.lock().unwrap() += 1;
+            (self.result)()
+        }
+    }
+
+    async fn transcribe(client: &FallbackTranscriptionClient) -> Result<Transcript> {
+        client
+            .transcribe(AudioData(Vec::new()), &TranscriptionOptions::default())
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_uses_first_provider_when_available() {
+        let (cloud, _) = StubClient::boxed(|| Ok(Transcript::new("cloud")));
+        let (local, local_calls) = StubClient::boxed(|| Ok(Transcript::new("local")));
+        let client = FallbackTranscriptionClient::new()
+            .with_provider("openai", cloud)
+            .with_provider("whisper-cpp", local);
+
+        let transcript = transcribe(&client).await.unwrap();
+        assert_eq!(transcript.text, "cloud");
+        assert_eq!(transcript.provider.as_deref(), Some("openai"));
+        assert!(!transcript.fallback);
+        assert_eq!(*local_calls.lock().unwrap(), 0);
+        assert_eq!(client.provider_names(), vec!["openai", "whisper-cpp"]);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_on_provider_failure() {
+        let (cloud, _) =
+            StubClient::boxed(|| Err(VoiceInputError::NetworkError("offline".to_string())));
+        let (budget, _) = StubClient::boxed(|| {
+            Err(VoiceInputError::ApiStatus {
+                status: 402,
+                message: "quota exceeded".to_string(),
+                retry_after: None,
+            })
+        });
+        let (local, _) = StubClient::boxed(|| Ok(Transcript::new("local")));
+        let client = FallbackTranscriptionClient::new()
+            .with_provider("openai", cloud)
+            .with_provider("openai:whisper-1", budget)
+            .with_provider("whisper-cpp", local);
+
+        let transcript = transcribe(&client).await.unwrap();
+        assert_eq!(transcript.text, "local");
+        assert_eq!(transcript.provider.as_deref(), Some("whisper-cpp"));
+        assert!(transcript.fallback);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_fall_back_on_bad_audio() {
+        let (cloud, _) = StubClient::boxed(|| {
+            Err(VoiceInputError::ApiStatus {
+                status: 400,
+                message: "invalid file".to_string(),
+                retry_after: None,
+            })
+        });
+        let (local, local_calls) = StubClient::boxed(|| Ok(Transcript::new("local")));
+        let client = FallbackTranscriptionClient::new()
+            .with_provider("openai", cloud)
+            .with_provider("whisper-cpp", local);
+
+        let err = transcribe(&client).await.unwrap_err();
+        assert!(matches!(
+            err,
+            VoiceInputError::ApiStatus { status: 400, .. }
+        ));
+        assert_eq!(*local_calls.lock().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_returns_last_error_when_all_fail() {
+        let (cloud, _) =
+            StubClient::boxed(|| Err(VoiceInputError::NetworkError("offline".to_string())));
+        let (local, _) = StubClient::boxed(|| {
+            Err(VoiceInputError::TranscriptionFailed(
+                "whisper-cli not found".to_string(),
+            ))
+        });
+        let client = FallbackTranscriptionClient::new()
+            .with_provider("openai", cloud)
+            .with_provider("whisper-cpp", local);
+
+        let err = transcribe(&client).await.unwrap_err();
+        assert!(err.to_string().contains("whisper-cli not found"));
+
+        assert!(
+            transcribe(&FallbackTranscriptionClient::new())
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! 転写リクエストの利用記録と予算管理
+//!
+//! # 責任
+//! - リクエスト・ストリーミングセッションごとの音声の長さ・送信バイト数・応答時間の記録
+//! - 月間予算（警告・上限）の確認
+
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use chrono::Local;
+use tokio::sync::mpsc;
+
+use crate::application::traits::{
+    StreamingTranscriptionClient, TranscriptionClient, TranscriptionStream,
+};
+use crate::application::{Transcript, TranscriptionOptions};
+use crate::domain::usage::{PriceTable, UsageBudget, UsageRecord};
+use crate::error::{Result, VoiceInputError};
+use crate::infrastructure::audio::PcmChunk;
+use crate::infrastructure::audio::cpal_backend::AudioData;
+use crate::infrastructure::usage::UsageLog;
+
+/// 利用記録と予算の確認（リクエスト・ストリーミングセッションで共通）
+#[derive(Clone)]
+struct UsageMeter {
+    provider: String,
+    model: String,
+    log: UsageLog,
+    prices: PriceTable,
+    budget: UsageBudget,
+    /// 振り分けルール名（振り分け先として作成した場合）
+    route: Option<String>,
+}
+
+impl UsageMeter {
+    fn new(provider: String, model: String, log: UsageLog) -> Self {
+        Self {
+            provider,
+            model,
+            log,
+            prices: PriceTable::default(),
+            budget: UsageBudget::default(),
+            route: None,
+        }
+    }
+
+    fn paid(&self) -> bool {
+        self.prices.per_minute(&self.provider, &self.model) > 0.0
+    }
+
+    /// 今月の推定料金（予算が設定されていない場合は記録を読まない）
+    fn month_cost(&self) -> f64 {
+        if self.budget == UsageBudget::default() {
+            return 0.0;
+        }
+        match self.log.load() {
+            Ok(records) => self.prices.month_cost(&records, Local::now().date_naive()),
+            Err(e) => {
+                eprintln!("Failed to read usage log: {}", e);
+                0.0
+            }
+        }
+    }
+
+    /// 上限に達していれば `BudgetExceeded` を返し、そうでなければ今月の推定料金を返す
+    fn check_budget(&self) -> Result<f64> {
+        let paid = self.paid();
+        let spent = if paid { self.month_cost() } else { 0.0 };
+        if let Some(limit) = self.budget.hard_usd.filter(|limit| paid && spent >= *limit) {
+            return Err(VoiceInputError::BudgetExceeded { spent, limit });
+        }
+        Ok(spent)
+    }
+
+    /// 利用を記録し、今回の利用で警告額を超えた場合だけ警告する
+    fn record(&self, spent: f64, audio_secs: f64, bytes: u64, latency_ms: u64, success: bool) {
+        let record = UsageRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            provider: self.provider.clone(),
+            model: self.model.clone(),
+            audio_secs,
+            bytes,
+            latency_ms,
+            success,
+            route: self.route.clone(),
+        };
+        if let Err(e) = self.log.append(&record) {
+            eprintln!("Failed to record usage: {}", e);
+        }
+
+        if let Some(soft) = self.budget.soft_usd {
+            let total = spent + self.prices.cost(&record);
+            if self.paid() && spent < soft && total >= soft {
+                eprintln!(
+                    "⚠️  Monthly transcription cost ${:.2} exceeded the soft budget ${:.2}",
+                    total, soft
+                );
+            }
+        }
+    }
+}
+
+/// 利用状況を記録する転写クライアント
+///
+/// 任意の `TranscriptionClient` を包み、リクエストごとに `UsageLog` へ記録する。
+/// 有料のモデル（料金表で 0 より大きい）では、今月の推定料金が上限に達していれば
+/// リクエストせずに `BudgetExceeded` を返す（フォールバック先の無料プロバイダーに切り替わる）。
+pub struct MeteredTranscriptionClient {
+    inner: Box<dyn TranscriptionClient>,
+    meter: UsageMeter,
+}
+
+impl MeteredTranscriptionClient {
+    /// 新しいクライアントを作成
+    pub fn new(
+        inner: Box<dyn TranscriptionClient>,
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        log: UsageLog,
+    ) -> Self {
+        Self {
+            inner,
+            meter: UsageMeter::new(provider.into(), model.into(), log),
+        }
+    }
+
+    /// 料金表を設定
+    pub fn with_prices(mut self, prices: PriceTable) -> Self {
+        self.meter.prices = prices;
+        self
+    }
+
+    /// 月間予算を設定
+    pub fn with_budget(mut self, budget: UsageBudget) -> Self {
+        self.meter.budget = budget;
+        self
+    }
+
+    /// 記録に残す振り分けルール名を設定
+    pub fn with_route(mut self, route: Option<String>) -> Self {
+        self.meter.route = route;
+        self
+    }
+}
+
+#[async_trait]
+impl TranscriptionClient for MeteredTranscriptionClient {
+    async fn transcribe(
+        &self,
+        audio: AudioData,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcript> {
+        let spent = self.meter.check_budget()?;
+
+        let bytes = audio.0.len() as u64;
+        let audio_secs = audio
+            .parse_wav()
+            .map(|wav| wav.duration().as_secs_f64())
+            .unwrap_or(0.0);
+        let started = Instant::now();
+        let result = self.inner.transcribe(audio, options).await;
+
+        self.meter.record(
+            spent,
+            audio_secs,
+            bytes,
+            started.elapsed().as_millis() as u64,
+            result.is_ok(),
+        );
+        result
+    }
+}
+
+/// 利用状況を記録するストリーミング転写クライアント
+///
+/// セッションごとに送った音声の長さ・バイト数（16bit PCM 換算）と、停止から確定結果までの
+/// 時間を記録する。有料のモデルで今月の推定料金が上限に達していればセッションを開かない
+/// （録音全体が通常の転写に回り、フォールバック先の無料プロバイダーに切り替わる）。
+pub struct MeteredStreamingClient {
+    inner: Box<dyn StreamingTranscriptionClient>,
+    meter: UsageMeter,
+}
+
+impl MeteredStreamingClient {
+    /// 新しいクライアントを作成
+    pub fn new(
+        inner: Box<dyn StreamingTranscriptionClient>,
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        log: UsageLog,
+    ) -> Self {
+        Self {
+            inner,
+            meter: UsageMeter::new(provider.into(), model.into(), log),
+        }
+    }
+
+    /// 料金表を設定
+    pub fn with_prices(mut self, prices: PriceTable) -> Self {
+        self.meter.prices = prices;
+        self
+    }
+
+    /// 月間予算を設定
+    pub fn with_budget(mut self, budget: UsageBudget) -> Self {
+        self.meter.budget = budget;
+        self
+    }
+}
+
+#[async_trait]
+impl StreamingTranscriptionClient for MeteredStreamingClient {
+    async fn open(
+        &self,
+        options: &TranscriptionOptions,
+        partials: mpsc::UnboundedSender<String>,
+    ) -> Result<Box<dyn TranscriptionStream>> {
+        let spent = self.meter.check_budget()?;
+        let inner = self.inner.open(options, partials).await?;
+        Ok(Box::new(MeteredStream {
+            inner,
+            meter: self.meter.clone(),
+            spent,
+            audio_secs: 0.0,
+            bytes: 0,
+        }))
+    }
+}
+
+/// 送った音声を数えるストリーミングセッション
+struct MeteredStream {
+    inner: Box<dyn TranscriptionStream>,
+    meter: UsageMeter,
+    /// セッション開始時の今月の推定料金
+    spent: f64,
+    audio_secs: f64,
+    bytes: u64,
+}
+
+#[async_trait]
+impl TranscriptionStream for MeteredStream {
+    async fn send_audio(&mut self, chunk: &PcmChunk) -> Result<()> {
+        self.inner.send_audio(chunk).await?;
+        let per_sec = (chunk.sample_rate as f64 * chunk.channels as f64).max(1.0);
+        self.audio_secs += chunk.samples.len() as f64 / per_sec;
+        self.bytes += chunk.samples.len() as u64 * 2;
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> Result<Transcript> {
+        let started = Instant::now();
+        let result = self.inner.finish().await;
+        self.meter.record(
+            self.spent,
+            self.audio_secs,
+            self.bytes,
+            started.elapsed().as_millis() as u64,
+            result.is_ok(),
+        );
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::service_container::test_helpers::MockTranscriptionClient;
+    use crate::infrastructure::audio::CpalAudioBackend;
+    use tempfile::TempDir;
+
+    /// 1 分間の 16kHz モノラル無音 WAV
+    fn one_minute() -> AudioData {
+        let samples = vec![0i16; 16_000 * 60];
+        AudioData(CpalAudioBackend::combine_wav_data(&samples, 16_000, 1).unwrap())
+    }
+
+    fn client(log: &UsageLog, model: &str) -> MeteredTranscriptionClient {
+        MeteredTranscriptionClient::new(
+            Box::new(MockTranscriptionClient::new("ok")),
+            "openai",
+            model,
+            log.clone(),
+        )
+        .with_budget(UsageBudget {
+            soft_usd: Some(0.005),
+            hard_usd: Some(0.01),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_records_usage() {
+        let tmp = TempDir::new().unwrap();
+        let log = UsageLog::new(tmp.path().join("usage.jsonl"));
+
+        client(&log, "whisper-1")
+            .transcribe(one_minute(), &TranscriptionOptions::default())
+            .await
+            .unwrap();
+
+        let records = log.load().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].provider, "openai");
+        assert_eq!(records[0].model, "whisper-1");
+        assert!((records[0].audio_secs - 60.0).abs() < 0.01);
+        assert_eq!(records[0].bytes, 16_000 * 2 * 60 + 44);
+        assert!(records[0].success);
+    }
+
+    #[tokio::test]
+    async fn test_hard_budget_blocks_paid_models() {
+        let tmp = TempDir::new().unwrap();
+        let log = UsageLog::new(tmp.path().join("usage.jsonl"));
+        let whisper = client(&log, "whisper-1");
+
+        // $0.006 + $0.006 で上限 $0.01 に達する
+        for _ in 0..2 {
+            whisper
+                .transcribe(one_minute(), &TranscriptionOptions::default())
+                .await
+                .unwrap();
+        }
+        let err = whisper
+            .transcribe(one_minute(), &TranscriptionOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VoiceInputError::BudgetExceeded { limit, .. } if limit == 0.01));
+        assert!(err.is_provider_failure());
+        assert_eq!(log.load().unwrap().len(), 2);
+
+        // 料金表にないモデル（無料扱い）は止めない
+        client(&log, "local-whisper")
+            .transcribe(one_minute(), &TranscriptionOptions::default())
+            .await
+            .unwrap();
+    }
+
+    /// 音声を受け取って固定の結果を返すストリーミングクライアント
+    struct StubStreaming;
+
+    struct StubStream;
+
+    #[async_trait]
+    impl StreamingTranscriptionClient for StubStreaming {
+        async fn open(
+            &self,
+            _options: &TranscriptionOptions,
+            _partials: mpsc::UnboundedSender<String>,
+        ) -> Result<Box<dyn TranscriptionStream>> {
+            Ok(Box::new(StubStream))
+        }
+    }
+
+    #[async_trait]
+    impl TranscriptionStream for StubStream {
+        async fn send_audio(&mut self, _chunk: &PcmChunk) -> Result<()> {
+            Ok(())
+        }
+
+        async fn finish(self: Box<Self>) -> Result<Transcript> {
+            Ok(Transcript::new("ok"))
+        }
+    }
+
+    /// 1 分間の音声を 2 回に分けて送るセッション
+    async fn stream_one_minute(client: &MeteredStreamingClient) -> Result<Transcript> {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut stream = client.open(&TranscriptionOptions::default(), tx).await?;
+        for _ in 0..2 {
+            let chunk = PcmChunk {
+                samples: vec![0; 16_000 * 30],
+                sample_rate: 16_000,
+                channels: 1,
+            };
+            stream.send_audio(&chunk).await?;
+        }
+        stream.finish().await
+    }
+
+    #[tokio::test]
+    async fn test_streaming_sessions_are_metered() {
+        let tmp = TempDir::new().unwrap();
+        let log = UsageLog::new(tmp.path().join("usage.jsonl"));
+        let client = MeteredStreamingClient::new(
+            Box::new(StubStreaming),
+            "openai",
+            "whisper-1",
+            log.clone(),
+        )
+        .with_budget(UsageBudget {
+            soft_usd: None,
+            hard_usd: Some(0.01),
+        });
+
+        // $0.006 + $0.006 で上限 $0.01 に達する
+        for _ in 0..2 {
+            stream_one_minute(&client).await.unwrap();
+        }
+        let records = log.load().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].model, "whisper-1");
+        assert!((records[0].audio_secs - 60.0).abs() < 0.01);
+        assert_eq!(records[0].bytes, 16_000 * 2 * 60);
+        assert!(records[0].success);
+
+        // 上限に達した後はセッションを開かない
+        let err = stream_one_minute(&client).await.unwrap_err();
+        assert!(matches!(err, VoiceInputError::BudgetExceeded { .. }));
+        assert_eq!(log.load().unwrap().len(), 2);
+    }
+}
@@ -1,18 +1,28 @@
 pub mod command_handler;
+pub mod fallback;
 pub mod media_control_service;
+pub mod metered;
+pub mod pipeline;
 pub mod recording_service;
+pub mod retry;
+pub mod routing;
 pub mod service_container;
 pub mod stack_service;
 pub mod traits;
 pub mod transcription_service;
 pub mod transcription_worker;
 
-pub use command_handler::{CommandHandler, TranscriptionMessage};
+pub use command_handler::{CommandHandler, StreamedTranscript, TranscriptionMessage};
+pub use fallback::FallbackTranscriptionClient;
 pub use media_control_service::MediaControlService;
+pub use metered::{MeteredStreamingClient, MeteredTranscriptionClient};
+pub use pipeline::{ProcessContext, TextPipeline};
 pub use recording_service::{
     RecordingConfig, RecordingContext, RecordingOptions, RecordingService, RecordingState,
 };
+pub use retry::{RetryEvent, RetryPolicy, RetryingTranscriptionClient};
+pub use routing::RoutingTranscriptionClient;
 pub use service_container::{AppConfig, ServiceContainer};
 pub use stack_service::{StackService, StackServiceError, UserFeedback};
-pub use transcription_service::{TranscriptionOptions, TranscriptionService};
-pub use transcription_worker::{handle_transcription, spawn_transcription_worker};
+pub use transcription_service::{Transcript, TranscriptionOptions, TranscriptionService};
+pub use transcription_worker::{handle_transcription, retry_queued, spawn_transcription_worker};
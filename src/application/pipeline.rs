@@ -0,0 +1,436 @@
+//! 転写後のテキスト処理パイプライン
+//!
+//! # 責任
+//! - 設定された順に `TextProcessor` を適用し、段ごとの処理時間と結果を記録
+//! - 段ごとのプロファイル指定による適用・スキップの判定
+//! - 組み込みの段（辞書変換・書き直し・翻訳・話し言葉のコマンド変換・表記の正規化・フィラーの除去）の提供
+//!
+//! 段が失敗した場合は警告を出し、その段の適用前のテキストのまま次の段へ進む。
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::application::traits::{Rewriter, TextProcessor, Translator};
+use crate::application::{Transcript, TranscriptionOptions};
+use crate::domain::dict::{DictRepository, apply_replacements};
+use crate::domain::fillers::FillerConfig;
+use crate::domain::normalize::NormalizeConfig;
+use crate::domain::pipeline::{PipelineReport, StageReport, StageSpec};
+use crate::domain::profile::{DictionaryStage, Profile};
+use crate::domain::spoken::SpokenCommands;
+use crate::error::{Result, VoiceInputError};
+
+/// 段の処理に渡すリクエストの情報
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessContext<'a> {
+    /// 確定済みの転写オプション
+    pub options: &'a TranscriptionOptions,
+    /// 適用中のプロファイル
+    pub profile: Option<&'a Profile>,
+    /// 翻訳先（翻訳しない場合・話した言語と同じ場合は `None`）
+    pub translate: Option<&'a str>,
+    /// 辞書変換を適用する段階（翻訳しない場合は常に `Before`）
+    pub dictionary_stage: DictionaryStage,
+    /// 辞書の使用回数の記録など、永続的な副作用を伴う処理をしない（`pipeline test` 用）
+    pub dry_run: bool,
+}
+
+impl ProcessContext<'_> {
+    /// プロファイル名
+    pub fn profile_name(&self) -> Option<&str> {
+        self.options.profile.as_deref()
+    }
+}
+
+/// パイプラインの段
+struct Stage {
+    spec: StageSpec,
+    processor: Box<dyn TextProcessor>,
+}
+
+/// 転写後のテキスト処理パイプライン
+#[derive(Default)]
+pub struct TextPipeline {
+    stages: Vec<Stage>,
+}
+
+impl TextPipeline {
+    /// 空のパイプラインを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 段を末尾に追加（無効化された段は追加しない）
+    pub fn with_stage(mut self, spec: StageSpec, processor: Box<dyn TextProcessor>) -> Self {
+        if spec.enabled {
+            self.stages.push(Stage { spec, processor });
+        }
+        self
+    }
+
+    /// 段の数
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// 段がないか
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// 各段を順に適用し、段ごとの結果を返す
+    pub async fn run(
+        &self,
+        transcript: &mut Transcript,
+        context: &ProcessContext<'_>,
+    ) -> PipelineReport {
+        let mut report = PipelineReport {
+            input: transcript.text.clone(),
+            stages: Vec::new(),
+        };
+        for stage in &self.stages {
+            if !stage.spec.applies_to(context.profile_name()) || !stage.processor.applies(context) {
+                continue;
+            }
+            let name = stage.spec.name();
+            let started = Instant::now();
+            let error = match stage.processor.process(transcript, context).await {
+                Ok(()) => None,
+                Err(e) => {
+                    eprintln!("Text processing stage {} failed, skipping: {}", name, e);
+                    Some(e.to_string())
+                }
+            };
+            report.stages.push(StageReport {
+                name: name.to_string(),
+                elapsed_ms: started.elapsed().as_millis() as u64,
+                text: transcript.text.clone(),
+                error,
+            });
+        }
+        report
+    }
+}
+
+/// ユーザー辞書による置換
+pub struct DictionaryProcessor {
+    repo: Arc<dyn DictRepository>,
+    /// 翻訳の前後どちらの段か（`None` なら常に適用）
+    when: Option<DictionaryStage>,
+}
+
+impl DictionaryProcessor {
+    pub fn new(repo: Arc<dyn DictRepository>, when: Option<DictionaryStage>) -> Self {
+        Self { repo, when }
+    }
+}
+
+#[async_trait]
+impl TextProcessor for DictionaryProcessor {
+    fn applies(&self, context: &ProcessContext<'_>) -> bool {
+        self.when
+            .is_none_or(|when| when == context.dictionary_stage)
+    }
+
+    /// セグメントにも適用するが、使用回数は全文に対してのみ数える
+    async fn process(
+        &self,
+        transcript: &mut Transcript,
+        context: &ProcessContext<'_>,
+    ) -> Result<()> {
+        let mut entries = self.repo.load().map_err(|e| {
+            VoiceInputError::SystemError(format!("Failed to load dictionary: {}", e))
+        })?;
+
+        for segment in &mut transcript.segments {
+            segment.text = apply_replacements(&segment.text, &mut entries.clone());
+        }
+        transcript.text = apply_replacements(&transcript.text, &mut entries);
+
+        // 変更があった場合は保存
+        if !context.dry_run && entries.iter().any(|e| e.hit > 0) {
+            self.repo.save(&entries).map_err(|e| {
+                VoiceInputError::SystemError(format!("Failed to save dictionary: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// LLM による全文の書き直し
+///
+/// セグメント・単語のタイムスタンプは転写結果のまま残す。
+pub struct RewriteProcessor {
+    /// 書き直しクライアント（未設定なら書き直しの指示があっても失敗扱い）
+    rewriter: Option<Arc<dyn Rewriter>>,
+    /// 書き直しの指示の既定値
+    instructions: Option<String>,
+    /// タイムアウト
+    timeout: Duration,
+}
+
+impl RewriteProcessor {
+    pub fn new(
+        rewriter: Option<Arc<dyn Rewriter>>,
+        instructions: Option<String>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            rewriter,
+            instructions,
+            timeout,
+        }
+    }
+
+    /// プロファイルの指示が既定の指示より優先（空文字列なら書き直さない）
+    fn instructions<'a>(&'a self, context: &ProcessContext<'a>) -> Option<&'a str> {
+        context
+            .profile
+            .and_then(|p| p.rewrite.as_deref())
+            .or(self.instructions.as_deref())
+            .filter(|i| !i.trim().is_empty())
+    }
+}
+
+#[async_trait]
+impl TextProcessor for RewriteProcessor {
+    fn applies(&self, context: &ProcessContext<'_>) -> bool {
+        self.instructions(context).is_some()
+    }
+
+    async fn process(
+        &self,
+        transcript: &mut Transcript,
+        context: &ProcessContext<'_>,
+    ) -> Result<()> {
+        let (Some(rewriter), Some(instructions)) = (&self.rewriter, self.instructions(context))
+        else {
+            return Err(VoiceInputError::ConfigMissingValue(
+                "rewriter is not configured".to_string(),
+            ));
+        };
+        if transcript.text.trim().is_empty() {
+            return Ok(());
+        }
+        transcript.text = tokio::time::timeout(
+            self.timeout,
+            rewriter.rewrite(&transcript.text, instructions),
+        )
+        .await
+        .unwrap_or(Err(VoiceInputError::RequestTimeout(self.timeout)))?;
+        Ok(())
+    }
+}
+
+/// 全文の翻訳
+///
+/// セグメント・単語のタイムスタンプは原文のまま残す。
+pub struct TranslateProcessor {
+    /// 翻訳クライアント（未設定なら翻訳指定があっても失敗扱い）
+    translator: Option<Arc<dyn Translator>>,
+}
+
+impl TranslateProcessor {
+    pub fn new(translator: Option<Arc<dyn Translator>>) -> Self {
+        Self { translator }
+    }
+}
+
+#[async_trait]
+impl TextProcessor for TranslateProcessor {
+    fn applies(&self, context: &ProcessContext<'_>) -> bool {
+        context.translate.is_some()
+    }
+
+    async fn process(
+        &self,
+        transcript: &mut Transcript,
+        context: &ProcessContext<'_>,
+    ) -> Result<()> {
+        let (Some(translator), Some(target)) = (&self.translator, context.translate) else {
+            return Err(VoiceInputError::ConfigMissingValue(
+                "translator is not configured".to_string(),
+            ));
+        };
+        if transcript.text.trim().is_empty() {
+            return Ok(());
+        }
+        transcript.text = translator.translate(&transcript.text, target).await?;
+        Ok(())
+    }
+}
+
+/// 話し言葉の記号・編集コマンドの変換
+///
+/// 転写言語の語彙を使う（自動判定の場合はすべての言語の語彙）。
+/// セグメント・単語のタイムスタンプは転写結果のまま残す。
+pub struct SpokenCommandProcessor {
+    commands: SpokenCommands,
+}
+
+impl SpokenCommandProcessor {
+    pub fn new(commands: SpokenCommands) -> Self {
+        Self { commands }
+    }
+}
+
+#[async_trait]
+impl TextProcessor for SpokenCommandProcessor {
+    async fn process(
+        &self,
+        transcript: &mut Transcript,
+        context: &ProcessContext<'_>,
+    ) -> Result<()> {
+        transcript.text = self
+            .commands
+            .apply(&transcript.text, context.options.language_code());
+        Ok(())
+    }
+}
+
+/// 日本語の表記の正規化
+///
+/// 転写言語が日本語または自動判定の場合に適用する。翻訳後の段に置いた場合は翻訳先の言語で判定する。
+/// セグメント・単語のタイムスタンプは転写結果のまま残す。
+pub struct NormalizeProcessor {
+    config: NormalizeConfig,
+}
+
+impl NormalizeProcessor {
+    pub fn new(config: NormalizeConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl TextProcessor for NormalizeProcessor {
+    fn applies(&self, context: &ProcessContext<'_>) -> bool {
+        context
+            .options
+            .language_code()
+            .is_none_or(|language| language.eq_ignore_ascii_case("ja"))
+    }
+
+    async fn process(
+        &self,
+        transcript: &mut Transcript,
+        _context: &ProcessContext<'_>,
+    ) -> Result<()> {
+        transcript.text = self.config.apply(&transcript.text);
+        Ok(())
+    }
+}
+
+/// フィラーと単語の繰り返しの除去
+///
+/// 転写言語の規則だけを使い、自動判定の場合はすべての言語の規則を使う。
+/// セグメント・単語のタイムスタンプは転写結果のまま残す。
+pub struct FillerProcessor {
+    config: FillerConfig,
+}
+
+impl FillerProcessor {
+    pub fn new(config: FillerConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl TextProcessor for FillerProcessor {
+    async fn process(
+        &self,
+        transcript: &mut Transcript,
+        context: &ProcessContext<'_>,
+    ) -> Result<()> {
+        transcript.text = self
+            .config
+            .apply(&transcript.text, context.options.language_code());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::pipeline::StageKind;
+
+    /// 末尾に文字列を付け足す段（`fail` なら失敗する）
+    struct Append {
+        suffix: &'static str,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl TextProcessor for Append {
+        async fn process(
+            &self,
+            transcript: &mut Transcript,
+            _context: &ProcessContext<'_>,
+        ) -> Result<()> {
+            if self.fail {
+                return Err(VoiceInputError::SystemError("broken".to_string()));
+            }
+            transcript.text.push_str(self.suffix);
+            Ok(())
+        }
+    }
+
+    fn append(suffix: &'static str, fail: bool) -> Box<dyn TextProcessor> {
+        Box::new(Append { suffix, fail })
+    }
+
+    fn spec(name: &str, profiles: &[&str]) -> StageSpec {
+        StageSpec {
+            name: Some(name.to_string()),
+            profiles: profiles.iter().map(|p| p.to_string()).collect(),
+            ..StageSpec::new(StageKind::Rewrite)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_in_order_with_profiles_and_failures() {
+        let disabled = StageSpec {
+            enabled: false,
+            ..spec("disabled", &[])
+        };
+        let pipeline = TextPipeline::new()
+            .with_stage(spec("a", &[]), append("a", false))
+            .with_stage(spec("broken", &[]), append("x", true))
+            .with_stage(spec("pr-only", &["pr"]), append("p", false))
+            .with_stage(disabled, append("d", false))
+            .with_stage(spec("b", &[]), append("b", false));
+        assert_eq!(pipeline.len(), 4);
+
+        let options = TranscriptionOptions::default();
+        let context = ProcessContext {
+            options: &options,
+            profile: None,
+            translate: None,
+            dictionary_stage: DictionaryStage::Before,
+            dry_run: false,
+        };
+        let mut transcript = Transcript::new("text:");
+        let report = pipeline.run(&mut transcript, &context).await;
+
+        // 失敗した段は適用前のまま次の段へ進み、対象外のプロファイルの段は実行しない
+        assert_eq!(transcript.text, "text:ab");
+        let names: Vec<_> = report.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["a", "broken", "b"]);
+        assert_eq!(report.stages[1].text, "text:a");
+        assert!(report.stages[1].error.is_some());
+        assert_eq!(report.output(), "text:ab");
+
+        let options = TranscriptionOptions {
+            profile: Some("pr".to_string()),
+            ..Default::default()
+        };
+        let context = ProcessContext {
+            options: &options,
+            ..context
+        };
+        let mut transcript = Transcript::new("text:");
+        pipeline.run(&mut transcript, &context).await;
+        assert_eq!(transcript.text, "text:apb");
+    }
+}
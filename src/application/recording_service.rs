@@ -8,11 +8,14 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
 
+use crate::application::TranscriptionOptions;
 use crate::domain::recorder::Recorder;
 use crate::error::{Result, VoiceInputError};
-use crate::infrastructure::audio::AudioBackend;
+use crate::infrastructure::audio::vad::VadConfig;
+use crate::infrastructure::audio::{AudioBackend, PcmChunk};
 use crate::ipc::RecordingResult;
 
 /// 録音状態
@@ -29,12 +32,18 @@ pub enum RecordingState {
 pub struct RecordingConfig {
     /// 最大録音時間（秒）
     pub max_duration_secs: u64,
+    /// push-to-talk で録音として扱う最小押下時間（ミリ秒）
+    pub ptt_min_hold_ms: u64,
+    /// 連続音声入力モードの発話区切り設定
+    pub vad: VadConfig,
 }
 
 impl Default for RecordingConfig {
     fn default() -> Self {
         Self {
             max_duration_secs: 30,
+            ptt_min_hold_ms: 300,
+            vad: VadConfig::default(),
         }
     }
 }
@@ -44,6 +53,12 @@ impl Default for RecordingConfig {
 pub struct RecordingOptions {
     /// 録音開始時のプロンプト
     pub prompt: Option<String>,
+    /// 転写言語（`None` なら既定の言語）
+    pub language: Option<String>,
+    /// 翻訳先の言語（`None` ならプロファイル・既定値に従う）
+    pub translate: Option<String>,
+    /// プロファイル名（`None` なら既定のプロファイル）
+    pub profile: Option<String>,
     /// ペーストフラグ
     pub paste: bool,
     /// 直接入力フラグ
@@ -61,10 +76,18 @@ pub struct RecordingContext {
     pub music_was_playing: bool,
     /// 録音開始時点で取得した選択テキストまたはCLIプロンプト
     pub start_prompt: Option<String>,
+    /// 録音開始時に指定された転写言語
+    pub language: Option<String>,
+    /// 録音開始時に指定された翻訳先の言語
+    pub translate: Option<String>,
+    /// 録音開始時に指定されたプロファイル
+    pub profile: Option<String>,
     /// 転写完了後にペーストを行うか
     pub paste: bool,
     /// 直接入力を使用するか
     pub direct_input: bool,
+    /// 録音開始時刻
+    pub started_at: Option<Instant>,
 }
 
 impl RecordingContext {
@@ -74,8 +97,12 @@ impl RecordingContext {
             cancel: None,
             music_was_playing: false,
             start_prompt: None,
+            language: None,
+            translate: None,
+            profile: None,
             paste: false,
             direct_input: false,
+            started_at: None,
         }
     }
 }
@@ -137,6 +164,9 @@ impl<T: AudioBackend> RecordingService<T> {
 
         // オプションを保存
         ctx.start_prompt = options.prompt;
+        ctx.language = options.language;
+        ctx.translate = options.translate;
+        ctx.profile = options.profile;
         ctx.paste = options.paste;
         ctx.direct_input = options.direct_input;
 
@@ -147,6 +177,7 @@ impl<T: AudioBackend> RecordingService<T> {
             .map_err(|e| VoiceInputError::AudioBackendError(e.to_string()))?;
 
         ctx.state = RecordingState::Recording(session_id);
+        ctx.started_at = Some(Instant::now());
 
         // 自動停止タイマーをセットアップ
         let (cancel_tx, _cancel_rx) = oneshot::channel::<()>();
@@ -182,13 +213,37 @@ impl<T: AudioBackend> RecordingService<T> {
             .map_err(|e| VoiceInputError::AudioBackendError(e.to_string()))?;
 
         ctx.state = RecordingState::Idle;
+        let duration_ms = ctx
+            .started_at
+            .take()
+            .map(|t| t.elapsed().as_millis() as u64)
+            .unwrap_or(0);
 
         Ok(RecordingResult {
             audio_data: audio_data.into(),
-            duration_ms: 0, // TODO: 実際の録音時間を計算
+            duration_ms,
         })
     }
 
+    /// 録音を停止し、音声データを破棄する（push-to-talk の誤タップ等）
+    pub async fn cancel_recording(&self) -> Result<()> {
+        self.stop_recording().await.map(|_| ())
+    }
+
+    /// 録音開始からの経過時間を取得（録音中でなければ `None`）
+    pub fn elapsed(&self) -> Option<Duration> {
+        let ctx = self.context.lock().ok()?;
+        match ctx.state {
+            RecordingState::Recording(_) => ctx.started_at.map(|t| t.elapsed()),
+            RecordingState::Idle => None,
+        }
+    }
+
+    /// 録音を止めずに蓄積済みのサンプルを取り出す（連続音声入力用）
+    pub fn drain_samples(&self) -> Option<PcmChunk> {
+        self.recorder.borrow().drain_samples()
+    }
+
     /// 録音中かどうかを確認
     pub fn is_recording(&self) -> bool {
         if let Ok(ctx) = self.context.lock() {
@@ -235,6 +290,20 @@ impl<T: AudioBackend> RecordingService<T> {
         ))
     }
 
+    /// 録音開始時のプロンプト・言語・翻訳先・プロファイルから転写オプションを作成
+    pub fn transcription_options(&self) -> Result<TranscriptionOptions> {
+        let ctx = self
+            .context
+            .lock()
+            .map_err(|e| VoiceInputError::SystemError(format!("Context lock error: {}", e)))?;
+        Ok(TranscriptionOptions {
+            language: ctx.language.clone(),
+            prompt: ctx.start_prompt.clone(),
+            translate: ctx.translate.clone(),
+            profile: ctx.profile.clone(),
+        })
+    }
+
     /// Apple Music再生状態を設定
     pub fn set_music_was_playing(&self, was_playing: bool) -> Result<()> {
         let mut ctx = self
@@ -291,12 +360,16 @@ mod tests {
         let recorder = Rc::new(RefCell::new(Recorder::new(backend)));
         let config = RecordingConfig {
             max_duration_secs: 30,
+            ..Default::default()
         };
         let service = RecordingService::new(recorder, config);
 
         // 録音開始
         let options = RecordingOptions {
             prompt: None,
+            language: None,
+            translate: None,
+            profile: None,
             paste: false,
             direct_input: false,
         };
@@ -325,6 +398,7 @@ mod tests {
         let recorder = Rc::new(RefCell::new(Recorder::new(backend)));
         let config = RecordingConfig {
             max_duration_secs: 30,
+            ..Default::default()
         };
         let service = RecordingService::new(recorder, config);
 
@@ -333,6 +407,9 @@ mod tests {
             // 録音開始
             let options = RecordingOptions {
                 prompt: Some(format!("Test {}", i)),
+                language: None,
+                translate: None,
+                profile: None,
                 paste: false,
                 direct_input: false,
             };
@@ -360,6 +437,7 @@ mod tests {
         let recorder = Rc::new(RefCell::new(Recorder::new(backend)));
         let config = RecordingConfig {
             max_duration_secs: 30,
+            ..Default::default()
         };
         let service = RecordingService::new(recorder, config);
 
@@ -386,4 +464,77 @@ mod tests {
         assert!(!direct_input);
         assert!(music_was_playing);
     }
+
+    #[tokio::test]
+    async fn test_elapsed_and_duration() {
+        let backend = MockAudioBackend::new();
+        let recorder = Rc::new(RefCell::new(Recorder::new(backend)));
+        let service = RecordingService::new(recorder, RecordingConfig::default());
+
+        assert!(service.elapsed().is_none());
+
+        let options = RecordingOptions {
+            prompt: None,
+            language: None,
+            translate: None,
+            profile: None,
+            paste: false,
+            direct_input: false,
+        };
+        service.start_recording(options).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(service.elapsed().unwrap() >= Duration::from_millis(50));
+
+        let result = service.stop_recording().await.unwrap();
+        assert!(result.duration_ms >= 50);
+        assert!(service.elapsed().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transcription_options_from_context() {
+        let backend = MockAudioBackend::new();
+        let recorder = Rc::new(RefCell::new(Recorder::new(backend)));
+        let service = RecordingService::new(recorder, RecordingConfig::default());
+
+        let options = RecordingOptions {
+            prompt: Some("議事録".to_string()),
+            language: Some("en".to_string()),
+            translate: Some("ja".to_string()),
+            profile: Some("review".to_string()),
+            paste: false,
+            direct_input: false,
+        };
+        service.start_recording(options).await.unwrap();
+
+        let options = service.transcription_options().unwrap();
+        assert_eq!(options.prompt.as_deref(), Some("議事録"));
+        assert_eq!(options.language_code(), Some("en"));
+        assert_eq!(options.translate_target(), Some("ja"));
+        assert_eq!(options.profile.as_deref(), Some("review"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_recording() {
+        let backend = MockAudioBackend::new();
+        let recorder = Rc::new(RefCell::new(Recorder::new(backend)));
+        let service = RecordingService::new(recorder, RecordingConfig::default());
+
+        // 録音していない状態でのキャンセルはエラー
+        assert!(matches!(
+            service.cancel_recording().await,
+            Err(VoiceInputError::RecordingNotStarted)
+        ));
+
+        let options = RecordingOptions {
+            prompt: None,
+            language: None,
+            translate: None,
+            profile: None,
+            paste: false,
+            direct_input: false,
+        };
+        service.start_recording(options).await.unwrap();
+        service.cancel_recording().await.unwrap();
+        assert!(!service.is_recording());
+    }
 }
@@ -0,0 +1,428 @@
+//! 転写リクエストの再試行
+//!
+//! # 責任
+//! - 一時的な失敗（接続エラー・タイムアウト・408/429/5xx）の再試行
+//! - ジッター付き指数バックオフと `Retry-After` の尊重
+//! - 1 回ごとのタイムアウトと全体の期限（デッドライン）の管理
+//! - 再試行状況のイベント通知とログ出力
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::application::traits::TranscriptionClient;
+use crate::application::{Transcript, TranscriptionOptions};
+use crate::error::{Result, VoiceInputError};
+use crate::infrastructure::audio::cpal_backend::AudioData;
+
+/// 再試行の設定
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// 最大試行回数（初回を含む）
+    pub max_attempts: u32,
+    /// 1 回目の再試行前の待ち時間（以降は倍々に伸ばす）
+    pub base_delay: Duration,
+    /// バックオフの上限（`Retry-After` はこの上限を超えても尊重する）
+    pub max_delay: Duration,
+    /// 1 回のリクエストのタイムアウト
+    pub request_timeout: Duration,
+    /// 全試行を通した最大所要時間
+    pub max_total: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            request_timeout: Duration::from_secs(60),
+            max_total: Duration::from_secs(120),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `attempt` 回目（1 始まり）の失敗後のバックオフ（ジッターなし）
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// ジッターを加えたバックオフ（ジッターなしの 50%〜100%）
+    fn jittered_backoff(&self, attempt: u32) -> Duration {
+        let half = self.backoff(attempt) / 2;
+        half + half.mul_f64(random_unit())
+    }
+}
+
+/// `[0, 1)` の乱数（ジッター用途のため暗号学的な品質は不要）
+fn random_unit() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// 再試行の進行状況
+#[derive(Clone, Debug, PartialEq)]
+pub enum RetryEvent {
+    /// 試行を開始
+    Attempt { attempt: u32, max_attempts: u32 },
+    /// 失敗したため `delay` 後に再試行する
+    RetryScheduled {
+        attempt: u32,
+        delay: Duration,
+        reason: String,
+    },
+    /// 成功
+    Succeeded { attempt: u32, elapsed: Duration },
+    /// 再試行を打ち切った
+    GaveUp { attempt: u32, reason: String },
+}
+
+impl fmt::Display for RetryEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryEvent::Attempt {
+                attempt,
+                max_attempts,
+            } => write!(f, "transcription attempt {}/{}", attempt, max_attempts),
+            RetryEvent::RetryScheduled {
+                attempt,
+                delay,
+                reason,
+            } => write!(
+                f,
+                "transcription attempt {} failed ({}), retrying in {:.1}s",
+                attempt,
+                reason,
+                delay.as_secs_f64()
+            ),
+            RetryEvent::Succeeded { attempt, elapsed } => write!(
+                f,
+                "transcription succeeded on attempt {} ({:.1}s)",
+                attempt,
+                elapsed.as_secs_f64()
+            ),
+            RetryEvent::GaveUp { attempt, reason } => write!(
+                f,
+                "transcription failed after {} attempt(s): {}",
+                attempt, reason
+            ),
+        }
+    }
+}
+
+/// 再試行付きの転写クライアント
+///
+/// 任意の `TranscriptionClient` を包み、`RetryPolicy` に従って再試行する。
+pub struct RetryingTranscriptionClient {
+    inner: Box<dyn TranscriptionClient>,
+    policy: RetryPolicy,
+    events: Option<mpsc::UnboundedSender<RetryEvent>>,
+}
+
+impl RetryingTranscriptionClient {
+    /// 新しいクライアントを作成
+    pub fn new(inner: Box<dyn TranscriptionClient>, policy: RetryPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            events: None,
+        }
+    }
+
+    /// 再試行イベントの通知先を設定
+    pub fn with_events(mut self, events: mpsc::UnboundedSender<RetryEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// 再試行の設定
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// イベントをログに出力し、通知先があれば送信
+    fn emit(&self, event: RetryEvent) {
+        match &event {
+            RetryEvent::RetryScheduled { .. } | RetryEvent::GaveUp { .. } => {
+                eprintln!("⚠️  {}", event)
+            }
+            // 初回で成功した場合はログを出さない
+            RetryEvent::Succeeded { attempt, .. } if *attempt > 1 => println!("✅ {}", event),
+            _ => {}
+        }
+        if let Some(tx) = &self.events {
+            let _ = tx.send(event);
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptionClient for RetryingTranscriptionClient {
+    async fn transcribe(
+        &self,
+        audio: AudioData,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcript> {
+        let started = Instant::now();
+        let deadline = started + self.policy.max_total;
+        let max_attempts = self.policy.max_attempts.max(1);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            self.emit(RetryEvent::Attempt {
+                attempt,
+                max_attempts,
+            });
+
+            let timeout = self
+                .policy
+                .request_timeout
+                .min(deadline.saturating_duration_since(Instant::now()));
+            let result =
+                match tokio::time::timeout(timeout, self.inner.transcribe(audio.clone(), options))
+                    .await
+                {
+                    Ok(result) => result,
+                    Err(_) => Err(VoiceInputError::RequestTimeout(timeout)),
+                };
+
+            let error = match result {
+                Ok(transcript) => {
+                    self.emit(RetryEvent::Succeeded {
+                        attempt,
+                        elapsed: started.elapsed(),
+                    });
+                    return Ok(transcript);
+                }
+                Err(error) => error,
+            };
+
+            if !error.is_retryable() || attempt >= max_attempts {
+                self.emit(RetryEvent::GaveUp {
+                    attempt,
+                    reason: error.to_string(),
+                });
+                return Err(error);
+            }
+
+            // サーバーの指定（Retry-After）より短くは待たない
+            let delay = self
+                .policy
+                .jittered_backoff(attempt)
+                .max(error.retry_after().unwrap_or_default());
+            if Instant::now() + delay >= deadline {
+                self.emit(RetryEvent::GaveUp {
+                    attempt,
+                    reason: format!("deadline exceeded: {}", error),
+                });
+                return Err(error);
+            }
+
+            self.emit(RetryEvent::RetryScheduled {
+                attempt,
+                delay,
+                reason: error.to_string(),
+            });
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// 応答を順番に返すモック（`None` は応答しない＝タイムアウト）
+    struct ScriptedClient {
+        script: Mutex<VecDeque<Option<Result<Transcript>>>>,
+    }
+
+    impl ScriptedClient {
+        fn new(script: Vec<Option<Result<Transcript>>>) -> Box<Self> {
+            Box::new(Self {
+                script: Mutex::new(script.into()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl TranscriptionClient for ScriptedClient {
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            _options: &TranscriptionOptions,
+        ) -> Result<Transcript> {
+            let next = self.script.lock().unwrap().pop_front().flatten();
+            match next {
+                Some(result) => result,
+                None => std::future::pending().await,
+            }
+        }
+    }
+
+    fn status(status: u16, retry_after: Option<Duration>) -> Option<Result<Transcript>> {
+        Some(Err(VoiceInputError::ApiStatus {
+            status,
+            message: "error".to_string(),
+            retry_after,
+        }))
+    }
+
+    fn ok(text: &str) -> Option<Result<Transcript>> {
+        Some(Ok(Transcript::new(text)))
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(5),
+            max_total: Duration::from_secs(30),
+        }
+    }
+
+    fn collect(rx: &mut mpsc::UnboundedReceiver<RetryEvent>) -> Vec<RetryEvent> {
+        std::iter::from_fn(|| rx.try_recv().ok()).collect()
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = policy();
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff(40), Duration::from_secs(1));
+
+        for _ in 0..100 {
+            let delay = policy.jittered_backoff(3);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retries_until_success_and_honours_retry_after() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let client = RetryingTranscriptionClient::new(
+            ScriptedClient::new(vec![
+                status(503, None),
+                status(429, Some(Duration::from_secs(3))),
+                ok("こんにちは"),
+            ]),
+            policy(),
+        )
+        .with_events(tx);
+
+        let started = Instant::now();
+        let transcript = client
+            .transcribe(AudioData(Vec::new()), &TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(transcript.text, "こんにちは");
+        // Retry-After の 3 秒はバックオフ上限（1 秒）より優先される
+        assert!(started.elapsed() >= Duration::from_secs(3));
+
+        let events = collect(&mut rx);
+        let delays: Vec<Duration> = events
+            .iter()
+            .filter_map(|e| match e {
+                RetryEvent::RetryScheduled { delay, .. } => Some(*delay),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(delays.len(), 2);
+        assert!(delays[0] <= Duration::from_millis(100));
+        assert_eq!(delays[1], Duration::from_secs(3));
+        assert!(matches!(
+            events.last(),
+            Some(RetryEvent::Succeeded { attempt: 3, .. })
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_does_not_retry_client_errors() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let client = RetryingTranscriptionClient::new(
+            ScriptedClient::new(vec![status(400, None)]),
+            policy(),
+        )
+        .with_events(tx);
+
+        let err = client
+            .transcribe(AudioData(Vec::new()), &TranscriptionOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VoiceInputError::ApiStatus { status: 400, .. }
+        ));
+        assert!(matches!(
+            collect(&mut rx).as_slice(),
+            [
+                RetryEvent::Attempt { attempt: 1, .. },
+                RetryEvent::GaveUp { attempt: 1, .. }
+            ]
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_times_out_and_gives_up_after_max_attempts() {
+        let client = RetryingTranscriptionClient::new(
+            ScriptedClient::new(vec![None, None, None, None]),
+            policy(),
+        );
+
+        let started = Instant::now();
+        let err = client
+            .transcribe(AudioData(Vec::new()), &TranscriptionOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VoiceInputError::RequestTimeout(_)));
+        // 4 回分のタイムアウト + 3 回分のバックオフ
+        assert!(started.elapsed() >= Duration::from_secs(20));
+        assert!(started.elapsed() < Duration::from_secs(21));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stops_at_deadline() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let client = RetryingTranscriptionClient::new(
+            ScriptedClient::new(vec![status(429, Some(Duration::from_secs(60))), ok("late")]),
+            policy(),
+        )
+        .with_events(tx);
+
+        let started = Instant::now();
+        let err = client
+            .transcribe(AudioData(Vec::new()), &TranscriptionOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VoiceInputError::ApiStatus { status: 429, .. }
+        ));
+        // 期限を超える Retry-After は待たずに打ち切る
+        assert!(started.elapsed() < Duration::from_secs(1));
+        match collect(&mut rx).last() {
+            Some(RetryEvent::GaveUp { reason, .. }) => assert!(reason.contains("deadline")),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! 転写リクエストごとのモデル・プロバイダーの振り分け
+//!
+//! # 責任
+//! - 録音の長さ・言語・プロファイル・プロンプトの大きさから `RoutingPolicy` のルールを選択
+//! - 選ばれたルールの転写クライアント（どれにも当てはまらなければ既定のクライアント）へ委譲
+
+use async_trait::async_trait;
+
+use crate::application::traits::TranscriptionClient;
+use crate::application::{Transcript, TranscriptionOptions};
+use crate::domain::routing::{RouteRequest, RoutingPolicy};
+use crate::error::Result;
+use crate::infrastructure::audio::cpal_backend::AudioData;
+
+/// 名前付きの振り分け先
+struct Route {
+    name: String,
+    client: Box<dyn TranscriptionClient>,
+}
+
+/// 振り分け方針に従って転写クライアントを選ぶクライアント
+///
+/// 短い録音は安価なモデル、長い専門的な口述は精度の高いモデル、のように
+/// 料金と精度の釣り合いをリクエストごとに自動で取る。
+pub struct RoutingTranscriptionClient {
+    policy: RoutingPolicy,
+    /// ルールと同じ順の振り分け先（作成できなかったルールは `None`）
+    routes: Vec<Option<Route>>,
+    default: Box<dyn TranscriptionClient>,
+}
+
+impl RoutingTranscriptionClient {
+    /// 既定のクライアントを指定して作成
+    pub fn new(policy: RoutingPolicy, default: Box<dyn TranscriptionClient>) -> Self {
+        let routes = policy.rules.iter().map(|_| None).collect();
+        Self {
+            policy,
+            routes,
+            default,
+        }
+    }
+
+    /// `index` 番目のルールの振り分け先を設定
+    pub fn with_route(
+        mut self,
+        index: usize,
+        name: impl Into<String>,
+        client: Box<dyn TranscriptionClient>,
+    ) -> Self {
+        if let Some(route) = self.routes.get_mut(index) {
+            *route = Some(Route {
+                name: name.into(),
+                client,
+            });
+        }
+        self
+    }
+
+    /// リクエストの振り分け先（ルール名と転写クライアント）
+    fn select(&self, audio: &AudioData, options: &TranscriptionOptions) -> Option<&Route> {
+        let request = RouteRequest {
+            duration_secs: audio
+                .parse_wav()
+                .map(|wav| wav.duration().as_secs_f64())
+                .unwrap_or(0.0),
+            language: options.language_code().map(str::to_string),
+            profile: options.profile.clone(),
+            prompt_chars: options.prompt.as_deref().map_or(0, |p| p.chars().count()),
+        };
+        let index = self.policy.route(&request)?;
+        self.routes[index].as_ref()
+    }
+}
+
+#[async_trait]
+impl TranscriptionClient for RoutingTranscriptionClient {
+    async fn transcribe(
+        &self,
+        audio: AudioData,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcript> {
+        match self.select(&audio, options) {
+            Some(route) => {
+                println!("🧭 routed to {}", route.name);
+                route.client.transcribe(audio, options).await
+            }
+            None => self.default.transcribe(audio, options).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::service_container::test_helpers::MockTranscriptionClient;
+    use crate::infrastructure::audio::CpalAudioBackend;
+
+    fn wav(secs: usize) -> AudioData {
+        let samples = vec![0i16; 16_000 * secs];
+        AudioData(CpalAudioBackend::combine_wav_data(&samples, 16_000, 1).unwrap())
+    }
+
+    fn client() -> RoutingTranscriptionClient {
+        let policy: RoutingPolicy = serde_json::from_str(
+            r#"{"rules": [
+                {"name": "short", "provider": "openai:gpt-4o-mini-transcribe", "max_secs": 5},
+                {"name": "chat", "provider": "whisper-cpp", "profiles": ["chat"]},
+                {"name": "broken", "provider": "unknown"}
+            ]}"#,
+        )
+        .unwrap();
+        RoutingTranscriptionClient::new(policy, Box::new(MockTranscriptionClient::new("default")))
+            .with_route(0, "short", Box::new(MockTranscriptionClient::new("mini")))
+            .with_route(1, "chat", Box::new(MockTranscriptionClient::new("local")))
+    }
+
+    #[tokio::test]
+    async fn test_routes_by_duration_and_profile() {
+        let client = client();
+        let chat = TranscriptionOptions {
+            profile: Some("chat".to_string()),
+            ..Default::default()
+        };
+
+        let short = client.transcribe(wav(2), &chat).await.unwrap();
+        assert_eq!(short.text, "mini");
+        let chat = client.transcribe(wav(8), &chat).await.unwrap();
+        assert_eq!(chat.text, "local");
+
+        // 作成できなかったルールに当たった場合は既定のクライアント
+        let long = client
+            .transcribe(wav(8), &TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(long.text, "default");
+    }
+}
@@ -10,16 +10,30 @@ use std::rc::Rc;
 use tokio::sync::mpsc;
 
 use crate::application::{
-    CommandHandler, MediaControlService, RecordingConfig, RecordingService, TranscriptionMessage,
-    TranscriptionService, traits::TranscriptionClient,
+    CommandHandler, FallbackTranscriptionClient, MediaControlService, MeteredStreamingClient,
+    MeteredTranscriptionClient, RecordingConfig, RecordingService, RetryEvent, RetryPolicy,
+    RetryingTranscriptionClient, RoutingTranscriptionClient, TranscriptionMessage,
+    TranscriptionService,
+    traits::{Rewriter, StreamingTranscriptionClient, TranscriptionClient, Translator},
+    transcription_service::{DEFAULT_LANGUAGE, DEFAULT_MIN_CONFIDENCE, DEFAULT_REWRITE_TIMEOUT},
 };
+use crate::domain::phantom::PhantomPhraseFilter;
+use crate::domain::profile::DictionaryStage;
 use crate::domain::recorder::Recorder;
+use crate::domain::routing::RoutingPolicy;
 use crate::error::Result;
 use crate::infrastructure::{
-    audio::{AudioBackend, CpalAudioBackend},
+    audio::{AudioBackend, CpalAudioBackend, vad::VadConfig},
+    cache::CacheConfig,
     dict::JsonFileDictRepo,
-    external::openai_adapter::OpenAiTranscriptionAdapter,
-    ui::UiProcessManager,
+    external::{
+        chat::{ChatClient, ChatConfig},
+        realtime::{RealtimeConfig, RealtimeTranscriptionClient},
+        stt_provider::{ProviderSpec, SttProvider},
+    },
+    queue::QueueConfig,
+    ui::{UiNotification, UiProcessManager},
+    usage::UsageConfig,
 };
 use crate::shortcut::ShortcutService;
 
@@ -30,6 +44,40 @@ pub struct AppConfig {
     pub recording: RecordingConfig,
     /// 最大同時転写数
     pub max_concurrent_transcriptions: usize,
+    /// 既定の転写言語（`auto` で自動判定）
+    pub default_language: String,
+    /// 転写プロバイダー
+    pub stt_provider: SttProvider,
+    /// 転写プロバイダーが失敗した場合に順に試すプロバイダー
+    pub stt_fallback: Vec<ProviderSpec>,
+    /// 転写リクエストごとのモデル・プロバイダーの振り分け方針
+    pub routing: RoutingPolicy,
+    /// 転写リクエストの再試行設定
+    pub retry: RetryPolicy,
+    /// 再試行イベントの通知先（`None` ならログ出力のみ）
+    pub retry_events: Option<mpsc::UnboundedSender<RetryEvent>>,
+    /// 録音中に音声を逐次送るストリーミング転写を使うか
+    pub stt_streaming: bool,
+    /// 転写結果キャッシュの設定
+    pub cache: CacheConfig,
+    /// 転写に失敗した録音のキューの設定
+    pub queue: QueueConfig,
+    /// 利用記録・料金表・月間予算の設定
+    pub usage: UsageConfig,
+    /// 翻訳先の既定値（`None` なら翻訳しない）
+    pub translate: Option<String>,
+    /// 翻訳する場合に辞書変換を適用する段階
+    pub translate_dictionary: DictionaryStage,
+    /// 既定のプロファイル
+    pub profile: Option<String>,
+    /// LLM による書き直しの指示（`None` なら書き直さない）
+    pub rewrite_instructions: Option<String>,
+    /// 書き直しのタイムアウト
+    pub rewrite_timeout: std::time::Duration,
+    /// 信頼度のしきい値（下回った結果は直接入力せずスタックに保存。0 で無効）
+    pub min_confidence: f64,
+    /// 無音時の定型句を検出するか
+    pub phantom_filter: bool,
 }
 
 impl Default for AppConfig {
@@ -40,8 +88,234 @@ impl Default for AppConfig {
                     .ok()
                     .and_then(|s| s.parse().ok())
                     .unwrap_or(30),
+                ptt_min_hold_ms: std::env::var("VOICE_INPUT_PTT_MIN_HOLD_MS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(300),
+                vad: VadConfig {
+                    energy_threshold: std::env::var("VOICE_INPUT_VAD_THRESHOLD")
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(VadConfig::default().energy_threshold),
+                    silence_ms: std::env::var("VOICE_INPUT_VAD_SILENCE_MS")
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(VadConfig::default().silence_ms),
+                    ..Default::default()
+                },
             },
             max_concurrent_transcriptions: 2,
+            default_language: std::env::var("VOICE_INPUT_LANGUAGE")
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string()),
+            // 不正な値は `ServiceContainer::new` で起動エラーにする
+            stt_provider: SttProvider::from_env().unwrap_or_default(),
+            stt_fallback: ProviderSpec::fallback_from_env(),
+            routing: crate::infrastructure::config::AppConfig::load().routing,
+            retry: RetryPolicy {
+                max_attempts: std::env::var("VOICE_INPUT_RETRY_MAX_ATTEMPTS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(RetryPolicy::default().max_attempts),
+                request_timeout: std::env::var("VOICE_INPUT_REQUEST_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .map(std::time::Duration::from_secs)
+                    .unwrap_or(RetryPolicy::default().request_timeout),
+                max_total: std::env::var("VOICE_INPUT_RETRY_DEADLINE_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .map(std::time::Duration::from_secs)
+                    .unwrap_or(RetryPolicy::default().max_total),
+                ..Default::default()
+            },
+            retry_events: None,
+            stt_streaming: std::env::var("VOICE_INPUT_STT_STREAMING")
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+            cache: CacheConfig::from_env(),
+            queue: QueueConfig::from_env(),
+            usage: UsageConfig::from_env(),
+            translate: std::env::var("VOICE_INPUT_TRANSLATE")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            translate_dictionary: std::env::var("VOICE_INPUT_TRANSLATE_DICTIONARY")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_default(),
+            profile: std::env::var("VOICE_INPUT_PROFILE")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            rewrite_instructions: rewrite_instructions_from_env(),
+            rewrite_timeout: std::env::var("VOICE_INPUT_REWRITE_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(DEFAULT_REWRITE_TIMEOUT),
+            min_confidence: std::env::var("VOICE_INPUT_MIN_CONFIDENCE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DEFAULT_MIN_CONFIDENCE),
+            phantom_filter: std::env::var("VOICE_INPUT_PHANTOM_FILTER")
+                .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+                .unwrap_or(true),
+        }
+    }
+}
+
+impl AppConfig {
+    /// 転写クライアントを作成
+    ///
+    /// 振り分け方針が定義されていれば、ルールごとのプロバイダーを先頭にしたチェーンへ振り分ける。
+    /// 作成できなかったルールは警告を出して外す（当てはまったリクエストは既定のチェーンで転写する）。
+    pub fn create_transcription_client(&self) -> Result<Box<dyn TranscriptionClient>> {
+        let default = self.create_chain(&ProviderSpec::from(self.stt_provider), None)?;
+        if self.routing.is_empty() {
+            return Ok(default);
+        }
+
+        let mut client = RoutingTranscriptionClient::new(self.routing.clone(), default);
+        for (index, rule) in self.routing.rules.iter().enumerate() {
+            let spec = match rule.provider.parse::<ProviderSpec>() {
+                Ok(spec) => spec,
+                Err(e) => {
+                    eprintln!("Skipping route {}: {}", rule.name, e);
+                    continue;
+                }
+            };
+            match self.create_chain(&spec, Some(&rule.name)) {
+                Ok(chain) => client = client.with_route(index, rule.name.clone(), chain),
+                Err(e) => eprintln!("Skipping route {}: {}", rule.name, e),
+            }
+        }
+        Ok(Box::new(client))
+    }
+
+    /// `primary` を先頭にしたフォールバックチェーンを作成
+    ///
+    /// 各プロバイダーを利用記録・再試行付きで包み、フォールバック順に連結する。
+    /// フォールバック先の作成に失敗した場合は警告を出してチェーンから外す。
+    fn create_chain(
+        &self,
+        primary: &ProviderSpec,
+        route: Option<&str>,
+    ) -> Result<Box<dyn TranscriptionClient>> {
+        let mut chain = FallbackTranscriptionClient::new().with_provider(
+            primary.to_string(),
+            self.with_retry(self.with_metering(primary, route, primary.create_client()?)),
+        );
+
+        for spec in &self.stt_fallback {
+            match spec.create_client() {
+                Ok(client) => {
+                    let client = self.with_retry(self.with_metering(spec, route, client));
+                    chain = chain.with_provider(spec.to_string(), client);
+                }
+                Err(e) => eprintln!("Skipping fallback provider {}: {}", spec, e),
+            }
+        }
+        Ok(Box::new(chain))
+    }
+
+    /// ストリーミング転写クライアントを作成（無効な場合は `None`）
+    pub fn create_streaming_client(&self) -> Result<Option<Box<dyn StreamingTranscriptionClient>>> {
+        if !self.stt_streaming {
+            return Ok(None);
+        }
+        let config = RealtimeConfig::from_env();
+        let model = config.model.clone();
+        let client: Box<dyn StreamingTranscriptionClient> =
+            Box::new(RealtimeTranscriptionClient::new(config)?);
+        if !self.usage.enabled {
+            return Ok(Some(client));
+        }
+        // 有料のセッションも利用記録に残し、月間予算の上限を超えていればセッションを開かない
+        Ok(Some(Box::new(
+            MeteredStreamingClient::new(client, "openai", model, self.usage.open())
+                .with_prices(self.usage.prices.clone())
+                .with_budget(self.usage.budget),
+        )))
+    }
+
+    /// 翻訳クライアントを作成（`VOICE_INPUT_TRANSLATE_MODEL` でモデルを上書き）
+    pub fn create_translator(&self) -> Result<Box<dyn Translator>> {
+        let mut config = ChatConfig::from_env();
+        if let Ok(model) = std::env::var("VOICE_INPUT_TRANSLATE_MODEL") {
+            if !model.trim().is_empty() {
+                config = config.with_model(model);
+            }
+        }
+        Ok(Box::new(ChatClient::new(config)?))
+    }
+
+    /// 書き直しクライアントを作成（`VOICE_INPUT_REWRITE_MODEL` でモデルを上書き）
+    pub fn create_rewriter(&self) -> Result<Box<dyn Rewriter>> {
+        let mut config = ChatConfig::from_env();
+        if let Ok(model) = std::env::var("VOICE_INPUT_REWRITE_MODEL") {
+            if !model.trim().is_empty() {
+                config = config.with_model(model);
+            }
+        }
+        Ok(Box::new(ChatClient::new(config)?))
+    }
+
+    fn with_retry(&self, client: Box<dyn TranscriptionClient>) -> Box<dyn TranscriptionClient> {
+        let client = RetryingTranscriptionClient::new(client, self.retry.clone());
+        match &self.retry_events {
+            Some(events) => Box::new(client.with_events(events.clone())),
+            None => Box::new(client),
+        }
+    }
+
+    /// 再試行イベントの通知先を設定し、受信側を返す
+    fn with_retry_channel(self) -> (Self, mpsc::UnboundedReceiver<RetryEvent>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let config = Self {
+            retry_events: Some(tx),
+            ..self
+        };
+        (config, rx)
+    }
+
+    /// 利用記録が有効なら試行ごとに記録するクライアントで包む
+    fn with_metering(
+        &self,
+        spec: &ProviderSpec,
+        route: Option<&str>,
+        client: Box<dyn TranscriptionClient>,
+    ) -> Box<dyn TranscriptionClient> {
+        if !self.usage.enabled {
+            return client;
+        }
+        Box::new(
+            MeteredTranscriptionClient::new(
+                client,
+                spec.provider.to_string(),
+                spec.model_name(),
+                self.usage.open(),
+            )
+            .with_prices(self.usage.prices.clone())
+            .with_budget(self.usage.budget)
+            .with_route(route.map(str::to_string)),
+        )
+    }
+}
+
+/// 書き直しの指示を読み込む（`VOICE_INPUT_REWRITE` を優先し、なければ `VOICE_INPUT_REWRITE_FILE`）
+fn rewrite_instructions_from_env() -> Option<String> {
+    if let Some(instructions) = std::env::var("VOICE_INPUT_REWRITE")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+    {
+        return Some(instructions);
+    }
+    let path = std::env::var("VOICE_INPUT_REWRITE_FILE").ok()?;
+    match std::fs::read_to_string(&path) {
+        Ok(instructions) => Some(instructions).filter(|s| !s.trim().is_empty()),
+        Err(e) => {
+            eprintln!("Failed to read rewrite instructions {}: {}", path, e);
+            None
         }
     }
 }
@@ -52,20 +326,32 @@ pub struct ServiceContainer<T: AudioBackend + 'static> {
     pub command_handler: Rc<RefCell<CommandHandler<T>>>,
     /// ショートカットサービス（独立ワーカー用）
     pub shortcut_service: Rc<RefCell<ShortcutService>>,
+    /// 転写サービス（転写ワーカー用）
+    pub transcription_service: Rc<RefCell<TranscriptionService>>,
     /// 転写メッセージ送信チャンネル
     pub transcription_tx: mpsc::UnboundedSender<TranscriptionMessage>,
     /// 転写メッセージ受信チャンネル
     pub transcription_rx: Option<mpsc::UnboundedReceiver<TranscriptionMessage>>,
+    /// UI マネージャー（再試行状況の表示用）
+    pub ui_manager: Rc<RefCell<UiProcessManager>>,
+    /// 転写の再試行イベント受信チャンネル
+    pub retry_events_rx: Option<mpsc::UnboundedReceiver<RetryEvent>>,
 }
 
 impl ServiceContainer<CpalAudioBackend> {
     /// デフォルト設定で新しいServiceContainerを作成
     pub fn new() -> Result<Self> {
-        let config = AppConfig::default();
+        let (config, retry_events_rx) = AppConfig {
+            stt_provider: SttProvider::from_env()?,
+            ..AppConfig::default()
+        }
+        .with_retry_channel();
         let recorder = Rc::new(RefCell::new(Recorder::new(CpalAudioBackend::default())));
-        let client = Box::new(OpenAiTranscriptionAdapter::new()?);
+        let client = config.create_transcription_client()?;
 
-        Self::with_dependencies(config, recorder, client)
+        let mut container = Self::with_dependencies(config, recorder, client)?;
+        container.retry_events_rx = Some(retry_events_rx);
+        Ok(container)
     }
 
     /// テスト用の設定で作成
@@ -86,10 +372,13 @@ impl<T: AudioBackend + 'static> ServiceContainer<T> {
     where
         T: Default,
     {
+        let (config, retry_events_rx) = config.with_retry_channel();
         let recorder = Rc::new(RefCell::new(Recorder::new(T::default())));
-        let client = Box::new(OpenAiTranscriptionAdapter::new()?);
+        let client = config.create_transcription_client()?;
 
-        Self::with_dependencies(config, recorder, client)
+        let mut container = Self::with_dependencies(config, recorder, client)?;
+        container.retry_events_rx = Some(retry_events_rx);
+        Ok(container)
     }
 
     /// 依存関係を注入して作成（テスト用）
@@ -104,11 +393,78 @@ impl<T: AudioBackend + 'static> ServiceContainer<T> {
             config.recording.clone(),
         )));
 
-        let transcription = Rc::new(RefCell::new(TranscriptionService::new(
+        let mut transcription = TranscriptionService::new(
             transcription_client,
             Box::new(JsonFileDictRepo::new()),
             config.max_concurrent_transcriptions,
-        )));
+        )
+        .with_default_language(config.default_language.clone())
+        .with_default_translate(config.translate.clone())
+        .with_dictionary_stage(config.translate_dictionary)
+        .with_min_confidence(config.min_confidence);
+
+        // プロファイル・無音時の定型句の追加分・テキスト処理の段は設定ファイル（config.json）で定義する
+        let file_config = crate::infrastructure::config::AppConfig::load();
+        transcription = transcription.with_pipeline(file_config.pipeline);
+        if config.phantom_filter {
+            let filter = PhantomPhraseFilter::default().with_phrases(file_config.phantom_phrases);
+            transcription = transcription.with_phantom_filter(filter, config.recording.vad.clone());
+        }
+        let profiles = file_config.profiles;
+        let default_profile = config.profile.clone().filter(|name| {
+            let defined = profiles.contains_key(name);
+            if !defined {
+                eprintln!("Ignoring VOICE_INPUT_PROFILE: unknown profile {}", name);
+            }
+            defined
+        });
+        let translates = config.translate.is_some()
+            || profiles.values().any(|profile| profile.translate.is_some());
+        let rewrites = config.rewrite_instructions.is_some()
+            || profiles.values().any(|profile| {
+                profile
+                    .rewrite
+                    .as_deref()
+                    .is_some_and(|r| !r.trim().is_empty())
+            });
+        transcription = transcription
+            .with_profiles(profiles)
+            .with_default_profile(default_profile);
+        match config.create_translator() {
+            Ok(translator) => transcription = transcription.with_translator(translator),
+            Err(e) if translates => eprintln!("Translation disabled: {}", e),
+            Err(_) => {}
+        }
+        transcription = transcription
+            .with_rewrite_instructions(config.rewrite_instructions.clone())
+            .with_rewrite_timeout(config.rewrite_timeout);
+        if rewrites {
+            match config.create_rewriter() {
+                Ok(rewriter) => transcription = transcription.with_rewriter(rewriter),
+                Err(e) => eprintln!("Rewrite disabled: {}", e),
+            }
+        }
+
+        if config.cache.enabled {
+            let mut namespace = ProviderSpec::from(config.stt_provider).cache_namespace();
+            // 振り分け方針が変わると同じ録音でも転写するモデルが変わる
+            if !config.routing.is_empty() {
+                namespace.push('|');
+                namespace.push_str(&serde_json::to_string(&config.routing)?);
+            }
+            // 振り分け方針はプロファイルでもプロバイダーを選ぶため、プロファイルもキーに含める
+            transcription = transcription.with_cache(
+                config
+                    .cache
+                    .open()
+                    .with_namespace(namespace)
+                    .with_profile_key(!config.routing.is_empty()),
+            );
+        }
+        if config.queue.enabled {
+            transcription = transcription.with_queue(config.queue.open());
+        }
+        let transcription = Rc::new(RefCell::new(transcription));
 
         let stack = Rc::new(RefCell::new(crate::application::StackService::new()));
         let media_control = Rc::new(RefCell::new(MediaControlService::new()));
@@ -119,21 +475,31 @@ impl<T: AudioBackend + 'static> ServiceContainer<T> {
         let (tx, rx) = mpsc::unbounded_channel();
 
         // コマンドハンドラーを構築
-        let command_handler = Rc::new(RefCell::new(CommandHandler::new(
+        let mut command_handler = CommandHandler::new(
             recording,
-            transcription,
+            transcription.clone(),
             stack,
             media_control,
-            ui_manager,
+            ui_manager.clone(),
             shortcut_service.clone(),
             tx.clone(),
-        )));
+        );
+        match config.create_streaming_client() {
+            Ok(Some(client)) => command_handler = command_handler.with_streaming(client),
+            Ok(None) => {}
+            Err(e) => eprintln!("Streaming transcription disabled: {}", e),
+        }
+        command_handler = command_handler.with_usage(config.usage.clone());
+        let command_handler = Rc::new(RefCell::new(command_handler));
 
         Ok(ServiceContainer {
             command_handler,
             shortcut_service,
+            transcription_service: transcription,
             transcription_tx: tx,
             transcription_rx: Some(rx),
+            ui_manager,
+            retry_events_rx: None,
         })
     }
 
@@ -143,13 +509,43 @@ impl<T: AudioBackend + 'static> ServiceContainer<T> {
     ) -> Option<mpsc::UnboundedReceiver<TranscriptionMessage>> {
         self.transcription_rx.take()
     }
+
+    /// 転写の再試行状況を UI の警告に表示するタスクを取得（一度だけ）
+    pub fn take_retry_notifier(
+        &mut self,
+    ) -> Option<impl std::future::Future<Output = ()> + 'static> {
+        let mut rx = self.retry_events_rx.take()?;
+        let ui_manager = self.ui_manager.clone();
+        Some(async move {
+            while let Some(event) = rx.recv().await {
+                if let Some(notification) = retry_notification(&event) {
+                    let _ = ui_manager.borrow().notify(notification);
+                }
+            }
+        })
+    }
+}
+
+/// 再試行イベントを UI への通知に変換
+///
+/// 再試行の予定と打ち切りを警告として表示し、再試行の末に成功したら表示を消す。
+fn retry_notification(event: &RetryEvent) -> Option<UiNotification> {
+    match event {
+        RetryEvent::RetryScheduled { .. } | RetryEvent::GaveUp { .. } => {
+            Some(UiNotification::Warning(event.to_string()))
+        }
+        RetryEvent::Succeeded { attempt, .. } if *attempt > 1 => {
+            Some(UiNotification::Warning(String::new()))
+        }
+        _ => None,
+    }
 }
 
 /// テスト用のヘルパー実装
 #[cfg(test)]
 pub mod test_helpers {
     use super::*;
-    use crate::application::{StackService, MediaControlService, TranscriptionService, RecordingService, RecordingConfig, CommandHandler};
+    use crate::application::{StackService, MediaControlService, TranscriptionService, RecordingService, RecordingConfig, CommandHandler, Transcript, TranscriptionOptions};
     use crate::infrastructure::{
         audio::cpal_backend::AudioData,
         ui::UiProcessManager,
@@ -204,8 +600,12 @@ pub mod test_helpers {
 
     #[async_trait]
     impl TranscriptionClient for MockTranscriptionClient {
-        async fn transcribe(&self, _audio: AudioData, _language: &str) -> Result<String> {
-            Ok(self.response.clone())
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            _options: &TranscriptionOptions,
+        ) -> Result<Transcript> {
+            Ok(Transcript::new(self.response.clone()))
         }
     }
 
@@ -274,10 +674,10 @@ pub mod test_helpers {
             // CommandHandlerを作成
             let command_handler = Rc::new(RefCell::new(CommandHandler::new(
                 recording_service,
-                transcription_service,
+                transcription_service.clone(),
                 stack_service,
                 media_control_service,
-                ui_manager,
+                ui_manager.clone(),
                 shortcut_service.clone(),
                 transcription_tx.clone(),
             )));
@@ -285,8 +685,11 @@ pub mod test_helpers {
             Ok(ServiceContainer {
                 command_handler,
                 shortcut_service,
+                transcription_service,
                 transcription_tx,
                 transcription_rx: Some(transcription_rx),
+                ui_manager,
+                retry_events_rx: None,
             })
         }
     }
@@ -326,4 +729,41 @@ mod tests {
         let rx2 = container.take_transcription_rx();
         assert!(rx2.is_none());
     }
+    #[test]
+    fn test_retry_events_are_shown_as_warnings() {
+        use super::{RetryEvent, UiNotification, retry_notification};
+        use std::time::Duration;
+
+        let scheduled = RetryEvent::RetryScheduled {
+            attempt: 1,
+            delay: Duration::from_secs(2),
+            reason: "HTTP 503".to_string(),
+        };
+        assert!(matches!(
+            retry_notification(&scheduled),
+            Some(UiNotification::Warning(text)) if text == scheduled.to_string()
+        ));
+
+        // 再試行の末に成功したら警告を消す
+        let recovered = RetryEvent::Succeeded {
+            attempt: 2,
+            elapsed: Duration::from_secs(3),
+        };
+        assert!(matches!(
+            retry_notification(&recovered),
+            Some(UiNotification::Warning(text)) if text.is_empty()
+        ));
+
+        // 初回の試行と初回での成功は通知しない
+        let first = RetryEvent::Succeeded {
+            attempt: 1,
+            elapsed: Duration::from_secs(1),
+        };
+        assert!(retry_notification(&first).is_none());
+        let attempt = RetryEvent::Attempt {
+            attempt: 1,
+            max_attempts: 3,
+        };
+        assert!(retry_notification(&attempt).is_none());
+    }
 }
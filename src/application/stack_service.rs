@@ -1,4 +1,5 @@
 use crate::domain::stack::{Stack, StackInfo};
+use crate::domain::transcript::Transcript;
 use crate::infrastructure::ui::{StackDisplayInfo, UiNotification};
 use std::collections::HashMap;
 use std::fmt;
@@ -168,8 +169,26 @@ impl StackService {
 
     /// 新しいスタックを保存
     pub fn save_stack(&mut self, text: String) -> u32 {
-        let id = self.next_id;
-        let stack = Stack::new(id, text);
+        self.save_stack_with_language(text, None)
+    }
+
+    /// 検出言語付きで新しいスタックを保存
+    pub fn save_stack_with_language(&mut self, text: String, language: Option<String>) -> u32 {
+        self.insert_stack(Stack::new(self.next_id, text).with_language(language))
+    }
+
+    /// 転写結果（検出言語・タイムスタンプ付き）を新しいスタックとして保存
+    pub fn save_transcript(&mut self, transcript: &Transcript) -> u32 {
+        self.insert_stack(
+            Stack::new(self.next_id, transcript.text.clone())
+                .with_language(transcript.language.clone())
+                .with_timestamps(transcript.segments.clone(), transcript.words.clone()),
+        )
+    }
+
+    /// 採番済みのスタックを追加して UI に通知
+    fn insert_stack(&mut self, stack: Stack) -> u32 {
+        let id = stack.id;
         let display_info = self.stack_to_display_info(&stack, false);
         self.stacks.insert(id, stack);
         self.next_id += 1;
@@ -267,9 +286,14 @@ impl StackService {
         let mut output = format!("📚 {} stack(s) in memory:\n", self.stacks.len());
 
         for info in self.list_stacks() {
+            let language = self
+                .get_stack(info.number)
+                .and_then(|stack| stack.language.as_deref())
+                .map(|language| format!(" <{}>", language))
+                .unwrap_or_default();
             output.push_str(&format!(
-                "  [{}] {} ({})\n",
-                info.number, info.preview, info.created_at
+                "  [{}] {}{} ({})\n",
+                info.number, info.preview, language, info.created_at
             ));
         }
 
@@ -338,6 +362,43 @@ mod tests {
         assert_eq!(stack.id, 1);
     }
 
+    #[test]
+    fn test_save_stack_with_language() {
+        let mut service = StackService::new();
+        service.enable_stack_mode();
+        let id =
+            service.save_stack_with_language("Fix typo".to_string(), Some("english".to_string()));
+
+        let stack = service.get_stack(id).unwrap();
+        assert_eq!(stack.language.as_deref(), Some("english"));
+        assert!(
+            service
+                .list_stacks_formatted()
+                .contains("Fix typo <english>")
+        );
+    }
+
+    #[test]
+    fn test_save_transcript_keeps_timestamps() {
+        use crate::domain::transcript::TranscriptSegment;
+
+        let mut service = StackService::new();
+        let transcript = Transcript {
+            language: Some("japanese".to_string()),
+            segments: vec![
+                TranscriptSegment::new(0.0, 1.0, "一文目。"),
+                TranscriptSegment::new(1.0, 2.5, "二文目。"),
+            ],
+            ..Transcript::new("一文目。二文目。")
+        };
+        let id = service.save_transcript(&transcript);
+
+        let stack = service.get_stack(id).unwrap();
+        assert_eq!(stack.text, "一文目。二文目。");
+        assert_eq!(stack.language.as_deref(), Some("japanese"));
+        assert_eq!(stack.segments, transcript.segments);
+    }
+
     #[test]
     fn test_list_and_clear_stacks() {
         let mut service = StackService::new();
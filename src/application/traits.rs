@@ -1,9 +1,13 @@
 //! Application層の抽象化トレイト定義
 //! 外部依存を抽象化し、テスト可能な構造を提供します
 
+use crate::application::pipeline::ProcessContext;
+use crate::application::{Transcript, TranscriptionOptions};
 use crate::error::Result;
+use crate::infrastructure::audio::PcmChunk;
 use crate::infrastructure::audio::cpal_backend::AudioData;
 use async_trait::async_trait;
+use tokio::sync::mpsc;
 
 /// 音声録音機能の抽象化
 #[async_trait]
@@ -21,8 +25,65 @@ pub trait AudioRecorder: Send + Sync {
 /// 音声文字起こし機能の抽象化
 #[async_trait]
 pub trait TranscriptionClient: Send + Sync {
-    /// 音声データを文字起こし
-    async fn transcribe(&self, audio: AudioData, language: &str) -> Result<String>;
+    /// 音声データを文字起こし（言語・プロンプトは `options` で指定）
+    async fn transcribe(
+        &self,
+        audio: AudioData,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcript>;
+}
+
+/// ストリーミング文字起こし機能の抽象化
+///
+/// 録音中に音声を逐次送り、途中結果を受け取りながら停止直後に確定結果を得る。
+#[async_trait]
+pub trait StreamingTranscriptionClient: Send + Sync {
+    /// ストリーミングセッションを開始（途中結果は確定済み部分を含む全文として `partials` に送る）
+    async fn open(
+        &self,
+        options: &TranscriptionOptions,
+        partials: mpsc::UnboundedSender<String>,
+    ) -> Result<Box<dyn TranscriptionStream>>;
+}
+
+/// 開始済みのストリーミング転写セッション
+#[async_trait]
+pub trait TranscriptionStream: Send {
+    /// 録音バッファから取り出した音声を送信
+    async fn send_audio(&mut self, chunk: &PcmChunk) -> Result<()>;
+
+    /// 音声の終端を通知し、確定した転写結果を受け取る
+    async fn finish(self: Box<Self>) -> Result<Transcript>;
+}
+
+/// 転写テキストの翻訳機能の抽象化
+#[async_trait]
+pub trait Translator: Send + Sync {
+    /// `text` を `target`（`en` などの言語コード）に翻訳
+    async fn translate(&self, text: &str, target: &str) -> Result<String>;
+}
+
+/// 転写テキストの書き直し（LLM による整形）機能の抽象化
+#[async_trait]
+pub trait Rewriter: Send + Sync {
+    /// `instructions` に従って `text` を書き直す
+    async fn rewrite(&self, text: &str, instructions: &str) -> Result<String>;
+}
+
+/// 転写後のテキスト処理（パイプラインの段）の抽象化
+#[async_trait]
+pub trait TextProcessor: Send + Sync {
+    /// このリクエストで段を適用するか（既定では常に適用）
+    fn applies(&self, _context: &ProcessContext<'_>) -> bool {
+        true
+    }
+
+    /// 転写結果を処理（失敗した場合は `transcript` を変更せずにエラーを返す）
+    async fn process(
+        &self,
+        transcript: &mut Transcript,
+        context: &ProcessContext<'_>,
+    ) -> Result<()>;
 }
 
 /// テキスト入力機能の抽象化
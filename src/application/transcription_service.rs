@@ -2,33 +2,84 @@
 //!
 //! # 責任
 //! - 音声データの文字起こし
-//! - 辞書変換の適用
+//! - プロファイルの適用
+//! - 辞書変換・LLM による書き直し・翻訳などのテキスト処理パイプラインの適用
+//! - 転写結果キャッシュの参照
+//! - 失敗した転写を保存するキューの保持
 //! - 同時実行数の制御
 
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Semaphore;
 
-use crate::application::traits::TranscriptionClient;
-use crate::domain::dict::{DictRepository, apply_replacements};
+use crate::application::pipeline::{
+    DictionaryProcessor, FillerProcessor, NormalizeProcessor, ProcessContext, RewriteProcessor,
+    SpokenCommandProcessor, TextPipeline, TranslateProcessor,
+};
+use crate::application::traits::{Rewriter, TextProcessor, TranscriptionClient, Translator};
+use crate::domain::dict::DictRepository;
+use crate::domain::phantom::PhantomPhraseFilter;
+use crate::domain::pipeline::{PipelineReport, StageKind, StageSpec};
+use crate::domain::profile::{DictionaryStage, Profile};
+use crate::domain::spoken::SpokenCommands;
+pub use crate::domain::transcript::Transcript;
 use crate::error::{Result, VoiceInputError};
 use crate::infrastructure::audio::cpal_backend::AudioData;
+use crate::infrastructure::audio::vad::{VadConfig, speech_ms};
+use crate::infrastructure::cache::TranscriptionCache;
 use crate::infrastructure::dict::JsonFileDictRepo;
+use crate::infrastructure::external::hook::CommandHookProcessor;
+use crate::infrastructure::queue::TranscriptionQueue;
+
+/// 既定の転写言語
+pub const DEFAULT_LANGUAGE: &str = "ja";
+
+/// 言語を自動判定させる指定値
+pub const AUTO_LANGUAGE: &str = "auto";
+
+/// 翻訳しない指定値（既定の翻訳先やプロファイルの設定を打ち消す）
+pub const NO_TRANSLATION: &str = "off";
+
+/// 書き直しのタイムアウトの既定値
+pub const DEFAULT_REWRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 信頼度のしきい値の既定値（これを下回る結果は直接入力しない）
+///
+/// Whisper が失敗とみなす `avg_logprob` の -1.0（信頼度で約 0.37）より緩くし、
+/// 普通の発話（`avg_logprob` が -1.0 前後の日本語など）は止めない。
+pub const DEFAULT_MIN_CONFIDENCE: f64 = 0.3;
 
 /// 転写オプション
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TranscriptionOptions {
-    /// 言語設定
-    pub language: String,
+    /// 言語設定（`None` ならサービスの既定言語、`"auto"` なら自動判定）
+    pub language: Option<String>,
     /// プロンプト（コンテキスト）
     pub prompt: Option<String>,
+    /// 翻訳先の言語（`None` ならプロファイル・既定値に従い、`"off"` なら翻訳しない）
+    pub translate: Option<String>,
+    /// プロファイル名（`None` なら既定のプロファイル）
+    pub profile: Option<String>,
 }
 
-impl Default for TranscriptionOptions {
-    fn default() -> Self {
-        Self {
-            language: "ja".to_string(),
-            prompt: None,
-        }
+impl TranscriptionOptions {
+    /// API に渡す言語コード（自動判定の場合は `None`）
+    pub fn language_code(&self) -> Option<&str> {
+        self.language
+            .as_deref()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.eq_ignore_ascii_case(AUTO_LANGUAGE))
+    }
+
+    /// 翻訳先の言語コード（翻訳しない場合は `None`）
+    pub fn translate_target(&self) -> Option<&str> {
+        self.translate
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty() && !t.eq_ignore_ascii_case(NO_TRANSLATION))
     }
 }
 
@@ -37,9 +88,39 @@ pub struct TranscriptionService {
     /// 転写クライアント（抽象化されたインターフェース）
     client: Box<dyn TranscriptionClient>,
     /// 辞書リポジトリ
-    dict_repo: Box<dyn DictRepository>,
+    dict_repo: Arc<dyn DictRepository>,
     /// 同時実行数制限用セマフォ
     semaphore: Arc<Semaphore>,
+    /// リクエストで言語が指定されなかった場合の言語
+    default_language: String,
+    /// 転写結果キャッシュ（無効な場合は `None`）
+    cache: Option<TranscriptionCache>,
+    /// 転写に失敗した録音の保存先（無効な場合は `None`）
+    queue: Option<TranscriptionQueue>,
+    /// 翻訳クライアント（未設定なら翻訳指定は無視して原文を返す）
+    translator: Option<Arc<dyn Translator>>,
+    /// 翻訳先の既定値
+    default_translate: Option<String>,
+    /// 書き直しクライアント（未設定なら書き直しの指示は無視して転写結果を返す）
+    rewriter: Option<Arc<dyn Rewriter>>,
+    /// 書き直しの指示の既定値（`None` なら書き直さない）
+    rewrite_instructions: Option<String>,
+    /// 書き直しのタイムアウト（超えた場合は転写結果をそのまま使う）
+    rewrite_timeout: Duration,
+    /// 翻訳する場合に辞書変換を適用する段階の既定値
+    dictionary_stage: DictionaryStage,
+    /// 転写後のテキスト処理の段（上から順に適用）
+    pipeline: Vec<StageSpec>,
+    /// 信頼度のしきい値（下回った結果に `low_confidence` を立てる。0 で無効）
+    min_confidence: f64,
+    /// 無音時の定型句の検出（無効な場合は `None`）
+    phantom_filter: Option<PhantomPhraseFilter>,
+    /// 録音に発話が含まれているかの判定に使う VAD 設定
+    speech_vad: VadConfig,
+    /// 名前付きプロファイル
+    profiles: BTreeMap<String, Profile>,
+    /// リクエストでプロファイルが指定されなかった場合のプロファイル
+    default_profile: Option<String>,
 }
 
 impl TranscriptionService {
@@ -51,11 +132,155 @@ impl TranscriptionService {
     ) -> Self {
         Self {
             client,
-            dict_repo,
+            dict_repo: Arc::from(dict_repo),
             semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            default_language: DEFAULT_LANGUAGE.to_string(),
+            cache: None,
+            queue: None,
+            translator: None,
+            default_translate: None,
+            rewriter: None,
+            rewrite_instructions: None,
+            rewrite_timeout: DEFAULT_REWRITE_TIMEOUT,
+            dictionary_stage: DictionaryStage::default(),
+            pipeline: StageSpec::default_pipeline(),
+            min_confidence: DEFAULT_MIN_CONFIDENCE,
+            phantom_filter: None,
+            speech_vad: VadConfig::default(),
+            profiles: BTreeMap::new(),
+            default_profile: None,
         }
     }
 
+    /// 転写結果キャッシュを設定
+    pub fn with_cache(mut self, cache: TranscriptionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 転写に失敗した録音の保存先を設定
+    pub fn with_queue(mut self, queue: TranscriptionQueue) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// 転写に失敗した録音の保存先を取得
+    pub fn queue(&self) -> Option<&TranscriptionQueue> {
+        self.queue.as_ref()
+    }
+
+    /// 既定の言語を設定（`"auto"` で自動判定）
+    pub fn with_default_language(mut self, language: impl Into<String>) -> Self {
+        self.default_language = language.into();
+        self
+    }
+
+    /// 既定の言語を取得
+    pub fn default_language(&self) -> &str {
+        &self.default_language
+    }
+
+    /// 翻訳クライアントを設定
+    pub fn with_translator(mut self, translator: Box<dyn Translator>) -> Self {
+        self.translator = Some(Arc::from(translator));
+        self
+    }
+
+    /// 翻訳先の既定値を設定
+    pub fn with_default_translate(mut self, target: Option<String>) -> Self {
+        self.default_translate = target;
+        self
+    }
+
+    /// 書き直しクライアントを設定
+    pub fn with_rewriter(mut self, rewriter: Box<dyn Rewriter>) -> Self {
+        self.rewriter = Some(Arc::from(rewriter));
+        self
+    }
+
+    /// 書き直しの指示の既定値を設定
+    pub fn with_rewrite_instructions(mut self, instructions: Option<String>) -> Self {
+        self.rewrite_instructions = instructions;
+        self
+    }
+
+    /// 書き直しのタイムアウトを設定
+    pub fn with_rewrite_timeout(mut self, timeout: Duration) -> Self {
+        self.rewrite_timeout = timeout;
+        self
+    }
+
+    /// 翻訳する場合に辞書変換を適用する段階を設定
+    pub fn with_dictionary_stage(mut self, stage: DictionaryStage) -> Self {
+        self.dictionary_stage = stage;
+        self
+    }
+
+    /// テキスト処理の段を設定（`None` なら辞書変換・書き直し・翻訳の既定のパイプライン）
+    pub fn with_pipeline(mut self, stages: Option<Vec<StageSpec>>) -> Self {
+        self.pipeline = stages.unwrap_or_else(StageSpec::default_pipeline);
+        self
+    }
+
+    /// 信頼度のしきい値を設定（0 で判定しない）
+    pub fn with_min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    /// 無音時の定型句の検出を設定（`vad` で録音に発話が含まれているかを判定）
+    pub fn with_phantom_filter(mut self, filter: PhantomPhraseFilter, vad: VadConfig) -> Self {
+        self.phantom_filter = Some(filter);
+        self.speech_vad = vad;
+        self
+    }
+
+    /// 名前付きプロファイルを設定
+    pub fn with_profiles(mut self, profiles: BTreeMap<String, Profile>) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    /// 既定のプロファイルを設定
+    pub fn with_default_profile(mut self, name: Option<String>) -> Self {
+        self.default_profile = name;
+        self
+    }
+
+    /// 名前付きプロファイルを取得
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(name)
+    }
+
+    /// リクエストの指定・プロファイル・既定値の順に転写オプションを確定
+    pub fn resolve_options(
+        &self,
+        mut options: TranscriptionOptions,
+    ) -> Result<TranscriptionOptions> {
+        if options.profile.is_none() {
+            options.profile = self.default_profile.clone();
+        }
+        let profile = match options.profile.as_deref() {
+            Some(name) => Some(
+                self.profile(name)
+                    .ok_or_else(|| VoiceInputError::UnknownProfile(name.to_string()))?,
+            ),
+            None => None,
+        };
+
+        if options.language.is_none() {
+            options.language = profile
+                .and_then(|p| p.language.clone())
+                .or_else(|| Some(self.default_language.clone()));
+        }
+        if options.translate.is_none() {
+            options.translate = profile
+                .and_then(|p| p.translate.clone())
+                .or_else(|| self.default_translate.clone());
+        }
+        Ok(options)
+    }
+
     /// デフォルト設定で作成
     pub fn with_default_repo(client: Box<dyn TranscriptionClient>) -> Self {
         Self::new(
@@ -70,37 +295,230 @@ impl TranscriptionService {
         &self,
         audio: AudioData,
         options: TranscriptionOptions,
-    ) -> Result<String> {
+    ) -> Result<Transcript> {
         // セマフォで同時実行数を制限
         let _permit = self.semaphore.acquire().await.map_err(|e| {
             VoiceInputError::SystemError(format!("Semaphore acquire failed: {}", e))
         })?;
 
-        // 転写実行
-        let text = self.client.transcribe(audio, &options.language).await?;
+        // プロファイルと既定値を反映
+        let options = self.resolve_options(options)?;
+
+        // 定型句の判定用に、音声を渡す前に発話の長さを測っておく
+        let speech = self
+            .phantom_filter
+            .as_ref()
+            .and_then(|_| self.measure_speech(&audio));
+
+        // キャッシュにあれば API を呼ばない（辞書変換前の結果を保存している）
+        let cached = self
+            .cache
+            .as_ref()
+            .map(|cache| (cache, cache.key(&audio.0, &options)));
+        let hit = cached.as_ref().and_then(|(cache, key)| cache.get(key));
+
+        let mut transcript = match hit {
+            Some(transcript) => transcript,
+            None => {
+                // 転写実行
+                let transcript = self.client.transcribe(audio, &options).await?;
+                // フォールバック先の結果は主のプロバイダーが復旧したら転写し直す
+                if let Some((cache, key)) = cached.as_ref().filter(|_| !transcript.fallback) {
+                    if let Err(e) = cache.put(key, &transcript) {
+                        eprintln!("Failed to write transcription cache: {}", e);
+                    }
+                }
+                transcript
+            }
+        };
+
+        // 書き直し・翻訳で対数確率との対応が崩れる前に信頼度を判定
+        self.assess_confidence(&mut transcript);
+        self.filter_phantom(&mut transcript, speech);
 
-        // 辞書変換を適用
-        let processed = self.apply_dictionary(&text)?;
+        // 辞書変換・書き直し・翻訳などを適用
+        self.postprocess(&mut transcript, &options).await;
 
-        Ok(processed)
+        Ok(transcript)
     }
 
-    /// 辞書変換を適用
-    fn apply_dictionary(&self, text: &str) -> Result<String> {
-        let mut entries = self.dict_repo.load().map_err(|e| {
-            VoiceInputError::SystemError(format!("Failed to load dictionary: {}", e))
-        })?;
+    /// ストリーミング転写などで得た転写結果に辞書変換・書き直し・翻訳を適用
+    pub async fn finalize(
+        &self,
+        mut transcript: Transcript,
+        options: TranscriptionOptions,
+    ) -> Result<Transcript> {
+        let options = self.resolve_options(options)?;
+        self.assess_confidence(&mut transcript);
+        self.filter_phantom(&mut transcript, None);
+        self.postprocess(&mut transcript, &options).await;
+        Ok(transcript)
+    }
 
-        let result = apply_replacements(text, &mut entries);
+    /// 録音中の発話の長さ（WAV として解析できない場合は `None`）
+    fn measure_speech(&self, audio: &AudioData) -> Option<Duration> {
+        let wav = audio.parse_wav().ok()?;
+        let ms = speech_ms(
+            &wav.samples_i16(),
+            wav.format.sample_rate,
+            wav.format.channels,
+            &self.speech_vad,
+        );
+        Some(Duration::from_millis(ms as u64))
+    }
+
+    /// 転写結果が無音時の定型句だけなら、録音に発話がなければ捨て、あれば `low_confidence` を立てる
+    ///
+    /// 発話の長さが分からない場合（ストリーミング転写など）は捨てずに `low_confidence` を立てる。
+    /// 一覧を調整できるよう、一致した定型句をログに出力する。
+    fn filter_phantom(&self, transcript: &mut Transcript, speech: Option<Duration>) {
+        let Some(phrase) = self
+            .phantom_filter
+            .as_ref()
+            .and_then(|filter| filter.matches(&transcript.text))
+        else {
+            return;
+        };
+        let min_speech = Duration::from_millis(self.speech_vad.min_speech_ms as u64);
+        match speech {
+            Some(speech) if speech < min_speech => {
+                eprintln!(
+                    "🔇 Dropped phantom phrase {:?} (matched {:?}, speech {} ms)",
+                    transcript.text,
+                    phrase,
+                    speech.as_millis()
+                );
+                *transcript = Transcript {
+                    provider: transcript.provider.take(),
+                    ..Transcript::default()
+                };
+            }
+            _ => {
+                eprintln!(
+                    "⚠️  Possible phantom phrase {:?} (matched {:?}, speech {})",
+                    transcript.text,
+                    phrase,
+                    speech.map_or("unknown".to_string(), |s| format!("{} ms", s.as_millis()))
+                );
+                transcript.low_confidence = true;
+            }
+        }
+    }
 
-        // 変更があった場合は保存
-        if entries.iter().any(|e| e.hit > 0) {
-            self.dict_repo.save(&entries).map_err(|e| {
-                VoiceInputError::SystemError(format!("Failed to save dictionary: {}", e))
-            })?;
+    /// 信頼度を推定し、しきい値を下回れば `low_confidence` を立てる
+    fn assess_confidence(&self, transcript: &mut Transcript) {
+        transcript.confidence = transcript.estimate_confidence();
+        transcript.low_confidence = transcript
+            .confidence
+            .is_some_and(|confidence| confidence < self.min_confidence);
+        if transcript.low_confidence {
+            eprintln!(
+                "⚠️  Low transcription confidence ({:.2}): {}",
+                transcript.confidence.unwrap_or_default(),
+                transcript.text
+            );
         }
+    }
+
+    /// テキスト処理パイプラインを適用し、段ごとの処理時間をログに出力
+    async fn postprocess(&self, transcript: &mut Transcript, options: &TranscriptionOptions) {
+        let report = self.run_pipeline(transcript, options, false).await;
+        if !report.stages.is_empty() {
+            eprintln!(
+                "⏱  Text pipeline: {} (total {}ms)",
+                report.timings(),
+                report.total_ms()
+            );
+        }
+    }
+
+    /// テキストをパイプラインに通し、段ごとの結果を返す（辞書の使用回数は記録しない）
+    pub async fn test_pipeline(
+        &self,
+        text: &str,
+        options: TranscriptionOptions,
+    ) -> Result<PipelineReport> {
+        let options = self.resolve_options(options)?;
+        let mut transcript = Transcript::new(text);
+        Ok(self.run_pipeline(&mut transcript, &options, true).await)
+    }
+
+    /// 設定された段を順に適用
+    ///
+    /// 書き直しは話した言語のテキストに対して行い、翻訳はその結果に対して行う（既定の順序の場合）。
+    async fn run_pipeline(
+        &self,
+        transcript: &mut Transcript,
+        options: &TranscriptionOptions,
+        dry_run: bool,
+    ) -> PipelineReport {
+        let profile = options
+            .profile
+            .as_deref()
+            .and_then(|name| self.profile(name));
+
+        // 話した言語と翻訳先が同じなら翻訳しない
+        let translate = options.translate_target().filter(|target| {
+            options
+                .language_code()
+                .is_none_or(|language| !language.eq_ignore_ascii_case(target))
+        });
+        let dictionary_stage = match translate {
+            Some(_) => profile
+                .and_then(|p| p.dictionary)
+                .unwrap_or(self.dictionary_stage),
+            None => DictionaryStage::Before,
+        };
+        let context = ProcessContext {
+            options,
+            profile,
+            translate,
+            dictionary_stage,
+            dry_run,
+        };
+        self.pipeline().run(transcript, &context).await
+    }
+
+    /// 設定された段からパイプラインを組み立てる（作成できない段は警告を出して除く）
+    fn pipeline(&self) -> TextPipeline {
+        self.pipeline
+            .iter()
+            .fold(TextPipeline::new(), |pipeline, spec| {
+                match self.processor(&spec.kind) {
+                    Ok(processor) => pipeline.with_stage(spec.clone(), processor),
+                    Err(e) => {
+                        eprintln!("Skipping text processing stage {}: {}", spec.name(), e);
+                        pipeline
+                    }
+                }
+            })
+    }
 
-        Ok(result)
+    /// 段の種類に対応する処理を作成
+    fn processor(&self, kind: &StageKind) -> Result<Box<dyn TextProcessor>> {
+        Ok(match kind {
+            StageKind::Dictionary { when } => {
+                Box::new(DictionaryProcessor::new(self.dict_repo.clone(), *when))
+            }
+            StageKind::Rewrite => Box::new(RewriteProcessor::new(
+                self.rewriter.clone(),
+                self.rewrite_instructions.clone(),
+                self.rewrite_timeout,
+            )),
+            StageKind::Translate => Box::new(TranslateProcessor::new(self.translator.clone())),
+            StageKind::SpokenCommands(config) => {
+                Box::new(SpokenCommandProcessor::new(SpokenCommands::new(config)))
+            }
+            StageKind::Normalize(config) => Box::new(NormalizeProcessor::new(config.clone())),
+            StageKind::Fillers(config) => Box::new(FillerProcessor::new(config.clone())),
+            StageKind::Hook {
+                command,
+                timeout_secs,
+            } => Box::new(CommandHookProcessor::new(
+                command,
+                Duration::from_secs(*timeout_secs),
+            )?),
+        })
     }
 
     /// セマフォの現在の利用可能数を取得（デバッグ用）
@@ -119,6 +537,7 @@ mod tests {
     struct MockTranscriptionClient {
         response: String,
         call_count: Arc<Mutex<usize>>,
+        last_options: Arc<Mutex<Option<TranscriptionOptions>>>,
     }
 
     impl MockTranscriptionClient {
@@ -126,6 +545,7 @@ mod tests {
             Self {
                 response: response.to_string(),
                 call_count: Arc::new(Mutex::new(0)),
+                last_options: Arc::new(Mutex::new(None)),
             }
         }
 
@@ -137,9 +557,26 @@ mod tests {
 
     #[async_trait]
     impl TranscriptionClient for MockTranscriptionClient {
-        async fn transcribe(&self, _audio: AudioData, _language: &str) -> Result<String> {
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            options: &TranscriptionOptions,
+        ) -> Result<Transcript> {
             *self.call_count.lock().unwrap() += 1;
-            Ok(self.response.clone())
+            *self.last_options.lock().unwrap() = Some(options.clone());
+            Ok(Transcript {
+                text: self.response.clone(),
+                language: options
+                    .language_code()
+                    .is_none()
+                    .then(|| "english".to_string()),
+                segments: vec![crate::domain::transcript::TranscriptSegment::new(
+                    0.0,
+                    1.0,
+                    self.response.clone(),
+                )],
+                ..Default::default()
+            })
         }
     }
 
@@ -181,7 +618,537 @@ mod tests {
         let options = TranscriptionOptions::default();
 
         let result = service.transcribe(audio, options).await.unwrap();
-        assert_eq!(result, "これはtestです");
+        assert_eq!(result.text, "これはtestです");
+        // セグメントにも同じ置換を適用する
+        assert_eq!(result.segments[0].text, "これはtestです");
+    }
+
+    #[tokio::test]
+    async fn test_prompt_passed_to_client() {
+        let client = MockTranscriptionClient::new("結果");
+        let last_options = client.last_options.clone();
+        let service = TranscriptionService::new(Box::new(client), Box::new(MockDictRepo::new()), 1);
+
+        let options = TranscriptionOptions {
+            prompt: Some("選択中のテキスト".to_string()),
+            ..Default::default()
+        };
+        service
+            .transcribe(AudioData(vec![0u8; 100]), options)
+            .await
+            .unwrap();
+
+        let received = last_options.lock().unwrap().clone().unwrap();
+        assert_eq!(received.prompt.as_deref(), Some("選択中のテキスト"));
+        assert_eq!(received.language_code(), Some("ja"));
+    }
+
+    #[tokio::test]
+    async fn test_language_resolution() {
+        let client = MockTranscriptionClient::new("hello");
+        let last_options = client.last_options.clone();
+        let service = TranscriptionService::new(Box::new(client), Box::new(MockDictRepo::new()), 1)
+            .with_default_language("en");
+
+        // 指定なしは既定の言語
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        let received = last_options.lock().unwrap().clone().unwrap();
+        assert_eq!(received.language_code(), Some("en"));
+        assert_eq!(result.language, None);
+
+        // auto は言語パラメータを送らず、検出結果を保持する
+        let options = TranscriptionOptions {
+            language: Some("auto".to_string()),
+            ..Default::default()
+        };
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), options)
+            .await
+            .unwrap();
+        let received = last_options.lock().unwrap().clone().unwrap();
+        assert_eq!(received.language_code(), None);
+        assert_eq!(result.language.as_deref(), Some("english"));
+    }
+
+    #[tokio::test]
+    async fn test_cache_skips_client_for_same_audio() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let client = MockTranscriptionClient::new("これはテストです");
+        let call_count = client.call_count.clone();
+        let cache = TranscriptionCache::new(tmp.path(), 1024 * 1024);
+        let service = TranscriptionService::new(Box::new(client), Box::new(MockDictRepo::new()), 1)
+            .with_cache(cache.clone());
+
+        for _ in 0..2 {
+            let result = service
+                .transcribe(AudioData(vec![1u8; 100]), TranscriptionOptions::default())
+                .await
+                .unwrap();
+            // キャッシュから返す場合も辞書変換を適用する
+            assert_eq!(result.text, "これはtestです");
+        }
+        assert_eq!(*call_count.lock().unwrap(), 1);
+
+        // 辞書変換前の結果を保存している
+        let options = TranscriptionOptions {
+            language: Some(DEFAULT_LANGUAGE.to_string()),
+            ..Default::default()
+        };
+        let stored = cache.get(&cache.key(&[1u8; 100], &options)).unwrap();
+        assert_eq!(stored.text, "これはテストです");
+
+        // 音声や言語が異なれば API を呼ぶ
+        service
+            .transcribe(AudioData(vec![2u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        let options = TranscriptionOptions {
+            language: Some("en".to_string()),
+            ..Default::default()
+        };
+        service
+            .transcribe(AudioData(vec![1u8; 100]), options)
+            .await
+            .unwrap();
+        assert_eq!(*call_count.lock().unwrap(), 3);
+    }
+
+    /// 常にネットワークエラーを返すクライアント
+    struct OfflineClient;
+
+    #[async_trait]
+    impl TranscriptionClient for OfflineClient {
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            _options: &TranscriptionOptions,
+        ) -> Result<Transcript> {
+            Err(VoiceInputError::NetworkError("offline".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_skips_fallback_results() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let local = MockTranscriptionClient::new("ローカル");
+        let call_count = local.call_count.clone();
+        let client = crate::application::FallbackTranscriptionClient::new()
+            .with_provider("openai", Box::new(OfflineClient))
+            .with_provider("whisper-cpp", Box::new(local));
+        let service = TranscriptionService::new(Box::new(client), Box::new(MockDictRepo::new()), 1)
+            .with_cache(TranscriptionCache::new(tmp.path(), 1024 * 1024));
+
+        // 主のプロバイダーが復旧すれば転写し直せるよう、フォールバック先の結果は保存しない
+        for _ in 0..2 {
+            let result = service
+                .transcribe(AudioData(vec![1u8; 100]), TranscriptionOptions::default())
+                .await
+                .unwrap();
+            assert!(result.fallback);
+        }
+        assert_eq!(*call_count.lock().unwrap(), 2);
+    }
+
+    /// テスト用のモック翻訳クライアント（`[target] text` を返す）
+    struct MockTranslator {
+        fail: bool,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Translator for MockTranslator {
+        async fn translate(&self, text: &str, target: &str) -> Result<String> {
+            self.calls.lock().unwrap().push(text.to_string());
+            if self.fail {
+                return Err(VoiceInputError::NetworkError("offline".to_string()));
+            }
+            Ok(format!("[{}] {}", target, text))
+        }
+    }
+
+    fn translating_service(fail: bool) -> (TranscriptionService, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let translator = MockTranslator {
+            fail,
+            calls: calls.clone(),
+        };
+        let service = TranscriptionService::new(
+            Box::new(MockTranscriptionClient::new("これはテストです")),
+            Box::new(MockDictRepo::new()),
+            1,
+        )
+        .with_translator(Box::new(translator));
+        (service, calls)
+    }
+
+    fn translate_to(target: &str) -> TranscriptionOptions {
+        TranscriptionOptions {
+            translate: Some(target.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_translation_dictionary_stage() {
+        // 既定では翻訳前に辞書変換する
+        let (service, calls) = translating_service(false);
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), translate_to("en"))
+            .await
+            .unwrap();
+        assert_eq!(result.text, "[en] これはtestです");
+        assert_eq!(calls.lock().unwrap().as_slice(), ["これはtestです"]);
+
+        // 翻訳後に適用する設定では訳文を置換する
+        let (service, calls) = translating_service(false);
+        let service = service.with_dictionary_stage(DictionaryStage::After);
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), translate_to("en"))
+            .await
+            .unwrap();
+        assert_eq!(calls.lock().unwrap().as_slice(), ["これはテストです"]);
+        assert_eq!(result.text, "[en] これはtestです");
+    }
+
+    #[tokio::test]
+    async fn test_translation_skipped_or_failed() {
+        let (service, calls) = translating_service(true);
+
+        // 失敗した場合は辞書変換済みの原文を返す
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), translate_to("en"))
+            .await
+            .unwrap();
+        assert_eq!(result.text, "これはtestです");
+        assert_eq!(calls.lock().unwrap().len(), 1);
+
+        // 話した言語と同じ言語・off の指定では翻訳しない
+        for options in [translate_to("ja"), translate_to("off")] {
+            service
+                .transcribe(AudioData(vec![0u8; 100]), options)
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_profile_resolution() {
+        let (service, calls) = translating_service(false);
+        let profiles = BTreeMap::from([(
+            "pr".to_string(),
+            Profile {
+                language: Some("ja".to_string()),
+                translate: Some("en".to_string()),
+                dictionary: Some(DictionaryStage::After),
+                ..Default::default()
+            },
+        )]);
+        let service = service
+            .with_default_language("auto")
+            .with_profiles(profiles)
+            .with_default_profile(Some("pr".to_string()));
+
+        // 既定のプロファイルを適用
+        let options = service
+            .resolve_options(TranscriptionOptions::default())
+            .unwrap();
+        assert_eq!(options.profile.as_deref(), Some("pr"));
+        assert_eq!(options.language_code(), Some("ja"));
+        assert_eq!(options.translate_target(), Some("en"));
+
+        // リクエストの指定はプロファイルより優先
+        let options = service
+            .resolve_options(TranscriptionOptions {
+                language: Some("en".to_string()),
+                translate: Some("off".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(options.language_code(), Some("en"));
+        assert_eq!(options.translate_target(), None);
+
+        // プロファイルの辞書変換の段階を使う
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(calls.lock().unwrap().as_slice(), ["これはテストです"]);
+        assert_eq!(result.text, "[en] これはtestです");
+
+        let err = service
+            .resolve_options(TranscriptionOptions {
+                profile: Some("chat".to_string()),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(matches!(err, VoiceInputError::UnknownProfile(name) if name == "chat"));
+    }
+
+    /// テスト用のモック書き直しクライアント（`delay` の間待ってから `<指示> text` を返す）
+    struct MockRewriter {
+        delay: Duration,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Rewriter for MockRewriter {
+        async fn rewrite(&self, text: &str, instructions: &str) -> Result<String> {
+            self.calls.lock().unwrap().push(text.to_string());
+            tokio::time::sleep(self.delay).await;
+            Ok(format!("<{}> {}", instructions, text))
+        }
+    }
+
+    fn rewriting_service(delay: Duration) -> (TranscriptionService, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let rewriter = MockRewriter {
+            delay,
+            calls: calls.clone(),
+        };
+        let service = TranscriptionService::new(
+            Box::new(MockTranscriptionClient::new("これはテストです")),
+            Box::new(MockDictRepo::new()),
+            1,
+        )
+        .with_rewriter(Box::new(rewriter))
+        .with_rewrite_instructions(Some("箇条書き".to_string()));
+        (service, calls)
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_after_dictionary() {
+        let (service, calls) = rewriting_service(Duration::ZERO);
+        let profiles = BTreeMap::from([
+            (
+                "chat".to_string(),
+                Profile {
+                    rewrite: Some("敬語".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "raw".to_string(),
+                Profile {
+                    rewrite: Some(String::new()),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let service = service.with_profiles(profiles);
+        let with_profile = |name: &str| TranscriptionOptions {
+            profile: Some(name.to_string()),
+            ..Default::default()
+        };
+
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.text, "<箇条書き> これはtestです");
+        assert_eq!(calls.lock().unwrap().as_slice(), ["これはtestです"]);
+
+        // プロファイルの指示が優先され、空文字列なら書き直さない
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), with_profile("chat"))
+            .await
+            .unwrap();
+        assert_eq!(result.text, "<敬語> これはtestです");
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), with_profile("raw"))
+            .await
+            .unwrap();
+        assert_eq!(result.text, "これはtestです");
+        assert_eq!(calls.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_timeout_falls_back_to_raw_text() {
+        let (service, calls) = rewriting_service(Duration::from_secs(5));
+        let service = service.with_rewrite_timeout(Duration::from_millis(50));
+
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.text, "これはtestです");
+        assert_eq!(calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_configured_pipeline() {
+        let (service, calls) = rewriting_service(Duration::ZERO);
+        let stages: Vec<StageSpec> = serde_json::from_str(
+            r#"[
+                {"stage": "rewrite", "profiles": ["chat"]},
+                {"stage": "hook", "name": "upper", "command": "tr a-z A-Z"},
+                {"stage": "dictionary"}
+            ]"#,
+        )
+        .unwrap();
+        let profiles = BTreeMap::from([("chat".to_string(), Profile::default())]);
+        let service = service.with_profiles(profiles).with_pipeline(Some(stages));
+
+        // 対象外のプロファイルの段は適用しない
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.text, "これはtestです");
+        assert!(calls.lock().unwrap().is_empty());
+
+        // 段ごとの結果を返す
+        let options = TranscriptionOptions {
+            profile: Some("chat".to_string()),
+            ..Default::default()
+        };
+        let report = service.test_pipeline("テスト", options).await.unwrap();
+        let stages: Vec<_> = report
+            .stages
+            .iter()
+            .map(|s| (s.name.as_str(), s.text.as_str()))
+            .collect();
+        assert_eq!(
+            stages,
+            [
+                ("rewrite", "<箇条書き> テスト"),
+                ("upper", "<箇条書き> テスト"),
+                ("dictionary", "<箇条書き> test"),
+            ]
+        );
+    }
+
+    /// 無音を「ご視聴ありがとうございました」と転写するクライアント
+    struct SilenceClient;
+
+    #[async_trait]
+    impl TranscriptionClient for SilenceClient {
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            _options: &TranscriptionOptions,
+        ) -> Result<Transcript> {
+            let segment = crate::domain::transcript::TranscriptSegment {
+                avg_logprob: Some(-0.3),
+                no_speech_prob: Some(0.9),
+                ..crate::domain::transcript::TranscriptSegment::new(
+                    0.0,
+                    2.0,
+                    "ご視聴ありがとうございました",
+                )
+            };
+            Ok(Transcript {
+                segments: vec![segment],
+                ..Transcript::new("ご視聴ありがとうございました")
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flags_low_confidence() {
+        let service =
+            TranscriptionService::new(Box::new(SilenceClient), Box::new(MockDictRepo::new()), 1);
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert!(result.confidence.unwrap() < 0.1);
+        assert!(result.low_confidence);
+
+        // しきい値 0 で判定しない
+        let service = service.with_min_confidence(0.0);
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert!(!result.low_confidence);
+
+        // 対数確率を返さないプロバイダーは判定しない
+        let service = TranscriptionService::new(
+            Box::new(MockTranscriptionClient::new("test")),
+            Box::new(MockDictRepo::new()),
+            1,
+        );
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.confidence, None);
+        assert!(!result.low_confidence);
+    }
+
+    /// 普通の発話（日本語で `avg_logprob` が -1.0 前後）を返すクライアント
+    struct SpeechClient;
+
+    #[async_trait::async_trait]
+    impl TranscriptionClient for SpeechClient {
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            _options: &TranscriptionOptions,
+        ) -> Result<Transcript> {
+            let segment = |start, end, text, avg_logprob, no_speech_prob| {
+                crate::domain::transcript::TranscriptSegment {
+                    avg_logprob: Some(avg_logprob),
+                    no_speech_prob: Some(no_speech_prob),
+                    ..crate::domain::transcript::TranscriptSegment::new(start, end, text)
+                }
+            };
+            Ok(Transcript {
+                segments: vec![
+                    segment(0.0, 2.0, "明日の会議は", -0.9, 0.05),
+                    segment(2.0, 4.0, "十時からです", -1.0, 0.1),
+                ],
+                ..Transcript::new("明日の会議は十時からです")
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_typical_speech_is_not_flagged() {
+        let service =
+            TranscriptionService::new(Box::new(SpeechClient), Box::new(MockDictRepo::new()), 1);
+        let result = service
+            .transcribe(AudioData(vec![0u8; 100]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert!(result.confidence.is_some());
+        assert!(!result.low_confidence);
+    }
+
+    #[tokio::test]
+    async fn test_phantom_phrase_filter() {
+        use crate::infrastructure::audio::CpalAudioBackend;
+
+        let wav = |samples: Vec<i16>| {
+            AudioData(CpalAudioBackend::combine_wav_data(&samples, 16_000, 1).unwrap())
+        };
+        let service =
+            TranscriptionService::new(Box::new(SilenceClient), Box::new(MockDictRepo::new()), 1)
+                .with_min_confidence(0.0)
+                .with_phantom_filter(PhantomPhraseFilter::default(), VadConfig::default());
+
+        // 無音の録音なら捨てる
+        let result = service
+            .transcribe(wav(vec![0; 32_000]), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.text, "");
+        assert!(result.segments.is_empty());
+
+        // 発話がある録音なら残して確認を促す
+        let speech = (0..32_000)
+            .map(|i| if i % 2 == 0 { 3000 } else { -3000 })
+            .collect();
+        let result = service
+            .transcribe(wav(speech), TranscriptionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.text, "ご視聴ありがとうございました");
+        assert!(result.low_confidence);
     }
 
     #[tokio::test]
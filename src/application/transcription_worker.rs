@@ -5,34 +5,33 @@
 //! - 辞書変換の適用
 //! - スタックへの保存
 //! - ペースト処理
+//! - 転写に失敗した録音のキューへの保存と再実行
 
 #![allow(clippy::await_holding_refcell_ref)]
 
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Semaphore;
 
-use crate::application::{
-    StackService, TranscriptionMessage, TranscriptionOptions, TranscriptionService,
-};
-use crate::error::Result;
+use crate::application::{StackService, Transcript, TranscriptionMessage, TranscriptionService};
+use crate::error::{Result, VoiceInputError};
 use crate::infrastructure::{
+    audio::cpal_backend::AudioData,
     external::{sound::resume_apple_music, text_input},
+    queue::{OutputMode, QueueRetryReport, now_secs},
     ui::{StackDisplayInfo, UiNotification, UiProcessManager},
 };
-use crate::ipc::RecordingResult;
 
 /// 転写結果を処理
 pub async fn handle_transcription(
-    result: RecordingResult,
-    paste: bool,
-    resume_music: bool,
-    direct_input: bool,
-    stack_service: Option<Rc<RefCell<StackService>>>,
-    ui_manager: Option<Rc<RefCell<UiProcessManager>>>,
+    message: TranscriptionMessage,
     transcription_service: Rc<RefCell<TranscriptionService>>,
 ) -> Result<()> {
+    let (result, options, paste, resume_music, direct_input, stack_service, ui_manager, streamed) =
+        message;
+
     // エラーが発生しても確実に音楽を再開するためにdeferパターンで実装
     let _defer_guard = scopeguard::guard(resume_music, |should_resume| {
         if should_resume {
@@ -42,44 +41,87 @@ pub async fn handle_transcription(
         }
     });
 
-    // 転写オプションを構築
-    let options = TranscriptionOptions {
-        language: "ja".to_string(),
-        prompt: None, // メモリモードではプロンプトファイルを使用しない
+    // ストリーミング転写の確定結果を待つ（失敗した場合は録音全体を転写）
+    let streamed = match streamed {
+        Some(rx) => match rx.await {
+            Ok(Ok(transcript)) => Some(transcript),
+            Ok(Err(e)) => {
+                eprintln!(
+                    "Streaming transcription failed, transcribing recording: {}",
+                    e
+                );
+                None
+            }
+            Err(_) => None,
+        },
+        None => None,
+    };
+
+    // 録音時に意図していた出力先（キューに保存する場合に記録）
+    let output = if stack_service
+        .as_ref()
+        .is_some_and(|s| s.borrow().is_stack_mode_enabled())
+    {
+        OutputMode::Stack
+    } else if paste && direct_input {
+        OutputMode::Input
+    } else {
+        OutputMode::None
     };
 
     // 転写実行
-    let text = transcription_service
-        .borrow()
-        .transcribe(result.audio_data.into(), options)
-        .await?;
+    let transcript = match streamed {
+        Some(transcript) => {
+            transcription_service
+                .borrow()
+                .finalize(transcript, options)
+                .await?
+        }
+        None => {
+            let audio: AudioData = result.audio_data.into();
+            // 失敗時にキューへ保存するため音声を残しておく
+            let backup = transcription_service
+                .borrow()
+                .queue()
+                .map(|_| audio.0.clone());
+            let transcribed = transcription_service
+                .borrow()
+                .transcribe(audio, options.clone())
+                .await;
+            match (transcribed, backup) {
+                (Ok(transcript), _) => transcript,
+                (Err(e), Some(audio)) if e.is_provider_failure() => {
+                    enqueue_failed(
+                        &transcription_service,
+                        &audio,
+                        result.duration_ms,
+                        options,
+                        output,
+                        &e,
+                    );
+                    return Err(e);
+                }
+                (Err(e), _) => return Err(e),
+            }
+        }
+    };
+    let text = transcript.text.clone();
+
+    // 発話がなかった（無音時の定型句を捨てた）場合は何も出力しない
+    if text.trim().is_empty() {
+        println!("🔇 No speech detected");
+        return Ok(());
+    }
 
     // スタックモードが有効な場合は自動保存
     if let Some(stack_service_ref) = &stack_service {
         if stack_service_ref.borrow().is_stack_mode_enabled() {
-            let stack_id = stack_service_ref.borrow_mut().save_stack(text.clone());
-            let preview = text.chars().take(30).collect::<String>();
-            println!(
-                "{}",
-                crate::application::UserFeedback::stack_saved(stack_id, &preview)
-            );
-
-            // UI にスタック追加を通知
-            if let Some(ui_manager_ref) = &ui_manager {
-                if let Ok(manager) = ui_manager_ref.try_borrow() {
-                    let stack_info = StackDisplayInfo {
-                        number: stack_id,
-                        preview: preview.clone(),
-                        created_at: std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs()
-                            .to_string(),
-                        is_active: false,
-                        char_count: text.len(),
-                    };
-                    let _ = manager.notify(UiNotification::StackAdded(stack_info));
-                }
+            let stack_id = save_to_stack(stack_service_ref, ui_manager.as_ref(), &transcript);
+            if transcript.low_confidence {
+                warn_low_confidence(
+                    ui_manager.as_ref(),
+                    format!("Low confidence transcript in stack {}", stack_id),
+                );
             }
         }
     }
@@ -93,6 +135,19 @@ pub async fn handle_transcription(
                 .borrow()
                 .is_stack_mode_enabled());
 
+    // 信頼度が低い結果（無音での幻聴など）は直接入力せず、スタックに保存して確認を促す
+    if should_paste && transcript.low_confidence {
+        let message = match &stack_service {
+            Some(stack_service_ref) => format!(
+                "Low confidence transcript saved to stack {} instead of typing",
+                save_to_stack(stack_service_ref, ui_manager.as_ref(), &transcript)
+            ),
+            None => "Low confidence transcript was not typed".to_string(),
+        };
+        warn_low_confidence(ui_manager.as_ref(), message);
+        return Ok(());
+    }
+
     // 即貼り付け
     if should_paste {
         tokio::time::sleep(tokio::time::Duration::from_millis(80)).await;
@@ -115,6 +170,152 @@ pub async fn handle_transcription(
     Ok(())
 }
 
+/// 転写結果をスタックに保存して UI に通知
+fn save_to_stack(
+    stack_service: &Rc<RefCell<StackService>>,
+    ui_manager: Option<&Rc<RefCell<UiProcessManager>>>,
+    transcript: &Transcript,
+) -> u32 {
+    let text = &transcript.text;
+    let stack_id = stack_service.borrow_mut().save_transcript(transcript);
+    let preview = text.chars().take(30).collect::<String>();
+    println!(
+        "{}",
+        crate::application::UserFeedback::stack_saved(stack_id, &preview)
+    );
+
+    // UI にスタック追加を通知
+    if let Some(ui_manager_ref) = ui_manager {
+        if let Ok(manager) = ui_manager_ref.try_borrow() {
+            let stack_info = StackDisplayInfo {
+                number: stack_id,
+                preview: preview.clone(),
+                created_at: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+                    .to_string(),
+                is_active: false,
+                char_count: text.len(),
+            };
+            let _ = manager.notify(UiNotification::StackAdded(stack_info));
+        }
+    }
+    stack_id
+}
+
+/// 信頼度の低い転写結果について警告し、UI にも表示
+fn warn_low_confidence(ui_manager: Option<&Rc<RefCell<UiProcessManager>>>, message: String) {
+    eprintln!("⚠️  {}", message);
+    if let Some(ui_manager_ref) = ui_manager {
+        if let Ok(manager) = ui_manager_ref.try_borrow() {
+            let _ = manager.notify(UiNotification::Warning(message));
+        }
+    }
+}
+
+/// 転写に失敗した録音をキューに保存（保存できなかった場合は警告のみ）
+fn enqueue_failed(
+    transcription_service: &Rc<RefCell<TranscriptionService>>,
+    audio: &[u8],
+    duration_ms: u64,
+    options: crate::application::TranscriptionOptions,
+    output: OutputMode,
+    error: &VoiceInputError,
+) {
+    let service = transcription_service.borrow();
+    let Some(queue) = service.queue() else {
+        return;
+    };
+    match queue.enqueue(
+        audio,
+        duration_ms,
+        options,
+        output,
+        &error.to_string(),
+        error.is_retryable(),
+    ) {
+        Ok(job) => println!(
+            "📥 Transcription queued as {} (retry with `voice_input queue retry`)",
+            job.id
+        ),
+        Err(e) => eprintln!("Failed to queue failed transcription: {}", e),
+    }
+}
+
+/// キューに保存された転写を再実行
+///
+/// 完了した結果は録音時の出力先にかかわらずスタックに保存する（時間が経ってから
+/// フォーカス中のウィンドウへ入力しないため）。再試行可能なエラー（ネットワーク障害など）で
+/// 失敗した場合は、まだ接続できないとみなして残りのジョブを次回に回す。
+/// `id` を指定した場合はそのジョブだけを再実行する。
+/// `auto_interval` を指定した場合（自動再実行）は、一時的なエラーで失敗して
+/// 再実行の時期が来たジョブだけを対象とする（`QueuedJob::due_for_auto_retry`）。
+pub async fn retry_queued(
+    transcription_service: Rc<RefCell<TranscriptionService>>,
+    stack_service: Option<Rc<RefCell<StackService>>>,
+    ui_manager: Option<Rc<RefCell<UiProcessManager>>>,
+    id: Option<&str>,
+    auto_interval: Option<Duration>,
+) -> Result<QueueRetryReport> {
+    let Some(queue) = transcription_service.borrow().queue().cloned() else {
+        return Err(VoiceInputError::SystemError(
+            "Transcription queue is disabled".to_string(),
+        ));
+    };
+    let jobs = match id {
+        Some(id) => vec![
+            queue
+                .get(id)?
+                .ok_or_else(|| VoiceInputError::QueuedJobNotFound(id.to_string()))?,
+        ],
+        None => queue.list()?,
+    };
+    let jobs = match auto_interval {
+        Some(interval) => {
+            let now = now_secs();
+            jobs.into_iter()
+                .filter(|job| job.due_for_auto_retry(interval, now))
+                .collect()
+        }
+        None => jobs,
+    };
+
+    let mut report = QueueRetryReport::default();
+    for mut job in jobs {
+        let audio = AudioData(queue.audio(&job.id)?);
+        let transcribed = transcription_service
+            .borrow()
+            .transcribe(audio, job.options.clone())
+            .await;
+        match transcribed {
+            Ok(transcript) => {
+                match &stack_service {
+                    _ if transcript.text.trim().is_empty() => {
+                        println!("🔇 Queued transcription {}: no speech detected", job.id)
+                    }
+                    Some(stack) => {
+                        save_to_stack(stack, ui_manager.as_ref(), &transcript);
+                    }
+                    None => println!("✅ Queued transcription {}: {}", job.id, transcript.text),
+                }
+                queue.remove(&job.id)?;
+                report.completed += 1;
+            }
+            Err(e) => {
+                eprintln!("Queued transcription {} failed: {}", job.id, e);
+                queue.record_failure(&mut job, &e.to_string(), e.is_retryable())?;
+                report.failed += 1;
+                if e.is_retryable() {
+                    break;
+                }
+            }
+        }
+    }
+    report.remaining = queue.list()?.len();
+    Ok(report)
+}
+
 /// 転写ワーカーを起動
 pub async fn spawn_transcription_worker(
     semaphore: Arc<Semaphore>,
@@ -123,9 +324,7 @@ pub async fn spawn_transcription_worker(
 ) {
     use tokio::task::spawn_local;
 
-    while let Some((result, paste, resume_music, direct_input, stack_service, ui_manager)) =
-        rx.recv().await
-    {
+    while let Some(message) = rx.recv().await {
         let permit = match semaphore.clone().acquire_owned().await {
             Ok(p) => p,
             Err(e) => {
@@ -136,17 +335,317 @@ pub async fn spawn_transcription_worker(
 
         let transcription_service = transcription_service.clone();
         spawn_local(async move {
-            let _ = handle_transcription(
-                result,
-                paste,
-                resume_music,
-                direct_input,
-                stack_service,
-                ui_manager,
-                transcription_service,
-            )
-            .await;
+            if let Err(e) = handle_transcription(message, transcription_service).await {
+                eprintln!("Transcription failed: {}", e);
+            }
             drop(permit);
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::StackService;
+    use crate::application::Transcript;
+    use crate::application::service_container::test_helpers::MockTranscriptionClient;
+    use crate::domain::dict::{DictRepository, WordEntry};
+    use crate::error::VoiceInputError;
+    use crate::infrastructure::audio::cpal_backend::AudioData;
+    use crate::ipc::RecordingResult;
+    use tokio::sync::oneshot;
+
+    /// 空の辞書
+    struct EmptyDictRepo;
+
+    impl DictRepository for EmptyDictRepo {
+        fn load(&self) -> std::io::Result<Vec<WordEntry>> {
+            Ok(Vec::new())
+        }
+
+        fn save(&self, _entries: &[WordEntry]) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// ストリーミング結果を渡して転写し、スタックに保存されたテキストを返す
+    async fn transcribe_streamed(streamed: Result<Transcript>) -> String {
+        let service = Rc::new(RefCell::new(TranscriptionService::new(
+            Box::new(MockTranscriptionClient::new("バッチ転写")),
+            Box::new(EmptyDictRepo),
+            1,
+        )));
+        let stack = Rc::new(RefCell::new(StackService::new()));
+        stack.borrow_mut().enable_stack_mode();
+        let (tx, rx) = oneshot::channel();
+        tx.send(streamed).unwrap();
+
+        let result = RecordingResult {
+            audio_data: AudioData(vec![0u8; 100]).into(),
+            duration_ms: 1000,
+        };
+        let message = (
+            result,
+            Default::default(),
+            false,
+            false,
+            false,
+            Some(stack.clone()),
+            None,
+            Some(rx),
+        );
+        handle_transcription(message, service).await.unwrap();
+        let text = stack.borrow().get_stack(1).unwrap().text.clone();
+        text
+    }
+
+    #[tokio::test]
+    async fn test_uses_streamed_transcript() {
+        let text = transcribe_streamed(Ok(Transcript::new("ストリーミング転写"))).await;
+        assert_eq!(text, "ストリーミング転写");
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_batch_when_streaming_fails() {
+        let text = transcribe_streamed(Err(VoiceInputError::NetworkError(
+            "connection reset".to_string(),
+        )))
+        .await;
+        assert_eq!(text, "バッチ転写");
+    }
+
+    /// 無音を「ご視聴ありがとうございました」と転写するクライアント
+    struct SilenceClient;
+
+    #[async_trait::async_trait]
+    impl crate::application::traits::TranscriptionClient for SilenceClient {
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            _options: &crate::application::TranscriptionOptions,
+        ) -> Result<Transcript> {
+            let segment = crate::domain::transcript::TranscriptSegment {
+                avg_logprob: Some(-0.3),
+                no_speech_prob: Some(0.95),
+                ..crate::domain::transcript::TranscriptSegment::new(
+                    0.0,
+                    2.0,
+                    "ご視聴ありがとうございました",
+                )
+            };
+            Ok(Transcript {
+                segments: vec![segment],
+                ..Transcript::new("ご視聴ありがとうございました")
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_low_confidence_is_saved_to_stack_instead_of_typed() {
+        let service = Rc::new(RefCell::new(TranscriptionService::new(
+            Box::new(SilenceClient),
+            Box::new(EmptyDictRepo),
+            1,
+        )));
+        let stack = Rc::new(RefCell::new(StackService::new()));
+
+        let message = (
+            RecordingResult {
+                audio_data: AudioData(vec![0u8; 100]).into(),
+                duration_ms: 2000,
+            },
+            Default::default(),
+            true,
+            false,
+            true,
+            Some(stack.clone()),
+            None,
+            None,
+        );
+        handle_transcription(message, service).await.unwrap();
+
+        assert!(!stack.borrow().is_stack_mode_enabled());
+        assert_eq!(
+            stack.borrow().get_stack(1).unwrap().text,
+            "ご視聴ありがとうございました"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confident_transcript_is_not_saved_without_stack_mode() {
+        let service = Rc::new(RefCell::new(TranscriptionService::new(
+            Box::new(MockTranscriptionClient::new("通常の転写")),
+            Box::new(EmptyDictRepo),
+            1,
+        )));
+        let stack = Rc::new(RefCell::new(StackService::new()));
+
+        // スタックモードでなくてもスタックは渡される（直接入力しない設定で確認）
+        let message = (
+            RecordingResult {
+                audio_data: AudioData(vec![0u8; 100]).into(),
+                duration_ms: 2000,
+            },
+            Default::default(),
+            false,
+            false,
+            true,
+            Some(stack.clone()),
+            None,
+            None,
+        );
+        handle_transcription(message, service).await.unwrap();
+
+        assert!(stack.borrow().get_stack(1).is_none());
+    }
+
+    /// `offline` が立っている間はネットワークエラーを返す転写クライアント
+    struct FlakyClient {
+        offline: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::application::traits::TranscriptionClient for FlakyClient {
+        async fn transcribe(
+            &self,
+            _audio: AudioData,
+            _options: &crate::application::TranscriptionOptions,
+        ) -> Result<Transcript> {
+            if self.offline.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(VoiceInputError::NetworkError("offline".to_string()));
+            }
+            Ok(Transcript::new("あとで転写"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_transcription_is_queued_and_retried() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let queue = crate::infrastructure::queue::TranscriptionQueue::new(tmp.path());
+        let offline = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let service = Rc::new(RefCell::new(
+            TranscriptionService::new(
+                Box::new(FlakyClient {
+                    offline: offline.clone(),
+                }),
+                Box::new(EmptyDictRepo),
+                1,
+            )
+            .with_queue(queue.clone()),
+        ));
+
+        let message = (
+            RecordingResult {
+                audio_data: AudioData(vec![7u8; 100]).into(),
+                duration_ms: 1200,
+            },
+            crate::application::TranscriptionOptions {
+                language: Some("en".to_string()),
+                ..Default::default()
+            },
+            true,
+            false,
+            true,
+            None,
+            None,
+            None,
+        );
+        assert!(
+            handle_transcription(message, service.clone())
+                .await
+                .is_err()
+        );
+
+        let jobs = queue.list().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].output, OutputMode::Input);
+        assert_eq!(jobs[0].duration_ms, 1200);
+        assert_eq!(jobs[0].options.language.as_deref(), Some("en"));
+        assert_eq!(queue.audio(&jobs[0].id).unwrap(), vec![7u8; 100]);
+
+        // まだ接続できなければ失敗として記録して残す
+        let stack = Rc::new(RefCell::new(StackService::new()));
+        let report = retry_queued(service.clone(), Some(stack.clone()), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            (report.completed, report.failed, report.remaining),
+            (0, 1, 1)
+        );
+        assert_eq!(queue.list().unwrap()[0].attempts, 2);
+
+        // 接続が戻ればスタックモードでなくてもスタックに保存する
+        offline.store(false, std::sync::atomic::Ordering::SeqCst);
+        let report = retry_queued(service.clone(), Some(stack.clone()), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            (report.completed, report.failed, report.remaining),
+            (1, 0, 0)
+        );
+        assert_eq!(stack.borrow().get_stack(1).unwrap().text, "あとで転写");
+
+        let err = retry_queued(service, Some(stack), None, Some("missing"), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VoiceInputError::QueuedJobNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_auto_retry_skips_jobs_that_failed_permanently() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let queue = crate::infrastructure::queue::TranscriptionQueue::new(tmp.path());
+        let service = Rc::new(RefCell::new(
+            TranscriptionService::new(
+                Box::new(FlakyClient {
+                    offline: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+                }),
+                Box::new(EmptyDictRepo),
+                1,
+            )
+            .with_queue(queue.clone()),
+        ));
+        let denied = queue
+            .enqueue(
+                b"wav",
+                1000,
+                Default::default(),
+                OutputMode::Input,
+                "HTTP 401",
+                false,
+            )
+            .unwrap();
+        queue
+            .enqueue(
+                b"wav",
+                1000,
+                Default::default(),
+                OutputMode::Input,
+                "offline",
+                true,
+            )
+            .unwrap();
+
+        // 失敗した直後はまだ自動では再実行しない
+        let stack = Rc::new(RefCell::new(StackService::new()));
+        let later = Some(Duration::from_secs(3600));
+        let report = retry_queued(service.clone(), Some(stack.clone()), None, None, later)
+            .await
+            .unwrap();
+        assert_eq!((report.completed, report.remaining), (0, 2));
+
+        // 一時的なエラーのジョブだけを自動で再実行する
+        let now = Some(Duration::ZERO);
+        let report = retry_queued(service.clone(), Some(stack.clone()), None, None, now)
+            .await
+            .unwrap();
+        assert_eq!((report.completed, report.remaining), (1, 1));
+        assert_eq!(queue.list().unwrap()[0].id, denied.id);
+
+        // 手動の再実行ではすべてのジョブを対象とする
+        let report = retry_queued(service, Some(stack), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!((report.completed, report.remaining), (1, 0));
+    }
+}
@@ -24,7 +24,7 @@ use tokio_util::codec::{FramedRead, FramedWrite, LinesCodec};
 use voice_input::{
     application::{ServiceContainer, spawn_transcription_worker},
     error::{Result, VoiceInputError},
-    infrastructure::audio::CpalAudioBackend,
+    infrastructure::{audio::CpalAudioBackend, queue::QueueConfig},
     ipc::{IpcCmd, IpcResp, socket_path},
     load_env,
     utils::config::EnvConfig,
@@ -88,24 +88,36 @@ async fn async_main() -> Result<()> {
         }
     });
 
-    // 転写ワーカーの起動
+    // 転写ワーカーの起動（プロファイル・キャッシュ・キューの設定を共有するためコンテナの転写サービスを使う）
     let semaphore = std::sync::Arc::new(Semaphore::new(2));
-    let transcription_service = {
-        // TranscriptionServiceを取得（CommandHandlerから）
-        // 注: 実際のアプリケーションではServiceContainerから直接取得する方が良い
-        use voice_input::application::TranscriptionService;
-        use voice_input::infrastructure::external::openai_adapter::OpenAiTranscriptionAdapter;
-        std::rc::Rc::new(std::cell::RefCell::new(
-            TranscriptionService::with_default_repo(Box::new(OpenAiTranscriptionAdapter::new()?)),
-        ))
-    };
-
     spawn_local(spawn_transcription_worker(
         semaphore.clone(),
         transcription_rx,
-        transcription_service,
+        container.transcription_service.clone(),
     ));
 
+    // 転写の再試行状況を UI に表示
+    if let Some(notifier) = container.take_retry_notifier() {
+        spawn_local(notifier);
+    }
+
+    // 転写に失敗した録音のキューを定期的に再実行（接続が戻れば処理される。
+    // 一時的でないエラーのジョブや失敗を重ねたジョブは `voice_input queue retry` に任せる）
+    let queue_config = QueueConfig::from_env();
+    if let Some(interval) = queue_config.retry_interval.filter(|_| queue_config.enabled) {
+        let command_handler_for_queue = command_handler.clone();
+        spawn_local(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let _ = command_handler_for_queue
+                    .borrow()
+                    .auto_retry_queue(interval)
+                    .await;
+            }
+        });
+    }
+
     // クライアント接続ループ
     loop {
         let (stream, _) = listener.accept().await?;
@@ -30,6 +30,15 @@ pub enum Cmd {
             help = "Only copy to clipboard without pasting (conflicts with --copy-and-paste)"
         )]
         copy_only: bool,
+        /// 転写言語（例: ja, en。auto で自動判定。省略時は VOICE_INPUT_LANGUAGE）
+        #[arg(long)]
+        language: Option<String>,
+        /// 翻訳先の言語（例: en。off で翻訳しない。省略時はプロファイル・VOICE_INPUT_TRANSLATE）
+        #[arg(long)]
+        translate: Option<String>,
+        /// 適用するプロファイル（省略時は VOICE_INPUT_PROFILE）
+        #[arg(long)]
+        profile: Option<String>,
     },
     /// 録音停止
     Stop,
@@ -49,6 +58,15 @@ pub enum Cmd {
             help = "Only copy to clipboard without pasting (conflicts with --copy-and-paste)"
         )]
         copy_only: bool,
+        /// 転写言語（例: ja, en。auto で自動判定。省略時は VOICE_INPUT_LANGUAGE）
+        #[arg(long)]
+        language: Option<String>,
+        /// 翻訳先の言語（例: en。off で翻訳しない。省略時はプロファイル・VOICE_INPUT_TRANSLATE）
+        #[arg(long)]
+        translate: Option<String>,
+        /// 適用するプロファイル（省略時は VOICE_INPUT_PROFILE）
+        #[arg(long)]
+        profile: Option<String>,
     },
     /// デーモン状態取得
     Status,
@@ -59,6 +77,27 @@ pub enum Cmd {
         #[command(subcommand)]
         action: DictCmd,
     },
+    /// 転写結果キャッシュ操作
+    Cache {
+        #[command(subcommand)]
+        action: CacheCmd,
+    },
+    /// 転写に失敗した録音のキュー操作
+    Queue {
+        #[command(subcommand)]
+        action: QueueCmd,
+    },
+    /// 転写の利用状況と推定料金を表示
+    Usage {
+        /// 日別に集計する日数
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+    },
+    /// 転写後のテキスト処理パイプラインの操作
+    Pipeline {
+        #[command(subcommand)]
+        action: PipelineCmd,
+    },
     /// 各種設定操作
     Config {
         #[command(subcommand)]
@@ -81,6 +120,100 @@ pub enum Cmd {
     /// 全スタックをクリア
     #[command(name = "clear-stacks")]
     ClearStacks,
+    /// push-to-talk 制御（フットペダル等の外部入力用）
+    Ptt {
+        #[command(subcommand)]
+        action: PttCmd,
+    },
+    /// 連続音声入力（発話ごとに自動で区切って転写）
+    Continuous {
+        #[command(subcommand)]
+        action: ContinuousCmd,
+    },
+    /// 入力デバイス一覧
+    Devices {
+        /// 対応サンプルレート・フォーマット・チャンネル数と選択デバイスを表示
+        #[arg(long, short)]
+        verbose: bool,
+    },
+    /// 音声ファイルを文字起こし（MP3 / M4A / Ogg Vorbis / FLAC / WAV。Opus は `opus` フィーチャー有効時のみ）
+    Transcribe {
+        /// 音声ファイルのパス
+        file: std::path::PathBuf,
+        /// 転写言語（例: ja, en。auto で自動判定。省略時は VOICE_INPUT_LANGUAGE）
+        #[arg(long)]
+        language: Option<String>,
+        /// 翻訳先の言語（例: en。off で翻訳しない。省略時はプロファイル・VOICE_INPUT_TRANSLATE）
+        #[arg(long)]
+        translate: Option<String>,
+        /// 適用するプロファイル（省略時は VOICE_INPUT_PROFILE）
+        #[arg(long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PttCmd {
+    /// 押下: 録音開始
+    Start {
+        #[arg(long)]
+        prompt: Option<String>,
+        /// クリップボード経由でペースト（デフォルトの直接入力を無効化）
+        #[arg(
+            long,
+            help = "Use clipboard copy-and-paste method instead of direct input"
+        )]
+        copy_and_paste: bool,
+        /// クリップボードにコピーのみ（ペーストしない）
+        #[arg(
+            long,
+            help = "Only copy to clipboard without pasting (conflicts with --copy-and-paste)"
+        )]
+        copy_only: bool,
+        /// 転写言語（例: ja, en。auto で自動判定。省略時は VOICE_INPUT_LANGUAGE）
+        #[arg(long)]
+        language: Option<String>,
+        /// 翻訳先の言語（例: en。off で翻訳しない。省略時はプロファイル・VOICE_INPUT_TRANSLATE）
+        #[arg(long)]
+        translate: Option<String>,
+        /// 適用するプロファイル（省略時は VOICE_INPUT_PROFILE）
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// 解放: 録音停止（最小押下時間未満なら破棄）
+    Stop,
+}
+
+#[derive(Subcommand)]
+pub enum ContinuousCmd {
+    /// マイクを開いたまま発話の区切りごとに転写
+    Start {
+        #[arg(long)]
+        prompt: Option<String>,
+        /// クリップボード経由でペースト（デフォルトの直接入力を無効化）
+        #[arg(
+            long,
+            help = "Use clipboard copy-and-paste method instead of direct input"
+        )]
+        copy_and_paste: bool,
+        /// クリップボードにコピーのみ（ペーストしない）
+        #[arg(
+            long,
+            help = "Only copy to clipboard without pasting (conflicts with --copy-and-paste)"
+        )]
+        copy_only: bool,
+        /// 転写言語（例: ja, en。auto で自動判定。省略時は VOICE_INPUT_LANGUAGE）
+        #[arg(long)]
+        language: Option<String>,
+        /// 翻訳先の言語（例: en。off で翻訳しない。省略時はプロファイル・VOICE_INPUT_TRANSLATE）
+        #[arg(long)]
+        translate: Option<String>,
+        /// 適用するプロファイル（省略時は VOICE_INPUT_PROFILE）
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// 連続音声入力を終了（話し途中の発話も転写）
+    Stop,
 }
 
 #[derive(Subcommand)]
@@ -104,6 +237,49 @@ pub enum DictCmd {
     List,
 }
 
+#[derive(Subcommand)]
+pub enum CacheCmd {
+    /// 件数・サイズ・保存先を表示
+    Stats,
+    /// すべて削除
+    Clear,
+}
+
+#[derive(Subcommand)]
+pub enum QueueCmd {
+    /// 保存されている録音を一覧表示
+    List,
+    /// 再実行して結果をスタックに保存（ID 省略時はすべて）
+    Retry { id: Option<String> },
+    /// 削除
+    Drop {
+        /// 削除するジョブ ID
+        #[arg(required_unless_present = "all")]
+        id: Option<String>,
+        /// すべて削除
+        #[arg(long, conflicts_with = "id")]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PipelineCmd {
+    /// テキストをパイプラインに通し、段ごとの結果と処理時間を表示
+    Test {
+        /// 処理するテキスト
+        text: String,
+        /// 転写言語（例: ja, en。省略時は VOICE_INPUT_LANGUAGE）
+        #[arg(long)]
+        language: Option<String>,
+        /// 翻訳先の言語（例: en。off で翻訳しない。省略時はプロファイル・VOICE_INPUT_TRANSLATE）
+        #[arg(long)]
+        translate: Option<String>,
+        /// 適用するプロファイル（省略時は VOICE_INPUT_PROFILE）
+        #[arg(long)]
+        profile: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ConfigCmd {
     /// `dict-path` 設定
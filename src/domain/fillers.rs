@@ -0,0 +1,489 @@
+use serde::{Deserialize, Serialize};
+
+/// フィラー（言いよどみ）の除去の設定（パイプラインの `fillers` 段）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FillerConfig {
+    /// 除去の強さ
+    pub level: FillerLevel,
+    /// 除去しない言葉（フィラー・繰り返しのどちらにも適用）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+}
+
+/// 除去の強さ
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FillerLevel {
+    /// 「えーと」「um」などの言いよどみだけ
+    Light,
+    /// 言いよどみに加え、「あの、」「ah」と単語の繰り返し・言い直し
+    #[default]
+    Normal,
+    /// さらに「まあ、」「なんか、」「you know,」などの口癖
+    Aggressive,
+}
+
+/// フィラーの定義
+struct Filler {
+    language: &'static str,
+    /// 言葉（日本語の `ー` は 1 文字以上の長音に一致し、英語は同じ文字をさらに繰り返した形にも一致する）
+    phrase: &'static str,
+    level: FillerLevel,
+    /// 実際の語として使われうる言葉で、区切られている場合だけ除去する
+    /// （日本語は直後が読点・空白・文末、英語は文頭か読点の後で、直後が読点）
+    delimited: bool,
+}
+
+const fn filler(
+    language: &'static str,
+    phrase: &'static str,
+    level: FillerLevel,
+    delimited: bool,
+) -> Filler {
+    Filler {
+        language,
+        phrase,
+        level,
+        delimited,
+    }
+}
+
+/// 組み込みのフィラー（長い言葉から順に並べる）
+const FILLERS: &[Filler] = &[
+    filler("ja", "ええっと", FillerLevel::Light, false),
+    filler("ja", "えーっと", FillerLevel::Light, false),
+    filler("ja", "えーと", FillerLevel::Light, false),
+    filler("ja", "ええと", FillerLevel::Light, false),
+    filler("ja", "えっと", FillerLevel::Light, false),
+    filler("ja", "あのー", FillerLevel::Light, false),
+    filler("ja", "そのー", FillerLevel::Light, false),
+    filler("ja", "うーん", FillerLevel::Light, false),
+    filler("ja", "えー", FillerLevel::Light, false),
+    filler("ja", "あー", FillerLevel::Light, false),
+    filler("ja", "んー", FillerLevel::Light, false),
+    filler("ja", "なんか", FillerLevel::Aggressive, true),
+    filler("ja", "あの", FillerLevel::Normal, true),
+    filler("ja", "その", FillerLevel::Aggressive, true),
+    filler("ja", "まあ", FillerLevel::Aggressive, true),
+    filler("en", "you know", FillerLevel::Aggressive, true),
+    filler("en", "i mean", FillerLevel::Aggressive, true),
+    filler("en", "like", FillerLevel::Aggressive, true),
+    filler("en", "uhm", FillerLevel::Light, false),
+    filler("en", "erm", FillerLevel::Light, false),
+    filler("en", "um", FillerLevel::Light, false),
+    filler("en", "uh", FillerLevel::Light, false),
+    // "er"・"mm" は "ER"・"5 mm" などの語と区別できるよう、文字を繰り返した形だけ
+    filler("en", "errr", FillerLevel::Light, false),
+    filler("en", "hm", FillerLevel::Light, false),
+    filler("en", "mmm", FillerLevel::Light, false),
+    filler("en", "ah", FillerLevel::Normal, false),
+];
+
+/// 英語で繰り返しても誤りではない語（"that that"・"had had"・"bye bye"・"ha ha" など）
+const LEGITIMATE_REPEATS: &[&str] = &["that", "had", "is", "bye", "ha", "no"];
+
+/// フィラーの後に続く区切りの読点
+fn is_comma(c: char) -> bool {
+    matches!(c, '、' | '，' | ',')
+}
+
+/// 文末の記号
+fn is_terminal(c: char) -> bool {
+    matches!(c, '。' | '．' | '.' | '!' | '?' | '！' | '？')
+}
+
+/// 日本語の長音
+fn is_long_mark(c: char) -> bool {
+    matches!(c, 'ー' | '〜' | '～')
+}
+
+/// ひらがな・カタカナ
+fn is_kana(c: char) -> bool {
+    matches!(c, '\u{3041}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}')
+}
+
+/// 言い直しとみなす日本語の言いかけの最大の長さ（「こ、これは」「わた、私は」など）
+const MAX_RESTART_CHARS: usize = 2;
+
+/// ひらがな・カタカナ・漢字
+fn is_japanese(c: char) -> bool {
+    matches!(c,
+        '\u{3041}'..='\u{309F}'
+        | '\u{30A0}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '々')
+}
+
+/// 英単語の文字
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '\''
+}
+
+/// 連続する同じ文字を 1 文字にまとめる（"ummm" → "um"）
+fn squeeze(word: &str) -> String {
+    let mut out = String::new();
+    for c in word.chars() {
+        if !out.ends_with(c) {
+            out.push(c);
+        }
+    }
+    out
+}
+
+impl FillerConfig {
+    /// フィラーと単語の繰り返しを除去（`language` が `None` ならすべての言語の規則を使う）
+    pub fn apply(&self, text: &str, language: Option<&str>) -> String {
+        let fillers: Vec<&Filler> = FILLERS
+            .iter()
+            .filter(|f| f.level <= self.level)
+            .filter(|f| language.is_none_or(|l| l.eq_ignore_ascii_case(f.language)))
+            .collect();
+        let text = self.remove_fillers(text, &fillers);
+        if self.level >= FillerLevel::Normal {
+            self.remove_repeats(&text)
+        } else {
+            text
+        }
+    }
+
+    fn allowed(&self, word: &str) -> bool {
+        self.allow
+            .iter()
+            .any(|a| a.trim().to_lowercase() == word.trim().to_lowercase())
+    }
+
+    fn remove_fillers(&self, text: &str, fillers: &[&Filler]) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut out = String::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            // 語の途中からは一致させない（「今日はえー」の「えー」や "umbrella" の "um" など）
+            let at_boundary = i == 0 || {
+                let prev = chars[i - 1];
+                prev.is_whitespace() || is_comma(prev) || is_terminal(prev)
+            };
+            // 文頭・読点の後か（英語の区切られたフィラー用）
+            let after_pause = out
+                .trim_end()
+                .chars()
+                .last()
+                .is_none_or(|c| is_comma(c) || is_terminal(c));
+            let found = at_boundary
+                .then(|| {
+                    fillers.iter().find_map(|f| {
+                        let end = match_filler(&chars, i, f, after_pause)?;
+                        let matched: String = chars[i..end].iter().collect();
+                        (!self.allowed(&matched)).then_some(end)
+                    })
+                })
+                .flatten();
+            let Some(end) = found else {
+                out.push(chars[i]);
+                i += 1;
+                continue;
+            };
+
+            // フィラーの後の読点・空白もまとめて除く
+            i = end;
+            while chars.get(i).is_some_and(|&c| is_long_mark(c)) {
+                i += 1;
+            }
+            while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+                i += 1;
+            }
+            if chars.get(i).is_some_and(|&c| is_comma(c)) {
+                i += 1;
+            }
+            while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+                i += 1;
+            }
+            // 文末のフィラーなら直前の読点・空白も除く（「晴れです、えー。」→「晴れです。」）
+            if chars.get(i).is_none_or(|&c| is_terminal(c)) {
+                let len = out
+                    .trim_end_matches(|c: char| c.is_whitespace() || is_comma(c))
+                    .len();
+                out.truncate(len);
+            }
+        }
+        out.trim().to_string()
+    }
+
+    /// 単語の繰り返し（"the the"）と日本語の言い直し（「私は、私は」「こ、これは」）を除く
+    fn remove_repeats(&self, text: &str) -> String {
+        // 区切り（空白・読点・文末の記号）とそれに続く語の組に分ける
+        let mut parts: Vec<(String, String)> = Vec::new();
+        let mut sep = String::new();
+        let mut word = String::new();
+        for c in text.chars() {
+            if c.is_whitespace() || is_comma(c) || is_terminal(c) {
+                if !word.is_empty() {
+                    parts.push((std::mem::take(&mut sep), std::mem::take(&mut word)));
+                }
+                sep.push(c);
+            } else {
+                word.push(c);
+            }
+        }
+        if !word.is_empty() || !sep.is_empty() {
+            parts.push((sep, word));
+        }
+
+        // 文をまたぐ繰り返し・言い直しは除かない
+        let pairs = |check: &dyn Fn(&str, &str) -> bool| -> Vec<bool> {
+            parts
+                .windows(2)
+                .map(|pair| !pair[1].0.contains(is_terminal) && check(&pair[0].1, &pair[1].1))
+                .collect()
+        };
+        let repeated = pairs(&|word, next| self.repeats(word, next));
+        let restarted = pairs(&|word, next| self.restarts(word, next));
+
+        let mut out = String::with_capacity(text.len());
+        for (index, (sep, word)) in parts.iter().enumerate() {
+            // 繰り返しは後の語を前の区切りとともに除き、文頭の大文字などを残す（"The the" → "The"）
+            let repeat = index > 0 && repeated[index - 1];
+            // 言いかけは前の語を後の区切りとともに除く（「こ、これは」→「これは」）
+            let restart = restarted.get(index).copied().unwrap_or(false);
+            let after_restart = index > 0 && restarted[index - 1];
+            if !(repeat || after_restart) {
+                out.push_str(sep);
+            }
+            if !(repeat || restart) {
+                out.push_str(word);
+            }
+        }
+        out
+    }
+
+    /// `next` が直前の `word` と同じ語の繰り返しか
+    fn repeats(&self, word: &str, next: &str) -> bool {
+        if word.is_empty() || self.allowed(word) {
+            return false;
+        }
+        if word.chars().all(is_japanese) {
+            return word == next;
+        }
+        word.chars().any(|c| c.is_ascii_alphabetic())
+            && word.eq_ignore_ascii_case(next)
+            && !LEGITIMATE_REPEATS
+                .iter()
+                .any(|w| w.eq_ignore_ascii_case(word))
+    }
+
+    /// `word` が直後の `next` の言いかけか
+    ///
+    /// 短い仮名の言いかけだけとする（「テスト、テストコード」は列挙として残す）。
+    fn restarts(&self, word: &str, next: &str) -> bool {
+        !self.allowed(word)
+            && word != next
+            && word.chars().count() <= MAX_RESTART_CHARS
+            && word.chars().all(is_kana)
+            && next.starts_with(word)
+    }
+}
+
+/// `at` の位置からフィラーが続けば、その終わりの位置を返す
+///
+/// `after_pause` は `at` が文頭・読点の後か。
+fn match_filler(chars: &[char], at: usize, filler: &Filler, after_pause: bool) -> Option<usize> {
+    if filler.language == "ja" {
+        let end = match_japanese(chars, at, filler.phrase)?;
+        let mut next = end;
+        while chars.get(next).is_some_and(|&c| is_long_mark(c)) {
+            next += 1;
+        }
+        let next = chars.get(next).copied();
+        let delimited = next.is_none_or(|c| c.is_whitespace() || is_comma(c) || is_terminal(c));
+        // 仮名が直接続く場合は語の一部とみなす（「あーいう」「あのー、」は区別する）
+        if !delimited && (filler.delimited || next.is_some_and(is_kana)) {
+            return None;
+        }
+        Some(end)
+    } else {
+        let end = match_english(chars, at, filler.phrase)?;
+        // 英語は語の間に必ず空白が入り、文末の語（"Do you know?"）とも区別できないため、
+        // 文頭か読点の後で、直後が読点の場合だけとする
+        if filler.delimited && !(after_pause && chars.get(end).is_some_and(|&c| is_comma(c))) {
+            return None;
+        }
+        Some(end)
+    }
+}
+
+/// 日本語のフィラー（`ー` は 1 文字以上の長音に一致）
+fn match_japanese(chars: &[char], at: usize, phrase: &str) -> Option<usize> {
+    let mut i = at;
+    for p in phrase.chars() {
+        if p == 'ー' {
+            if !chars.get(i).is_some_and(|&c| is_long_mark(c)) {
+                return None;
+            }
+            while chars.get(i).is_some_and(|&c| is_long_mark(c)) {
+                i += 1;
+            }
+        } else {
+            if chars.get(i) != Some(&p) {
+                return None;
+            }
+            i += 1;
+        }
+    }
+    Some(i)
+}
+
+/// 英語のフィラー（単語単位で、大文字小文字と文字の繰り返しを無視して比較）
+fn match_english(chars: &[char], at: usize, phrase: &str) -> Option<usize> {
+    let mut i = at;
+    for (n, expected) in phrase.split_whitespace().enumerate() {
+        if n > 0 {
+            let start = i;
+            while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+                i += 1;
+            }
+            if i == start {
+                return None;
+            }
+        }
+        let start = i;
+        while chars.get(i).is_some_and(|&c| is_word_char(c)) {
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().collect::<String>().to_lowercase();
+        // 繰り返しをまとめて比べ、`expected` より短い形（"mmm" に対する "mm"）には一致させない
+        let matches = squeeze(&word) == squeeze(expected) && word.len() >= expected.len();
+        if word.is_empty() || !matches {
+            return None;
+        }
+    }
+    Some(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(level: FillerLevel) -> FillerConfig {
+        FillerConfig {
+            level,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_removes_japanese_fillers() {
+        let fillers = FillerConfig::default();
+        let apply = |text| fillers.apply(text, Some("ja"));
+
+        assert_eq!(apply("えーと、今日はですね"), "今日はですね");
+        assert_eq!(apply("今日は、えーー、晴れです"), "今日は、晴れです");
+        assert_eq!(apply("晴れです、あのー。"), "晴れです。");
+        assert_eq!(apply("あの、資料を見ました"), "資料を見ました");
+        // 語として使われている場合・語の途中は除かない
+        assert_eq!(apply("あの人はええ人です"), "あの人はええ人です");
+        assert_eq!(apply("今日はえー晴れ"), "今日はえー晴れ");
+        assert_eq!(apply("あーいうのは嫌だ"), "あーいうのは嫌だ");
+        assert_eq!(apply("あー いうのは嫌だ"), "いうのは嫌だ");
+    }
+
+    #[test]
+    fn test_removes_english_fillers_at_word_boundaries() {
+        let fillers = FillerConfig::default();
+        let apply = |text| fillers.apply(text, Some("en"));
+
+        assert_eq!(
+            apply("So, um, I think uhh we should, uh."),
+            "So, I think we should."
+        );
+        assert_eq!(apply("Ummm the umbrella is here"), "the umbrella is here");
+        assert_eq!(apply("Hmm, size M is fine"), "size M is fine");
+        assert_eq!(apply("Mmm, errr, okay"), "okay");
+        // 単位や略語と同じ綴りは除かない
+        assert_eq!(apply("a 5 mm screw"), "a 5 mm screw");
+        assert_eq!(apply("go to the ER."), "go to the ER.");
+        assert_eq!(apply("to err is human"), "to err is human");
+        // 他の言語の規則は使わない
+        assert_eq!(apply("えーと hello"), "えーと hello");
+    }
+
+    #[test]
+    fn test_levels() {
+        let text = "まあ、あの、ah I mean, it works like a charm";
+        assert_eq!(
+            config(FillerLevel::Light).apply(text, None),
+            "まあ、あの、ah I mean, it works like a charm"
+        );
+        assert_eq!(
+            config(FillerLevel::Normal).apply(text, None),
+            "まあ、I mean, it works like a charm"
+        );
+        assert_eq!(
+            config(FillerLevel::Aggressive).apply(text, None),
+            "it works like a charm"
+        );
+    }
+
+    #[test]
+    fn test_keeps_english_words_used_in_sentences() {
+        let fillers = config(FillerLevel::Aggressive);
+        let apply = |text| fillers.apply(text, Some("en"));
+
+        assert_eq!(apply("Do you know?"), "Do you know?");
+        assert_eq!(apply("That is what I mean."), "That is what I mean.");
+        assert_eq!(apply("Things I like."), "Things I like.");
+        assert_eq!(apply("If you like, we can go."), "If you like, we can go.");
+        // 文頭・読点の後で、読点で区切られている場合は除く
+        assert_eq!(
+            apply("Like, it is fine. So, you know, it works"),
+            "it is fine. So, it works"
+        );
+    }
+
+    #[test]
+    fn test_removes_repeats() {
+        let fillers = FillerConfig::default();
+        assert_eq!(
+            fillers.apply("I I think the the plan works", Some("en")),
+            "I think the plan works"
+        );
+        assert_eq!(
+            fillers.apply("I know that that is true", Some("en")),
+            "I know that that is true"
+        );
+        // 後の語を除き、文頭の大文字を残す
+        assert_eq!(
+            fillers.apply("The the plan works.", Some("en")),
+            "The plan works."
+        );
+        // 重ねて使う語はそのまま
+        assert_eq!(fillers.apply("Bye bye.", Some("en")), "Bye bye.");
+        assert_eq!(fillers.apply("Ha ha ha", Some("en")), "Ha ha ha");
+        assert_eq!(
+            fillers.apply("私は、私は、そう思います。こ、これは", Some("ja")),
+            "私は、そう思います。これは"
+        );
+        // 前の語で始まる語の列挙は言い直しとみなさない
+        assert_eq!(
+            fillers.apply("テスト、テストコード、ドキュメントを更新", Some("ja")),
+            "テスト、テストコード、ドキュメントを更新"
+        );
+        // 軽い設定では繰り返しを除かない
+        assert_eq!(
+            config(FillerLevel::Light).apply("the the plan", Some("en")),
+            "the the plan"
+        );
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let fillers = FillerConfig {
+            allow: vec!["あの".to_string(), "ah".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            fillers.apply("あの、ah, えー、それ", None),
+            "あの、ah, それ"
+        );
+    }
+}
@@ -1,6 +1,24 @@
 // src/domain/mod.rs
 pub mod dict;
+pub mod fillers;
+pub mod normalize;
+pub mod phantom;
+pub mod pipeline;
+pub mod profile;
 pub mod recorder;
+pub mod routing;
+pub mod spoken;
 pub mod stack;
+pub mod transcript;
+pub mod usage;
 
+pub use fillers::{FillerConfig, FillerLevel};
+pub use normalize::NormalizeConfig;
+pub use phantom::PhantomPhraseFilter;
+pub use pipeline::{PipelineReport, StageKind, StageReport, StageSpec};
+pub use profile::{DictionaryStage, Profile};
+pub use routing::{RouteRequest, RouteRule, RoutingPolicy};
+pub use spoken::{SpokenCommandConfig, SpokenCommands};
 pub use stack::{Stack, StackInfo};
+pub use transcript::{Transcript, TranscriptSegment, TranscriptWord};
+pub use usage::{PriceTable, UsageBudget, UsageRecord, UsageReport, UsageRow};
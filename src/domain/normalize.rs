@@ -0,0 +1,475 @@
+use serde::{Deserialize, Serialize};
+
+/// 日本語テキストの表記の正規化（パイプラインの `normalize` 段）
+///
+/// 未指定の項目は転写結果の表記のまま残す。英数字の幅 → 漢数字 → 句読点 → 空白の順に適用する。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizeConfig {
+    /// 英数字の幅
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<Width>,
+    /// 句読点の種類
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub punctuation: Option<PunctuationStyle>,
+    /// 和文と英数字の間に半角空白を入れる（`true`）・取り除く（`false`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<bool>,
+    /// 数字の表記
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numerals: Option<NumeralStyle>,
+}
+
+/// 英数字の幅
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Width {
+    /// 半角（`ABC123`）
+    Half,
+    /// 全角（`ＡＢＣ１２３`）
+    Full,
+}
+
+/// 句読点の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PunctuationStyle {
+    /// `、` と `。`
+    Standard,
+    /// `，` と `．`
+    Academic,
+}
+
+/// 数字の表記
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumeralStyle {
+    /// 算用数字（`百二十三` → `123`）
+    Arabic,
+    /// 漢数字（`123` → `百二十三`）
+    Kanji,
+}
+
+impl NormalizeConfig {
+    /// 設定された正規化を適用
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if let Some(width) = self.width {
+            text = convert_width(&text, width);
+        }
+        match self.numerals {
+            Some(NumeralStyle::Arabic) => text = kanji_to_arabic(&text),
+            Some(NumeralStyle::Kanji) => text = arabic_to_kanji(&text),
+            None => {}
+        }
+        if let Some(style) = self.punctuation {
+            text = convert_punctuation(&text, style);
+        }
+        if let Some(spacing) = self.spacing {
+            text = convert_spacing(&text, spacing);
+        }
+        text
+    }
+}
+
+/// ひらがな・カタカナ・漢字
+fn is_japanese(c: char) -> bool {
+    matches!(c,
+        '\u{3041}'..='\u{309F}'
+        | '\u{30A0}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '々' | '〇')
+}
+
+/// 英数字（全角を含む）
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ')
+}
+
+/// 漢字（漢数字を含む）
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '々' | '〇')
+}
+
+fn convert_width(text: &str, width: Width) -> String {
+    text.chars()
+        .map(|c| match width {
+            Width::Half if matches!(c, '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ') => {
+                char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)
+            }
+            Width::Full if c.is_ascii_alphanumeric() => {
+                char::from_u32(c as u32 + 0xFEE0).unwrap_or(c)
+            }
+            _ => c,
+        })
+        .collect()
+}
+
+fn convert_punctuation(text: &str, style: PunctuationStyle) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let between_digits = prev.is_some_and(|p| p.is_numeric())
+            && chars.get(i + 1).is_some_and(|n| n.is_numeric());
+        // 和文の直後の半角の句読点も和文の句読点として扱う（「設定ファイル.json」のように英字が続く場合を除く）
+        let after_japanese = prev.is_some_and(is_japanese)
+            && chars
+                .get(i + 1)
+                .is_none_or(|&n| n.is_whitespace() || is_japanese(n));
+        let (comma, period) = match style {
+            PunctuationStyle::Standard => ('、', '。'),
+            PunctuationStyle::Academic => ('，', '．'),
+        };
+        let converted = match c {
+            _ if between_digits => None,
+            '，' | '、' => Some(comma),
+            '．' | '。' => Some(period),
+            ',' if after_japanese => Some(comma),
+            '.' if after_japanese => Some(period),
+            _ => None,
+        };
+        match converted {
+            Some(mark) => {
+                out.push(mark);
+                i += 1;
+                // 和文の句読点の後の空白は不要
+                while chars.get(i).is_some_and(|&c| c == ' ') {
+                    i += 1;
+                }
+            }
+            None => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn convert_spacing(text: &str, spacing: bool) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if spacing {
+            out.push(c);
+            if let Some(&next) = chars.get(i + 1) {
+                if (is_japanese(c) && is_latin(next)) || (is_latin(c) && is_japanese(next)) {
+                    out.push(' ');
+                }
+            }
+            i += 1;
+        } else if matches!(c, ' ' | '\u{3000}') {
+            let end = (i..chars.len())
+                .find(|&j| !matches!(chars[j], ' ' | '\u{3000}'))
+                .unwrap_or(chars.len());
+            let prev = out.chars().last();
+            let next = chars.get(end).copied();
+            let joins = match (prev, next) {
+                (Some(p), Some(n)) => {
+                    (is_japanese(p) && is_latin(n)) || (is_latin(p) && is_japanese(n))
+                }
+                _ => false,
+            };
+            if !joins {
+                out.extend(&chars[i..end]);
+            }
+            i = end;
+        } else {
+            out.push(c);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// 漢数字の数字
+const KANJI_DIGITS: [char; 10] = ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// 1 文字の漢数字を算用数字に変換してよい助数詞
+const COUNTERS: &[char] = &[
+    'つ', '人', '個', '日', '月', '年', '時', '回', '件', '本', '枚', '台', '歳', '円', '週', '秒',
+    '冊', '行', '点', '割', '分', '%', '％',
+];
+
+/// 助数詞の後に続いても数量の表現とみなす漢字（「三日後」「一時間」「五年目」）
+const NUMERIC_SUFFIXES: &[char] = &['後', '前', '目', '間', '半', '以', '毎'];
+
+/// 「一時」の後に続くと副詞（「一時は危なかった」「一時も離れない」）とみなす助詞
+const ADVERBIAL_PARTICLES: &[char] = &['は', 'も'];
+
+fn kanji_digit(c: char) -> Option<u64> {
+    KANJI_DIGITS.iter().position(|&d| d == c).map(|d| d as u64)
+}
+
+/// 位取りの単位（十・百・千）
+fn small_unit(c: char) -> Option<u64> {
+    match c {
+        '十' => Some(10),
+        '百' => Some(100),
+        '千' => Some(1_000),
+        _ => None,
+    }
+}
+
+/// 大きな単位（万・億・兆）
+fn large_unit(c: char) -> Option<u64> {
+    match c {
+        '万' => Some(10_000),
+        '億' => Some(100_000_000),
+        '兆' => Some(1_000_000_000_000),
+        _ => None,
+    }
+}
+
+fn is_kanji_numeral(c: char) -> bool {
+    kanji_digit(c).is_some() || small_unit(c).is_some() || large_unit(c).is_some()
+}
+
+/// 漢数字の並びを数値に変換（`二〇二四` のような位取りのない表記にも対応）
+fn parse_kanji_number(chars: &[char]) -> Option<u64> {
+    if chars.iter().all(|&c| kanji_digit(c).is_some()) {
+        return chars.iter().try_fold(0u64, |n, &c| {
+            n.checked_mul(10)?.checked_add(kanji_digit(c)?)
+        });
+    }
+    let (mut total, mut section, mut digit) = (0u64, 0u64, None);
+    for &c in chars {
+        if let Some(d) = kanji_digit(c) {
+            if digit.is_some() {
+                return None;
+            }
+            digit = Some(d);
+        } else if let Some(unit) = small_unit(c) {
+            section = section.checked_add(digit.take().unwrap_or(1).checked_mul(unit)?)?;
+        } else if let Some(unit) = large_unit(c) {
+            let value = section + digit.take().unwrap_or(0);
+            if value == 0 {
+                return None;
+            }
+            total = total.checked_add(value.checked_mul(unit)?)?;
+            section = 0;
+        }
+    }
+    total.checked_add(section + digit.unwrap_or(0))
+}
+
+/// 数として使われている漢数字を算用数字に変換
+///
+/// 「一般」「十分」「八百屋」のような語を壊さないよう、漢字に挟まれた並びは変換しない。
+/// 1 文字の並び（`三日` など）は助数詞が続く場合だけ変換する（「十分」は変換しない）。
+fn kanji_to_arabic(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if !is_kanji_numeral(chars[i]) {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        let end = (i..chars.len())
+            .find(|&j| !is_kanji_numeral(chars[j]))
+            .unwrap_or(chars.len());
+        let run = &chars[i..end];
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(end).copied();
+        let counter = next.is_some_and(|n| COUNTERS.contains(&n));
+        // 1 文字の数字と助数詞の後に数量を表さない漢字が続けば語の一部（「一時的」「三日月」）
+        let in_word = chars
+            .get(end + 1)
+            .is_some_and(|&c| is_kanji(c) && !NUMERIC_SUFFIXES.contains(&c));
+        let adverb = run == ['一']
+            && next == Some('時')
+            && chars
+                .get(end + 1)
+                .is_some_and(|c| ADVERBIAL_PARTICLES.contains(c));
+        let convertible = prev.is_none_or(|p| !is_kanji(p) || p == '第')
+            && next.is_none_or(|n| !is_kanji(n) || counter)
+            && (run.len() >= 2 || (counter && next != Some('分') && !in_word && !adverb))
+            && large_unit(run[0]).is_none();
+        match parse_kanji_number(run).filter(|_| convertible) {
+            Some(n) => out.push_str(&n.to_string()),
+            None => out.extend(run),
+        }
+        i = end;
+    }
+    out
+}
+
+/// 1 万未満の数を漢数字に変換（0 は空文字列）
+fn small_to_kanji(mut n: u64) -> String {
+    let mut out = String::new();
+    for (unit, name) in [(1_000, '千'), (100, '百'), (10, '十')] {
+        let d = n / unit;
+        if d > 0 {
+            if d > 1 {
+                out.push(KANJI_DIGITS[d as usize]);
+            }
+            out.push(name);
+        }
+        n %= unit;
+    }
+    if n > 0 {
+        out.push(KANJI_DIGITS[n as usize]);
+    }
+    out
+}
+
+fn number_to_kanji(n: u64) -> String {
+    if n == 0 {
+        return "〇".to_string();
+    }
+    let mut out = String::new();
+    let mut rest = n;
+    for (unit, name) in [
+        (1_000_000_000_000, "兆"),
+        (100_000_000, "億"),
+        (10_000, "万"),
+        (1, ""),
+    ] {
+        let section = rest / unit;
+        if section > 0 {
+            out.push_str(&small_to_kanji(section));
+            out.push_str(name);
+        }
+        rest %= unit;
+    }
+    out
+}
+
+/// 算用数字を漢数字に変換
+///
+/// 小数・時刻・バージョン番号・英単語の一部（`3.14`・`10:30`・`v1`・`GPT4` など）は変換しない。
+fn arabic_to_kanji(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        let end = (i..chars.len())
+            .find(|&j| !chars[j].is_ascii_digit())
+            .unwrap_or(chars.len());
+        let run: String = chars[i..end].iter().collect();
+        let attached = |c: Option<char>| {
+            c.is_some_and(|c| {
+                c.is_ascii_alphabetic() || matches!(c, '.' | ',' | ':' | '/' | '-' | '_')
+            })
+        };
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(end).copied();
+        match run.parse::<u64>() {
+            Ok(n) if !attached(prev) && !attached(next) && n < 10_000_000_000_000_000 => {
+                out.push_str(&number_to_kanji(n))
+            }
+            _ => out.push_str(&run),
+        }
+        i = end;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(json: &str) -> NormalizeConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_width_and_spacing() {
+        let docs = config(r#"{"width": "half", "spacing": true}"#);
+        assert_eq!(
+            docs.apply("ＡＰＩのレスポンスを３回確認"),
+            "API のレスポンスを 3 回確認"
+        );
+        // 記号との間には空白を入れない
+        assert_eq!(docs.apply("「Rust」で書く"), "「Rust」で書く");
+
+        let chat = config(r#"{"width": "full", "spacing": false}"#);
+        assert_eq!(chat.apply("API のレスポンス"), "ＡＰＩのレスポンス");
+        assert_eq!(
+            config(r#"{"spacing": false}"#).apply("Rust と Go は good language です"),
+            "RustとGoはgood languageです"
+        );
+
+        // 未指定の項目は変更しない
+        assert_eq!(
+            NormalizeConfig::default().apply("ＡＰＩ です"),
+            "ＡＰＩ です"
+        );
+    }
+
+    #[test]
+    fn test_punctuation_style() {
+        let academic = config(r#"{"punctuation": "academic"}"#);
+        assert_eq!(academic.apply("今日は、晴れ。"), "今日は，晴れ．");
+
+        let standard = config(r#"{"punctuation": "standard"}"#);
+        assert_eq!(standard.apply("今日は，晴れ．"), "今日は、晴れ。");
+        // 和文の直後の半角の句読点も変換し、英文・小数はそのまま
+        assert_eq!(
+            standard.apply("今日は, 晴れ. Hello, world. 3.14 と ３．１４"),
+            "今日は、晴れ。Hello, world. 3.14 と ３．１４"
+        );
+        // 英字が続く場合はファイル名などの一部
+        assert_eq!(
+            standard.apply("設定ファイル.json を開く。終わり."),
+            "設定ファイル.json を開く。終わり。"
+        );
+    }
+
+    #[test]
+    fn test_kanji_to_arabic() {
+        let arabic = config(r#"{"numerals": "arabic"}"#);
+        assert_eq!(
+            arabic.apply("会議は三日後で、参加者は百二十三人、予算は一万五千円"),
+            "会議は3日後で、参加者は123人、予算は15000円"
+        );
+        assert_eq!(
+            arabic.apply("二〇二四年の第三回は二十分"),
+            "2024年の第3回は20分"
+        );
+        // 数以外の語は変換しない
+        assert_eq!(
+            arabic.apply("一般に十分な量を八百屋で統一した。一から万一"),
+            "一般に十分な量を八百屋で統一した。一から万一"
+        );
+        assert_eq!(
+            arabic.apply("一時的に一時停止した。三日月が見えた"),
+            "一時的に一時停止した。三日月が見えた"
+        );
+        assert_eq!(
+            arabic.apply("一時に三本、五点。一時間後と三日目"),
+            "1時に3本、5点。1時間後と3日目"
+        );
+        // 「一時は」「一時も」は副詞として残す（時刻なら「1時は」と書く）
+        assert_eq!(
+            arabic.apply("一時は危なかった。一時も目を離せない"),
+            "一時は危なかった。一時も目を離せない"
+        );
+    }
+
+    #[test]
+    fn test_arabic_to_kanji() {
+        let kanji = config(r#"{"numerals": "kanji"}"#);
+        assert_eq!(
+            kanji.apply("参加者は123人、予算は15000円、0件"),
+            "参加者は百二十三人、予算は一万五千円、〇件"
+        );
+        assert_eq!(
+            kanji.apply("v1 と GPT4 と 3.14 と 10:30"),
+            "v1 と GPT4 と 3.14 と 10:30"
+        );
+    }
+}
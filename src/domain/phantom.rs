@@ -0,0 +1,113 @@
+/// 無音・雑音に対して音声認識モデルがよく出力する定型句（組み込みの一覧）
+const BUILTIN_PHANTOM_PHRASES: &[&str] = &[
+    "ご視聴ありがとうございました",
+    "ご清聴ありがとうございました",
+    "最後までご視聴いただきありがとうございます",
+    "チャンネル登録よろしくお願いします",
+    "チャンネル登録お願いします",
+    "字幕は視聴者によって作成されました",
+    "Thanks for watching",
+    "Thank you for watching",
+    "Thank you so much for watching",
+    "Please subscribe",
+    "Subtitles by the Amara.org community",
+];
+
+/// 無音時の定型句（幻聴）の検出
+///
+/// 句読点・空白・大文字小文字を無視して、転写結果全体が定型句（またはその繰り返し）だけで
+/// できている場合に一致とみなす。実際の発話の途中に含まれる場合は一致しない。
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhantomPhraseFilter {
+    /// 正規化前の定型句と正規化後の文字列
+    phrases: Vec<(String, String)>,
+}
+
+impl Default for PhantomPhraseFilter {
+    /// 組み込みの一覧で作成
+    fn default() -> Self {
+        Self::new(BUILTIN_PHANTOM_PHRASES.iter().copied())
+    }
+}
+
+impl PhantomPhraseFilter {
+    /// 定型句を指定して作成
+    pub fn new<S: Into<String>>(phrases: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            phrases: Vec::new(),
+        }
+        .with_phrases(phrases)
+    }
+
+    /// 定型句を追加
+    pub fn with_phrases<S: Into<String>>(mut self, phrases: impl IntoIterator<Item = S>) -> Self {
+        for phrase in phrases {
+            let phrase = phrase.into();
+            let normalized = normalize(&phrase);
+            if !normalized.is_empty() && !self.phrases.iter().any(|(_, n)| *n == normalized) {
+                self.phrases.push((phrase, normalized));
+            }
+        }
+        self
+    }
+
+    /// 定型句の一覧
+    pub fn phrases(&self) -> impl Iterator<Item = &str> {
+        self.phrases.iter().map(|(phrase, _)| phrase.as_str())
+    }
+
+    /// 転写結果が定型句だけでできていれば、最初に一致した定型句を返す
+    pub fn matches(&self, text: &str) -> Option<&str> {
+        let normalized = normalize(text);
+        let mut rest = normalized.as_str();
+        let mut first = None;
+        while !rest.is_empty() {
+            let (phrase, normalized) = self.phrases.iter().find(|(_, n)| rest.starts_with(n))?;
+            first.get_or_insert(phrase.as_str());
+            rest = &rest[normalized.len()..];
+        }
+        first
+    }
+}
+
+/// 比較用に文字・数字以外を除き、小文字にそろえる
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_whole_text_only() {
+        let filter = PhantomPhraseFilter::default();
+        assert_eq!(
+            filter.matches("ご視聴ありがとうございました。"),
+            Some("ご視聴ありがとうございました")
+        );
+        assert_eq!(
+            filter.matches(" thanks for watching! Thanks for watching."),
+            Some("Thanks for watching")
+        );
+        assert_eq!(
+            filter.matches("ご視聴ありがとうございました。チャンネル登録よろしくお願いします。"),
+            Some("ご視聴ありがとうございました")
+        );
+
+        // 実際の発話に含まれる場合や空文字列は一致しない
+        assert_eq!(filter.matches("動画のご視聴ありがとうございました"), None);
+        assert_eq!(filter.matches("Thanks for watching the demo"), None);
+        assert_eq!(filter.matches("。"), None);
+    }
+
+    #[test]
+    fn test_with_phrases() {
+        let filter = PhantomPhraseFilter::default().with_phrases(["お疲れ様でした", "  "]);
+        assert_eq!(filter.matches("お疲れ様でした"), Some("お疲れ様でした"));
+        assert_eq!(filter.phrases().count(), BUILTIN_PHANTOM_PHRASES.len() + 1);
+    }
+}
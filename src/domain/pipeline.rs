@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::fillers::FillerConfig;
+use crate::domain::normalize::NormalizeConfig;
+use crate::domain::profile::DictionaryStage;
+use crate::domain::spoken::SpokenCommandConfig;
+
+/// 外部フックのタイムアウトの既定値（秒）
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 5;
+
+/// 転写後のテキスト処理の段（設定ファイルの `pipeline` の要素）
+///
+/// 段は上から順に適用する。`profiles` を指定した段は、そのプロファイルでの転写にだけ適用する。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageSpec {
+    /// 段の名前（`pipeline test` やログに表示。省略時は種類名）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// 段の種類と設定
+    #[serde(flatten)]
+    pub kind: StageKind,
+    /// `false` で段を無効化
+    #[serde(default = "enabled_by_default", skip_serializing_if = "is_enabled")]
+    pub enabled: bool,
+    /// 適用するプロファイル（空ならすべて）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<String>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn is_enabled(enabled: &bool) -> bool {
+    *enabled
+}
+
+impl StageSpec {
+    /// 種類を指定して作成
+    pub fn new(kind: StageKind) -> Self {
+        Self {
+            name: None,
+            kind,
+            enabled: true,
+            profiles: Vec::new(),
+        }
+    }
+
+    /// 段の名前
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(self.kind.name())
+    }
+
+    /// プロファイル `profile` での転写に適用するか
+    pub fn applies_to(&self, profile: Option<&str>) -> bool {
+        self.enabled
+            && (self.profiles.is_empty()
+                || profile.is_some_and(|profile| self.profiles.iter().any(|p| p == profile)))
+    }
+
+    /// 段を設定しない場合の既定のパイプライン（辞書変換・書き直し・翻訳）
+    pub fn default_pipeline() -> Vec<StageSpec> {
+        vec![
+            StageSpec::new(StageKind::Dictionary {
+                when: Some(DictionaryStage::Before),
+            }),
+            StageSpec::new(StageKind::Rewrite),
+            StageSpec::new(StageKind::Translate),
+            StageSpec::new(StageKind::Dictionary {
+                when: Some(DictionaryStage::After),
+            }),
+        ]
+    }
+}
+
+/// 段の種類
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum StageKind {
+    /// ユーザー辞書による置換
+    Dictionary {
+        /// 翻訳の前後どちらの段として扱うか（省略時は常に適用。翻訳しない場合は `before` の段だけ適用）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        when: Option<DictionaryStage>,
+    },
+    /// LLM による書き直し
+    Rewrite,
+    /// 翻訳
+    Translate,
+    /// 話し言葉の記号・編集コマンド（「改行」「句点」など）の変換
+    SpokenCommands(SpokenCommandConfig),
+    /// 日本語の表記（英数字の幅・句読点・和欧間の空白・数字）の正規化
+    Normalize(NormalizeConfig),
+    /// フィラー（「えーと」「um」など）と単語の繰り返しの除去（LLM を使わずローカルで処理）
+    Fillers(FillerConfig),
+    /// 外部コマンド（テキストを標準入力で受け取り、標準出力を結果とする）
+    Hook {
+        /// プログラムと引数（シェル風のクォートに対応）
+        command: String,
+        /// タイムアウト（秒）
+        #[serde(default = "default_hook_timeout_secs")]
+        timeout_secs: u64,
+    },
+}
+
+fn default_hook_timeout_secs() -> u64 {
+    DEFAULT_HOOK_TIMEOUT_SECS
+}
+
+impl StageKind {
+    /// 種類名
+    pub fn name(&self) -> &'static str {
+        match self {
+            StageKind::Dictionary { .. } => "dictionary",
+            StageKind::Rewrite => "rewrite",
+            StageKind::Translate => "translate",
+            StageKind::SpokenCommands(_) => "spoken_commands",
+            StageKind::Normalize(_) => "normalize",
+            StageKind::Fillers(_) => "fillers",
+            StageKind::Hook { .. } => "hook",
+        }
+    }
+}
+
+/// パイプラインの各段の実行結果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PipelineReport {
+    /// 入力テキスト
+    pub input: String,
+    /// 実行した段（適用しなかった段は含まない）
+    pub stages: Vec<StageReport>,
+}
+
+impl PipelineReport {
+    /// 全段の処理時間の合計（ミリ秒）
+    pub fn total_ms(&self) -> u64 {
+        self.stages.iter().map(|s| s.elapsed_ms).sum()
+    }
+
+    /// 最終的なテキスト
+    pub fn output(&self) -> &str {
+        self.stages
+            .last()
+            .map_or(self.input.as_str(), |s| s.text.as_str())
+    }
+
+    /// 段ごとの処理時間の要約（ログ用）
+    pub fn timings(&self) -> String {
+        self.stages
+            .iter()
+            .map(|s| format!("{} {}ms", s.name, s.elapsed_ms))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl fmt::Display for PipelineReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "input: {}", self.input)?;
+        for (i, stage) in self.stages.iter().enumerate() {
+            write!(f, "{}. {} ({} ms)", i + 1, stage.name, stage.elapsed_ms)?;
+            match &stage.error {
+                Some(error) => writeln!(f, " failed: {}", error)?,
+                None => writeln!(f)?,
+            }
+            writeln!(f, "   {}", stage.text)?;
+        }
+        write!(f, "output: {} ({} ms)", self.output(), self.total_ms())
+    }
+}
+
+/// 段ごとの実行結果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StageReport {
+    /// 段の名前
+    pub name: String,
+    /// 処理時間（ミリ秒）
+    pub elapsed_ms: u64,
+    /// 段を適用した後のテキスト
+    pub text: String,
+    /// 失敗した場合のエラー（テキストは段の適用前のまま）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fillers::FillerLevel;
+
+    #[test]
+    fn test_stage_spec_deserialization() {
+        let stages: Vec<StageSpec> = serde_json::from_str(
+            r#"[
+                {"stage": "dictionary"},
+                {"stage": "rewrite", "profiles": ["pr"]},
+                {"stage": "spoken_commands", "escape": {"ja": "そのまま"}},
+                {"stage": "fillers", "level": "light"},
+                {"stage": "hook", "name": "kana", "command": "kanaconv --hira", "enabled": false}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(stages[0].kind, StageKind::Dictionary { when: None });
+        assert_eq!(stages[0].name(), "dictionary");
+        assert!(stages[0].applies_to(None));
+
+        assert!(stages[1].applies_to(Some("pr")));
+        assert!(!stages[1].applies_to(Some("chat")));
+        assert!(!stages[1].applies_to(None));
+
+        let StageKind::SpokenCommands(config) = &stages[2].kind else {
+            panic!("unexpected stage: {:?}", stages[2]);
+        };
+        assert!(config.builtin);
+        assert_eq!(config.escape["ja"], "そのまま");
+
+        assert_eq!(
+            stages[3].kind,
+            StageKind::Fillers(FillerConfig {
+                level: FillerLevel::Light,
+                allow: Vec::new(),
+            })
+        );
+
+        assert_eq!(stages[4].name(), "kana");
+        assert_eq!(
+            stages[4].kind,
+            StageKind::Hook {
+                command: "kanaconv --hira".to_string(),
+                timeout_secs: DEFAULT_HOOK_TIMEOUT_SECS,
+            }
+        );
+        assert!(!stages[4].applies_to(None));
+
+        // 既定値の項目は出力しない
+        assert_eq!(
+            serde_json::to_string(&StageSpec::new(StageKind::Rewrite)).unwrap(),
+            r#"{"stage":"rewrite"}"#
+        );
+    }
+
+    #[test]
+    fn test_report_display() {
+        let report = PipelineReport {
+            input: "えー テスト".to_string(),
+            stages: vec![
+                StageReport {
+                    name: "dictionary".to_string(),
+                    elapsed_ms: 1,
+                    text: "えー test".to_string(),
+                    error: None,
+                },
+                StageReport {
+                    name: "rewrite".to_string(),
+                    elapsed_ms: 10,
+                    text: "えー test".to_string(),
+                    error: Some("offline".to_string()),
+                },
+            ],
+        };
+        assert_eq!(report.output(), "えー test");
+        assert_eq!(report.timings(), "dictionary 1ms, rewrite 10ms");
+        assert_eq!(
+            report.to_string(),
+            "input: えー テスト\n1. dictionary (1 ms)\n   えー test\n2. rewrite (10 ms) failed: offline\n   えー test\noutput: えー test (11 ms)"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// 出力プロファイル
+///
+/// 用途ごと（PR 説明文・チャットなど）の転写設定をまとめたもの。
+/// リクエストで明示した値がプロファイルより優先され、どちらもなければデーモンの既定値を使う。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// 転写言語（`auto` で自動判定）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// 翻訳先の言語（例: `en`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translate: Option<String>,
+    /// 辞書変換を翻訳の前後どちらで適用するか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dictionary: Option<DictionaryStage>,
+    /// LLM による書き直しの指示（空文字列で書き直しを無効化）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rewrite: Option<String>,
+}
+
+/// 翻訳する場合に辞書変換を適用する段階
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DictionaryStage {
+    /// 翻訳前の転写テキストに適用（話した言語の表記ゆれを直す）
+    #[default]
+    Before,
+    /// 翻訳後のテキストに適用（訳語を統一する）
+    After,
+}
+
+impl FromStr for DictionaryStage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "before" => Ok(Self::Before),
+            "after" => Ok(Self::After),
+            other => Err(format!("unknown dictionary stage: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for DictionaryStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DictionaryStage::Before => "before",
+            DictionaryStage::After => "after",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_deserialization() {
+        let profile: Profile =
+            serde_json::from_str(r#"{"translate":"en","dictionary":"after"}"#).unwrap();
+        assert_eq!(profile.translate.as_deref(), Some("en"));
+        assert_eq!(profile.dictionary, Some(DictionaryStage::After));
+        assert_eq!(profile.language, None);
+
+        // 未指定の項目は出力しない
+        assert_eq!(serde_json::to_string(&Profile::default()).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_parse_dictionary_stage() {
+        assert_eq!(" After ".parse(), Ok(DictionaryStage::After));
+        assert_eq!(DictionaryStage::Before.to_string(), "before");
+        assert!("middle".parse::<DictionaryStage>().is_err());
+    }
+}
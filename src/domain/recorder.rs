@@ -1,4 +1,4 @@
-use crate::infrastructure::audio::{AudioBackend, AudioData};
+use crate::infrastructure::audio::{AudioBackend, AudioData, PcmChunk};
 use crate::monitoring::{
     MemoryMonitor,
     metrics::{MetricsCollector, RecordingMode},
@@ -71,6 +71,11 @@ impl<T: AudioBackend> Recorder<T> {
     pub fn is_recording(&self) -> bool {
         self.backend.is_recording()
     }
+
+    /// 録音を続けたまま、蓄積済みのサンプルを取り出します。
+    pub fn drain_samples(&self) -> Option<PcmChunk> {
+        self.backend.drain_samples()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+
+/// プロンプトを「大きい」とみなす文字数の既定値
+pub const DEFAULT_LARGE_PROMPT_CHARS: usize = 200;
+
+/// 転写リクエストごとのモデル・プロバイダーの振り分け方針
+///
+/// 設定ファイル（config.json）の `routing` で定義する。ルールを上から順に評価し、
+/// 最初に条件を満たしたルールのプロバイダーで転写する。どれにも当てはまらなければ
+/// `VOICE_INPUT_STT_PROVIDER` の既定のプロバイダーを使う。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingPolicy {
+    /// プロンプトを「大きい」とみなす文字数
+    pub large_prompt_chars: usize,
+    /// 振り分けルール（上から順に評価）
+    pub rules: Vec<RouteRule>,
+}
+
+impl Default for RoutingPolicy {
+    fn default() -> Self {
+        Self {
+            large_prompt_chars: DEFAULT_LARGE_PROMPT_CHARS,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl RoutingPolicy {
+    /// ルールが定義されているか
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// リクエストに当てはまる最初のルールの位置
+    pub fn route(&self, request: &RouteRequest) -> Option<usize> {
+        let large_prompt = request.prompt_chars >= self.large_prompt_chars;
+        self.rules
+            .iter()
+            .position(|rule| rule.matches(request, large_prompt))
+    }
+}
+
+/// 振り分けルール
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteRule {
+    /// ルール名（利用記録に残る）
+    pub name: String,
+    /// 転写に使うプロバイダー（`openai:gpt-4o-mini-transcribe` など、`VOICE_INPUT_STT_FALLBACK` と同じ形式）
+    pub provider: String,
+    /// この秒数以上の録音に適用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_secs: Option<f64>,
+    /// この秒数未満の録音に適用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_secs: Option<f64>,
+    /// 転写言語がいずれかに一致する場合に適用（空ならすべて。自動判定は `auto`）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    /// プロファイルがいずれかに一致する場合に適用（空ならすべて）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<String>,
+    /// プロンプトが大きい（`true`）・小さい（`false`）場合に適用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub large_prompt: Option<bool>,
+}
+
+impl RouteRule {
+    fn matches(&self, request: &RouteRequest, large_prompt: bool) -> bool {
+        let language = request.language.as_deref().unwrap_or("auto");
+        self.min_secs.is_none_or(|min| request.duration_secs >= min)
+            && self.max_secs.is_none_or(|max| request.duration_secs < max)
+            && (self.languages.is_empty()
+                || self
+                    .languages
+                    .iter()
+                    .any(|l| l.eq_ignore_ascii_case(language)))
+            && (self.profiles.is_empty()
+                || request
+                    .profile
+                    .as_deref()
+                    .is_some_and(|profile| self.profiles.iter().any(|p| p == profile)))
+            && self.large_prompt.is_none_or(|large| large == large_prompt)
+    }
+}
+
+/// 振り分けに使うリクエストの特徴
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteRequest {
+    /// 録音の長さ（秒）
+    pub duration_secs: f64,
+    /// 転写言語（自動判定なら `None`）
+    pub language: Option<String>,
+    /// プロファイル名
+    pub profile: Option<String>,
+    /// プロンプトの文字数
+    pub prompt_chars: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RoutingPolicy {
+        serde_json::from_str(
+            r#"{
+                "large_prompt_chars": 20,
+                "rules": [
+                    {"name": "short", "provider": "openai:gpt-4o-mini-transcribe", "max_secs": 10},
+                    {"name": "technical", "provider": "openai:gpt-4o-transcribe", "large_prompt": true},
+                    {"name": "english-chat", "provider": "whisper-cpp", "languages": ["en"], "profiles": ["chat"]}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn request(
+        duration_secs: f64,
+        language: &str,
+        profile: Option<&str>,
+        prompt: &str,
+    ) -> RouteRequest {
+        RouteRequest {
+            duration_secs,
+            language: Some(language.to_string()),
+            profile: profile.map(str::to_string),
+            prompt_chars: prompt.chars().count(),
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let policy = policy();
+        let long_prompt = "Rust, tokio, async-trait, serde";
+
+        assert_eq!(
+            policy.route(&request(3.0, "ja", None, long_prompt)),
+            Some(0)
+        );
+        assert_eq!(
+            policy.route(&request(10.0, "ja", None, long_prompt)),
+            Some(1)
+        );
+        assert_eq!(
+            policy.route(&request(30.0, "EN", Some("chat"), "")),
+            Some(2)
+        );
+        assert_eq!(policy.route(&request(30.0, "en", Some("pr"), "")), None);
+        assert_eq!(policy.route(&request(30.0, "ja", Some("chat"), "")), None);
+    }
+
+    #[test]
+    fn test_defaults() {
+        let policy: RoutingPolicy = serde_json::from_str("{}").unwrap();
+        assert!(policy.is_empty());
+        assert_eq!(policy.large_prompt_chars, DEFAULT_LARGE_PROMPT_CHARS);
+        assert_eq!(policy.route(&RouteRequest::default()), None);
+    }
+}
@@ -0,0 +1,541 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 話し言葉の記号・編集コマンドの設定（パイプラインの `spoken_commands` 段）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpokenCommandConfig {
+    /// 組み込みの語彙を使うか
+    pub builtin: bool,
+    /// 追加の語彙（組み込みと同じ言語・言葉なら上書き）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<SpokenCommand>,
+    /// 言語ごとのエスケープ語（組み込みの `ja: リテラル`・`en: literal` を上書き）
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub escape: BTreeMap<String, String>,
+}
+
+impl Default for SpokenCommandConfig {
+    fn default() -> Self {
+        Self {
+            builtin: true,
+            commands: Vec::new(),
+            escape: BTreeMap::new(),
+        }
+    }
+}
+
+/// 話し言葉のコマンド
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpokenCommand {
+    /// 言語コード（`ja`・`en` など）
+    pub language: String,
+    /// 認識する言葉（英語は単語の境界でのみ一致し、大文字小文字を区別しない）
+    pub phrase: String,
+    /// 動作
+    pub action: SpokenAction,
+}
+
+impl SpokenCommand {
+    fn new(language: &str, phrase: impl Into<String>, action: SpokenAction) -> Self {
+        Self {
+            language: language.to_string(),
+            phrase: phrase.into(),
+            action,
+        }
+    }
+}
+
+/// コマンドの動作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpokenAction {
+    /// 文字列を挿入
+    Insert(String),
+    /// それまでの出力をすべて消す
+    Clear,
+}
+
+/// 組み込みの語彙
+fn builtin_commands() -> Vec<SpokenCommand> {
+    let insert = |language, phrase: &str, text: &str| {
+        SpokenCommand::new(language, phrase, SpokenAction::Insert(text.to_string()))
+    };
+    let mut commands = vec![
+        insert("ja", "改行", "\n"),
+        insert("ja", "新しい段落", "\n\n"),
+        insert("ja", "句点", "。"),
+        insert("ja", "読点", "、"),
+        insert("ja", "疑問符", "？"),
+        insert("ja", "感嘆符", "！"),
+        SpokenCommand::new("ja", "全部消して", SpokenAction::Clear),
+        insert("en", "new line", "\n"),
+        insert("en", "newline", "\n"),
+        insert("en", "new paragraph", "\n\n"),
+        insert("en", "period", "."),
+        insert("en", "full stop", "."),
+        insert("en", "comma", ","),
+        insert("en", "colon", ":"),
+        insert("en", "question mark", "?"),
+        insert("en", "exclamation mark", "!"),
+        insert("en", "exclamation point", "!"),
+        insert("en", "open paren", "("),
+        insert("en", "open parenthesis", "("),
+        insert("en", "close paren", ")"),
+        insert("en", "close parenthesis", ")"),
+        SpokenCommand::new("en", "delete everything", SpokenAction::Clear),
+    ];
+    // 転写モデルによって仮名・漢字の表記が揺れるため組み合わせをすべて登録する
+    for (opens, open, close) in [
+        (&["かっこ", "カッコ", "括弧"][..], "（", "）"),
+        (&["かぎかっこ", "カギカッコ", "鉤括弧"][..], "「", "」"),
+    ] {
+        for phrase in opens {
+            commands.push(insert("ja", phrase, open));
+            for suffix in ["とじ", "閉じ"] {
+                commands.push(insert("ja", &format!("{}{}", phrase, suffix), close));
+            }
+        }
+    }
+    commands
+}
+
+/// 組み込みのエスケープ語
+const BUILTIN_ESCAPES: &[(&str, &str)] = &[("ja", "リテラル"), ("en", "literal")];
+
+/// 挿入する文字列の種類（前後の空白・句読点の扱いを決める）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InsertKind {
+    /// 改行など空白だけの文字列
+    Break,
+    /// 開き括弧
+    Opening,
+    /// 閉じ括弧
+    Closing,
+    /// 句読点などの記号
+    Mark,
+}
+
+impl InsertKind {
+    fn of(text: &str) -> Self {
+        if text.chars().all(char::is_whitespace) {
+            return InsertKind::Break;
+        }
+        match text.chars().last() {
+            Some('(' | '（' | '[' | '［' | '{' | '「' | '『' | '【' | '“') => {
+                InsertKind::Opening
+            }
+            Some(')' | '）' | ']' | '］' | '}' | '」' | '』' | '】' | '”') => {
+                InsertKind::Closing
+            }
+            _ => InsertKind::Mark,
+        }
+    }
+}
+
+/// 転写モデルがコマンドの前後に付けがちな区切りの読点
+fn is_comma(c: char) -> bool {
+    matches!(c, '、' | '，' | ',')
+}
+
+/// 転写モデルがコマンドの前後に付けがちな句点
+fn is_period(c: char) -> bool {
+    matches!(c, '。' | '．' | '.')
+}
+
+/// 文の終わりの記号
+fn is_sentence_end(c: char) -> bool {
+    is_period(c) || matches!(c, '!' | '?' | '！' | '？' | '\n')
+}
+
+/// 漢字・カタカナ（日本語のコマンドの直後に続けば、より長い語の一部とみなす）
+fn is_kanji_or_katakana(c: char) -> bool {
+    matches!(c,
+        '\u{30A0}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '々')
+}
+
+/// 英語で名詞として使われたコマンドの言葉の前に付く冠詞（"a new line"・"the period"）
+const ARTICLES: &[&str] = &["a", "an", "the"];
+
+/// 話し言葉の記号・編集コマンドの変換
+///
+/// 「改行」「句点」「かっこ … かっことじ」などの言葉を記号に置き換え、「全部消して」で
+/// それまでの出力を消す。コマンドの前後に転写モデルが付けた空白・句読点は取り除く。
+/// エスケープ語（「リテラル 改行」など）に続くコマンドは、言葉のまま出力する。
+///
+/// 普通の文中の言葉を壊さないよう、消去のコマンドは文末にある場合だけ、開き括弧のコマンドは
+/// 同じ文の中に閉じ括弧のコマンドが続く場合だけ変換する（「全部消してください」「かっこいい」は変換しない）。
+/// 改行・記号のコマンドは、直後に別のコマンドが続く場合を除き、日本語は漢字・カタカナが直接続く場合
+/// （「改行コード」）、英語は冠詞の後（"a new line"）と、文末の記号で小文字の語が続く場合
+/// （"The trial period ended."）は変換しない。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpokenCommands {
+    /// 長い言葉から順に並べた語彙
+    commands: Vec<SpokenCommand>,
+    /// 言語ごとのエスケープ語
+    escapes: BTreeMap<String, String>,
+}
+
+impl Default for SpokenCommands {
+    fn default() -> Self {
+        Self::new(&SpokenCommandConfig::default())
+    }
+}
+
+impl SpokenCommands {
+    /// 設定から作成
+    pub fn new(config: &SpokenCommandConfig) -> Self {
+        let mut commands: Vec<SpokenCommand> = Vec::new();
+        let mut escapes = BTreeMap::new();
+        if config.builtin {
+            commands = builtin_commands();
+            escapes.extend(
+                BUILTIN_ESCAPES
+                    .iter()
+                    .map(|(language, word)| (language.to_string(), word.to_string())),
+            );
+        }
+        for command in &config.commands {
+            commands.retain(|c| {
+                !(c.language.eq_ignore_ascii_case(&command.language)
+                    && c.phrase.to_lowercase() == command.phrase.to_lowercase())
+            });
+            commands.push(command.clone());
+        }
+        commands.retain(|c| !c.phrase.trim().is_empty());
+        commands.sort_by_key(|c| std::cmp::Reverse(c.phrase.chars().count()));
+        escapes.extend(
+            config
+                .escape
+                .iter()
+                .map(|(language, word)| (language.to_ascii_lowercase(), word.clone())),
+        );
+        escapes.retain(|_, word| !word.trim().is_empty());
+        Self { commands, escapes }
+    }
+
+    /// テキストのコマンドを変換（`language` が `None` ならすべての言語の語彙を使う）
+    pub fn apply(&self, text: &str, language: Option<&str>) -> String {
+        let in_language =
+            |l: &str| language.is_none_or(|language| l.eq_ignore_ascii_case(language));
+        let commands: Vec<&SpokenCommand> = self
+            .commands
+            .iter()
+            .filter(|c| in_language(&c.language))
+            .collect();
+        let escapes: Vec<&str> = self
+            .escapes
+            .iter()
+            .filter(|(l, _)| in_language(l))
+            .map(|(_, word)| word.as_str())
+            .collect();
+        let find_command = |chars: &[char], at: usize| {
+            commands
+                .iter()
+                .find_map(|c| match_phrase(chars, at, &c.phrase).map(|end| (end, *c)))
+        };
+
+        let chars: Vec<char> = text.chars().collect();
+        let mut out = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            // エスケープ語に続くコマンドは言葉のまま出力
+            if let Some(end) = escapes.iter().find_map(|e| match_phrase(&chars, i, e)) {
+                let next = skip(&chars, end, |c| c.is_whitespace() || is_comma(c));
+                if let Some((command_end, _)) = find_command(&chars, next) {
+                    out.extend(&chars[next..command_end]);
+                    i = command_end;
+                    continue;
+                }
+            }
+
+            let found = find_command(&chars, i).filter(|(end, command)| match &command.action {
+                SpokenAction::Clear => ends_sentence(&chars, *end),
+                SpokenAction::Insert(insert) if InsertKind::of(insert) == InsertKind::Opening => {
+                    (*end..chars.len())
+                        .take_while(|&j| !is_sentence_end(chars[j]))
+                        .any(|j| {
+                            find_command(&chars, j).is_some_and(|(_, c)| {
+                                matches!(&c.action, SpokenAction::Insert(close)
+                                    if InsertKind::of(close) == InsertKind::Closing)
+                            })
+                        })
+                }
+                SpokenAction::Insert(insert) if InsertKind::of(insert) == InsertKind::Closing => {
+                    true
+                }
+                SpokenAction::Insert(insert) => {
+                    stands_alone(&chars, i, *end, command, insert)
+                        || find_command(&chars, skip(&chars, *end, char::is_whitespace)).is_some()
+                }
+            });
+            let Some((end, command)) = found else {
+                out.push(chars[i]);
+                i += 1;
+                continue;
+            };
+            let kind = match &command.action {
+                SpokenAction::Clear => {
+                    out.clear();
+                    InsertKind::Break
+                }
+                SpokenAction::Insert(insert) => {
+                    let kind = InsertKind::of(insert);
+                    match kind {
+                        InsertKind::Break => trim_end(&mut out, char::is_whitespace),
+                        InsertKind::Opening => {}
+                        InsertKind::Closing | InsertKind::Mark => trim_end(&mut out, |c| {
+                            c.is_whitespace() || is_comma(c) || is_period(c)
+                        }),
+                    }
+                    out.push_str(insert);
+                    kind
+                }
+            };
+            // 閉じ括弧の後の句読点は文の区切りとして残す
+            i = match kind {
+                InsertKind::Closing => skip(&chars, end, char::is_whitespace),
+                _ => skip(&chars, end, |c| {
+                    c.is_whitespace() || is_comma(c) || is_period(c)
+                }),
+            };
+            // 英文の記号の後は次の単語との間に空白を入れる
+            if matches!(kind, InsertKind::Closing | InsertKind::Mark)
+                && out.ends_with(|c: char| c.is_ascii_punctuation())
+                && chars.get(i).is_some_and(|c| c.is_ascii_alphanumeric())
+            {
+                out.push(' ');
+            }
+        }
+        out.trim_matches(' ').to_string()
+    }
+}
+
+/// `at` の位置から `phrase` が続けば、その終わりの位置を返す
+///
+/// 大文字小文字を区別せず、言葉の中の空白は 0 個以上の空白に一致する。英数字で始まる・終わる
+/// 言葉は、前後が英数字でない場合（単語の境界）にのみ一致する。
+fn match_phrase(chars: &[char], at: usize, phrase: &str) -> Option<usize> {
+    let phrase: Vec<char> = phrase.trim().chars().collect();
+    let first = *phrase.first()?;
+    if first.is_ascii_alphanumeric() && at > 0 && chars[at - 1].is_ascii_alphanumeric() {
+        return None;
+    }
+    let mut i = at;
+    for &p in &phrase {
+        if p.is_whitespace() {
+            i = skip(chars, i, char::is_whitespace);
+            continue;
+        }
+        let c = *chars.get(i)?;
+        if !c.to_lowercase().eq(p.to_lowercase()) {
+            return None;
+        }
+        i += 1;
+    }
+    let last = phrase[phrase.len() - 1];
+    if last.is_ascii_alphanumeric() && chars.get(i).is_some_and(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some(i)
+}
+
+/// `start..end` のコマンドの言葉が、前後の語から独立して使われているか（改行・記号のコマンド用）
+fn stands_alone(
+    chars: &[char],
+    start: usize,
+    end: usize,
+    command: &SpokenCommand,
+    insert: &str,
+) -> bool {
+    if !command
+        .phrase
+        .starts_with(|c: char| c.is_ascii_alphanumeric())
+    {
+        return !chars.get(end).is_some_and(|&c| is_kanji_or_katakana(c));
+    }
+    // 直前の語
+    let mut before: Vec<char> = chars[..start]
+        .iter()
+        .rev()
+        .skip_while(|c| c.is_whitespace())
+        .take_while(|c| c.is_ascii_alphabetic())
+        .copied()
+        .collect();
+    before.reverse();
+    let before: String = before.into_iter().collect();
+    if ARTICLES.iter().any(|a| a.eq_ignore_ascii_case(&before)) {
+        return false;
+    }
+    // 文末の記号の後は文の終わりか、大文字で始まる次の文が続く
+    let next = chars.get(skip(chars, end, char::is_whitespace));
+    !insert.ends_with(['.', '?', '!'])
+        || next.is_none_or(|c| !c.is_alphanumeric() || c.is_uppercase())
+}
+
+/// `at` の位置で文が終わるか（空白の後がテキストの終わり・文末の記号）
+fn ends_sentence(chars: &[char], at: usize) -> bool {
+    let i = skip(chars, at, |c| c.is_whitespace() && c != '\n');
+    chars.get(i).is_none_or(|&c| is_sentence_end(c))
+}
+
+/// `from` の位置から `pred` を満たす文字を読み飛ばした位置
+fn skip(chars: &[char], from: usize, pred: impl Fn(char) -> bool) -> usize {
+    let mut i = from;
+    while chars.get(i).is_some_and(|&c| pred(c)) {
+        i += 1;
+    }
+    i
+}
+
+/// 末尾の `pred` を満たす文字を取り除く
+fn trim_end(text: &mut String, pred: impl Fn(char) -> bool) {
+    let len = text.trim_end_matches(pred).len();
+    text.truncate(len);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_japanese_commands() {
+        let commands = SpokenCommands::default();
+        let apply = |text| commands.apply(text, Some("ja"));
+
+        assert_eq!(
+            apply("最初の行です。改行、次の行です句点"),
+            "最初の行です。\n次の行です。"
+        );
+        assert_eq!(
+            apply("これはかっこ重要かっことじです"),
+            "これは（重要）です"
+        );
+        assert_eq!(
+            apply("彼は鉤括弧はい鉤括弧閉じと言った。"),
+            "彼は「はい」と言った。"
+        );
+        assert_eq!(apply("間違えた、全部消して。正しい文です"), "正しい文です");
+        // 他の言語の語彙は使わない
+        assert_eq!(apply("new line"), "new line");
+    }
+
+    #[test]
+    fn test_english_commands_respect_word_boundaries() {
+        let commands = SpokenCommands::default();
+        let apply = |text| commands.apply(text, Some("en"));
+
+        assert_eq!(
+            apply("Hello comma world period New line. How are you question mark"),
+            "Hello, world.\nHow are you?"
+        );
+        assert_eq!(
+            apply("see open paren the docs close paren, then periodically retry"),
+            "see (the docs), then periodically retry"
+        );
+        assert_eq!(apply("one Newline two"), "one\ntwo");
+    }
+
+    #[test]
+    fn test_commands_inside_ordinary_words_are_kept() {
+        let commands = SpokenCommands::default();
+
+        // 文末でない消去のコマンドは普通の文として残す
+        assert_eq!(
+            commands.apply("ファイルを全部消してください。", Some("ja")),
+            "ファイルを全部消してください。"
+        );
+        assert_eq!(
+            commands.apply("please delete everything in the folder", Some("en")),
+            "please delete everything in the folder"
+        );
+        assert_eq!(commands.apply("wrong delete everything", Some("en")), "");
+
+        // 閉じ括弧のコマンドが続かない開き括弧のコマンドは言葉のまま
+        assert_eq!(
+            commands.apply("このデザインはかっこいい。", Some("ja")),
+            "このデザインはかっこいい。"
+        );
+        assert_eq!(
+            commands.apply("カッコよく仕上げる。かっこ仮かっことじ", Some("ja")),
+            "カッコよく仕上げる。（仮）"
+        );
+
+        // 長い語の一部・名詞として使われた改行・記号のコマンドは言葉のまま
+        assert_eq!(
+            commands.apply("改行コードはLFです", Some("ja")),
+            "改行コードはLFです"
+        );
+        assert_eq!(
+            commands.apply("疑問符付きの文です。改行、次の文", Some("ja")),
+            "疑問符付きの文です。\n次の文"
+        );
+        assert_eq!(
+            commands.apply("The trial period ended.", Some("en")),
+            "The trial period ended."
+        );
+        assert_eq!(
+            commands.apply("we launched a new line of products", Some("en")),
+            "we launched a new line of products"
+        );
+        assert_eq!(
+            commands.apply("Put the comma here", Some("en")),
+            "Put the comma here"
+        );
+        assert_eq!(
+            commands.apply("It ended period Then we left", Some("en")),
+            "It ended. Then we left"
+        );
+    }
+
+    #[test]
+    fn test_escape_keeps_command_word() {
+        let commands = SpokenCommands::default();
+        assert_eq!(
+            commands.apply("リテラル改行コードを直す", Some("ja")),
+            "改行コードを直す"
+        );
+        assert_eq!(
+            commands.apply("a literal period of time", Some("en")),
+            "a period of time"
+        );
+        // コマンドが続かないエスケープ語はそのまま
+        assert_eq!(
+            commands.apply("literal meaning", Some("en")),
+            "literal meaning"
+        );
+    }
+
+    #[test]
+    fn test_custom_vocabulary() {
+        let config: SpokenCommandConfig = serde_json::from_str(
+            r#"{
+                "commands": [
+                    {"language": "ja", "phrase": "中黒", "action": {"insert": "・"}},
+                    {"language": "ja", "phrase": "改行", "action": {"insert": "<br>"}},
+                    {"language": "ja", "phrase": "やり直し", "action": "clear"}
+                ],
+                "escape": {"ja": "そのまま"}
+            }"#,
+        )
+        .unwrap();
+        let commands = SpokenCommands::new(&config);
+
+        assert_eq!(
+            commands.apply("えーとやり直し。赤中黒、青改行句点", None),
+            "赤・青<br>。"
+        );
+        assert_eq!(commands.apply("そのまま句点", Some("ja")), "句点");
+
+        // 組み込みの語彙を使わない
+        let config = SpokenCommandConfig {
+            builtin: false,
+            ..Default::default()
+        };
+        let commands = SpokenCommands::new(&config);
+        assert_eq!(commands.apply("改行", Some("ja")), "改行");
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 
+use crate::domain::transcript::{TranscriptSegment, TranscriptWord};
+
 /// 音声入力結果を保持するスタック
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stack {
@@ -10,6 +12,15 @@ pub struct Stack {
     pub text: String,
     /// 作成日時
     pub created_at: SystemTime,
+    /// 転写時にプロバイダーが検出した言語
+    #[serde(default)]
+    pub language: Option<String>,
+    /// セグメント単位のタイムスタンプ（プロバイダーが返した場合のみ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<TranscriptSegment>,
+    /// 単語単位のタイムスタンプ（プロバイダーが返した場合のみ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<TranscriptWord>,
 }
 
 /// CLI表示用のスタック情報
@@ -35,9 +46,29 @@ impl Stack {
             id,
             text,
             created_at: SystemTime::now(),
+            language: None,
+            segments: Vec::new(),
+            words: Vec::new(),
         }
     }
 
+    /// 検出言語を設定します。
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// セグメント・単語単位のタイムスタンプを設定します。
+    pub fn with_timestamps(
+        mut self,
+        segments: Vec<TranscriptSegment>,
+        words: Vec<TranscriptWord>,
+    ) -> Self {
+        self.segments = segments;
+        self.words = words;
+        self
+    }
+
     /// StackをCLI表示用のStackInfoに変換します。
     ///
     /// テキストは最大30文字に切り詰められ、それ以上の場合は"..."が追加されます。
@@ -62,6 +93,33 @@ mod tests {
         assert_eq!(stack.text, "Hello, world!");
     }
 
+    #[test]
+    fn test_stack_language() {
+        let stack = Stack::new(1, "Fix typo".to_string()).with_language(Some("en".to_string()));
+        assert_eq!(stack.language.as_deref(), Some("en"));
+
+        // 言語情報を持たない旧形式も読み込める
+        let json = serde_json::to_value(Stack::new(2, "テスト".to_string())).unwrap();
+        let mut object = json.as_object().unwrap().clone();
+        object.remove("language");
+        let stack: Stack = serde_json::from_value(object.into()).unwrap();
+        assert_eq!(stack.language, None);
+    }
+
+    #[test]
+    fn test_stack_timestamps() {
+        // タイムスタンプがなければ出力しない
+        let json = serde_json::to_value(Stack::new(1, "テスト".to_string())).unwrap();
+        assert!(json.get("segments").is_none());
+
+        let stack = Stack::new(1, "テスト".to_string())
+            .with_timestamps(vec![TranscriptSegment::new(0.0, 0.8, "テスト")], Vec::new());
+        let json = serde_json::to_string(&stack).unwrap();
+        let restored: Stack = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.segments, stack.segments);
+        assert!(restored.words.is_empty());
+    }
+
     #[test]
     fn test_stack_to_info_preview() {
         let stack = Stack::new(
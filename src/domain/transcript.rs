@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+/// 転写結果
+///
+/// テキストに加え、プロバイダーが返した場合はセグメント・単語単位のタイムスタンプを保持します。
+/// 時刻はすべて音声先頭からの秒数です。
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Transcript {
+    /// 転写テキスト
+    pub text: String,
+    /// プロバイダーが検出した言語（返された場合のみ）
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 転写したプロバイダー（フォールバックチェーン経由の場合に記録）
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// フォールバック先のプロバイダーで転写した（主のプロバイダーの結果としてキャッシュしない）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fallback: bool,
+    /// セグメント（文・フレーズ）単位の結果
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<TranscriptSegment>,
+    /// 単語単位の結果
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<TranscriptWord>,
+    /// トークン単位の対数確率（プロバイダーが返した場合のみ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logprobs: Vec<f64>,
+    /// 信頼度（0〜1、対数確率から推定できた場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// 信頼度がしきい値を下回った（無音での幻聴などの可能性がある）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub low_confidence: bool,
+}
+
+/// セグメント単位の転写結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    /// 開始時刻（秒）
+    pub start: f64,
+    /// 終了時刻（秒）
+    pub end: f64,
+    pub text: String,
+    /// トークンの平均対数確率（低いほど自信がない）
+    #[serde(default)]
+    pub avg_logprob: Option<f64>,
+    /// 無音である確率
+    #[serde(default)]
+    pub no_speech_prob: Option<f64>,
+}
+
+/// 単語単位の転写結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub word: String,
+    /// 開始時刻（秒）
+    pub start: f64,
+    /// 終了時刻（秒）
+    pub end: f64,
+}
+
+impl Transcript {
+    /// 言語情報なしの転写結果を作成
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 転写したプロバイダーを記録
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    /// タイムスタンプ情報を持つか
+    pub fn has_timestamps(&self) -> bool {
+        !self.segments.is_empty() || !self.words.is_empty()
+    }
+
+    /// 対数確率から信頼度（0〜1）を推定（推定できる情報がなければ `None`）
+    ///
+    /// セグメントの `avg_logprob` があれば、各セグメントの確率に「無音でない確率」を掛けて
+    /// 長さで重み付け平均する。なければトークンの対数確率の平均から求める。
+    pub fn estimate_confidence(&self) -> Option<f64> {
+        let scored: Vec<(f64, f64)> = self
+            .segments
+            .iter()
+            .filter_map(|s| {
+                let speech = 1.0 - s.no_speech_prob.unwrap_or(0.0).clamp(0.0, 1.0);
+                s.avg_logprob
+                    .map(|logprob| (s.duration().max(0.01), logprob.exp().min(1.0) * speech))
+            })
+            .collect();
+        if !scored.is_empty() {
+            let total: f64 = scored.iter().map(|(weight, _)| weight).sum();
+            return Some(scored.iter().map(|(w, score)| w * score).sum::<f64>() / total);
+        }
+        if !self.logprobs.is_empty() {
+            let mean = self.logprobs.iter().sum::<f64>() / self.logprobs.len() as f64;
+            return Some(mean.exp().min(1.0));
+        }
+        None
+    }
+
+    /// 音声の長さの目安（最後のセグメントまたは単語の終了時刻）
+    pub fn end_time(&self) -> Option<f64> {
+        self.segments
+            .iter()
+            .map(|s| s.end)
+            .chain(self.words.iter().map(|w| w.end))
+            .reduce(f64::max)
+    }
+}
+
+impl TranscriptSegment {
+    /// タイムスタンプとテキストからセグメントを作成
+    pub fn new(start: f64, end: f64, text: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            text: text.into(),
+            avg_logprob: None,
+            no_speech_prob: None,
+        }
+    }
+
+    /// セグメントの長さ（秒）
+    pub fn duration(&self) -> f64 {
+        (self.end - self.start).max(0.0)
+    }
+}
+
+/// 行・セグメント単位のテキストを連結
+///
+/// 英語などは空白で区切り、日本語のように空白を使わない文字同士はそのまま連結する。
+pub fn join_segments<'a>(segments: impl Iterator<Item = &'a str>) -> String {
+    let mut text = String::new();
+    for segment in segments.map(str::trim).filter(|s| !s.is_empty()) {
+        let needs_space = text.chars().last().is_some_and(|c| c.is_ascii())
+            || segment.chars().next().is_some_and(|c| c.is_ascii());
+        if !text.is_empty() && needs_space {
+            text.push(' ');
+        }
+        text.push_str(segment);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_timestamps() {
+        let mut transcript = Transcript::new("こんにちは。今日は晴れです。");
+        assert!(!transcript.has_timestamps());
+        assert_eq!(transcript.end_time(), None);
+
+        transcript.segments = vec![
+            TranscriptSegment::new(0.0, 1.2, "こんにちは。"),
+            TranscriptSegment::new(1.2, 3.5, "今日は晴れです。"),
+        ];
+        transcript.words = vec![TranscriptWord {
+            word: "こんにちは".to_string(),
+            start: 0.1,
+            end: 0.9,
+        }];
+        assert!(transcript.has_timestamps());
+        assert_eq!(transcript.end_time(), Some(3.5));
+        assert!((transcript.segments[1].duration() - 2.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transcript_serialization() {
+        // タイムスタンプがない場合は空配列を出力しない
+        let json = serde_json::to_value(Transcript::new("hello")).unwrap();
+        assert!(json.get("segments").is_none());
+        assert!(json.get("words").is_none());
+
+        let transcript = Transcript {
+            segments: vec![TranscriptSegment::new(0.0, 1.0, "hello")],
+            ..Transcript::new("hello")
+        };
+        let back: Transcript =
+            serde_json::from_str(&serde_json::to_string(&transcript).unwrap()).unwrap();
+        assert_eq!(back, transcript);
+    }
+
+    #[test]
+    fn test_estimate_confidence() {
+        assert_eq!(Transcript::new("hello").estimate_confidence(), None);
+
+        // 無音の可能性が高いセグメントは信頼度を下げる
+        let segment = |start: f64, end: f64, logprob: f64, no_speech: f64| TranscriptSegment {
+            avg_logprob: Some(logprob),
+            no_speech_prob: Some(no_speech),
+            ..TranscriptSegment::new(start, end, "text")
+        };
+        let transcript = Transcript {
+            segments: vec![segment(0.0, 3.0, -0.1, 0.0), segment(3.0, 4.0, -0.1, 1.0)],
+            ..Transcript::new("text")
+        };
+        let expected = (-0.1f64).exp() * 3.0 / 4.0;
+        assert!((transcript.estimate_confidence().unwrap() - expected).abs() < 1e-9);
+
+        let transcript = Transcript {
+            logprobs: vec![-0.5, -1.5],
+            ..Transcript::new("text")
+        };
+        assert!((transcript.estimate_confidence().unwrap() - (-1.0f64).exp()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_join_segments() {
+        assert_eq!(
+            join_segments([" こんにちは。", "", " 今日は晴れです。 "].into_iter()),
+            "こんにちは。今日は晴れです。"
+        );
+        assert_eq!(
+            join_segments([" Hello there.", " How are you?"].into_iter()),
+            "Hello there. How are you?"
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// 既定の料金表（USD / 音声 1 分）
+const DEFAULT_PRICES: &[(&str, f64)] = &[
+    ("whisper-1", 0.006),
+    ("gpt-4o-transcribe", 0.006),
+    ("gpt-4o-mini-transcribe", 0.003),
+];
+
+/// 転写リクエスト 1 回分の利用記録
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// リクエストした時刻（UNIX 秒）
+    pub timestamp: u64,
+    /// プロバイダー（`openai` / `whisper-cpp` / `command`）
+    pub provider: String,
+    /// モデル
+    pub model: String,
+    /// 音声の長さ（秒）
+    pub audio_secs: f64,
+    /// 送信したバイト数
+    pub bytes: u64,
+    /// 応答までの時間（ミリ秒）
+    pub latency_ms: u64,
+    /// 転写に成功したか（失敗したリクエストは料金に含めない）
+    pub success: bool,
+    /// 振り分けルール名（振り分け方針で選ばれた場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+}
+
+impl UsageRecord {
+    /// 記録した時刻（ローカル時刻）
+    fn local_time(&self) -> Option<DateTime<Local>> {
+        DateTime::from_timestamp(self.timestamp as i64, 0).map(|t| t.with_timezone(&Local))
+    }
+}
+
+/// 料金表（モデル名またはプロバイダー名 → USD / 音声 1 分）
+///
+/// 載っていないモデル（ローカルコマンド・セルフホストサーバーなど）は無料として扱う。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PriceTable(pub BTreeMap<String, f64>);
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self(
+            DEFAULT_PRICES
+                .iter()
+                .map(|(model, price)| (model.to_string(), *price))
+                .collect(),
+        )
+    }
+}
+
+impl PriceTable {
+    /// 既定の料金表に設定値を上書き
+    pub fn with_overrides(mut self, overrides: &BTreeMap<String, f64>) -> Self {
+        self.0
+            .extend(overrides.iter().map(|(k, v)| (k.clone(), *v)));
+        self
+    }
+
+    /// 1 分あたりの料金（モデル名を優先し、なければプロバイダー名で引く）
+    pub fn per_minute(&self, provider: &str, model: &str) -> f64 {
+        self.0
+            .get(model)
+            .or_else(|| self.0.get(provider))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// 記録の推定料金（USD）
+    pub fn cost(&self, record: &UsageRecord) -> f64 {
+        if !record.success {
+            return 0.0;
+        }
+        self.per_minute(&record.provider, &record.model) * record.audio_secs / 60.0
+    }
+
+    /// `today` を含む月の推定料金の合計（USD）
+    pub fn month_cost(&self, records: &[UsageRecord], today: NaiveDate) -> f64 {
+        records
+            .iter()
+            .filter(|r| {
+                r.local_time()
+                    .is_some_and(|t| (t.year(), t.month()) == (today.year(), today.month()))
+            })
+            .map(|r| self.cost(r))
+            .sum()
+    }
+}
+
+/// 月間予算
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageBudget {
+    /// 超えたら警告する金額（USD）
+    pub soft_usd: Option<f64>,
+    /// 超えたら有料プロバイダーでの転写を止める金額（USD）
+    pub hard_usd: Option<f64>,
+}
+
+/// 集計の単位（期間・プロバイダー・モデル・振り分けルール）
+type UsageKey = (String, String, String, Option<String>);
+
+/// 期間・プロバイダー・モデル・振り分けルールごとの集計
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRow {
+    /// 期間（日別は `2025-01-31`、月別は `2025-01`）
+    pub period: String,
+    pub provider: String,
+    pub model: String,
+    /// 振り分けルール名
+    #[serde(default)]
+    pub route: Option<String>,
+    /// リクエスト数（失敗を含む）
+    pub requests: u32,
+    /// 失敗したリクエスト数
+    pub failed: u32,
+    /// 音声の長さの合計（秒）
+    pub audio_secs: f64,
+    /// 送信したバイト数の合計
+    pub bytes: u64,
+    /// 平均応答時間（ミリ秒）
+    pub avg_latency_ms: u64,
+    /// 推定料金（USD）
+    pub cost_usd: f64,
+}
+
+/// 利用状況のレポート（`voice_input usage` の応答）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// 直近の日別集計（新しい順）
+    pub daily: Vec<UsageRow>,
+    /// 月別集計（新しい順）
+    pub monthly: Vec<UsageRow>,
+    /// 今月の推定料金（USD）
+    pub month_cost_usd: f64,
+    /// 月間予算
+    pub budget: UsageBudget,
+}
+
+impl UsageReport {
+    /// 記録を集計してレポートを作成（日別は `today` から `days` 日分）
+    pub fn build(
+        records: &[UsageRecord],
+        prices: &PriceTable,
+        budget: UsageBudget,
+        today: NaiveDate,
+        days: u32,
+    ) -> Self {
+        // 範囲が暦の始まりを越える場合はすべての記録を対象とする
+        let since = today
+            .checked_sub_days(chrono::Days::new(days.saturating_sub(1) as u64))
+            .unwrap_or(NaiveDate::MIN);
+        let mut daily: BTreeMap<UsageKey, Vec<&UsageRecord>> = BTreeMap::new();
+        let mut monthly: BTreeMap<UsageKey, Vec<&UsageRecord>> = BTreeMap::new();
+
+        for record in records {
+            let Some(time) = record.local_time() else {
+                continue;
+            };
+            let key = |period: String| {
+                (
+                    period,
+                    record.provider.clone(),
+                    record.model.clone(),
+                    record.route.clone(),
+                )
+            };
+            if days > 0 && time.date_naive() >= since {
+                daily
+                    .entry(key(time.format("%Y-%m-%d").to_string()))
+                    .or_default()
+                    .push(record);
+            }
+            monthly
+                .entry(key(time.format("%Y-%m").to_string()))
+                .or_default()
+                .push(record);
+        }
+
+        let rows = |groups: BTreeMap<UsageKey, Vec<&UsageRecord>>| {
+            groups
+                .into_iter()
+                .rev()
+                .map(|((period, provider, model, route), records)| {
+                    let requests = records.len() as u32;
+                    UsageRow {
+                        period,
+                        provider,
+                        model,
+                        route,
+                        requests,
+                        failed: records.iter().filter(|r| !r.success).count() as u32,
+                        audio_secs: records.iter().map(|r| r.audio_secs).sum(),
+                        bytes: records.iter().map(|r| r.bytes).sum(),
+                        avg_latency_ms: records.iter().map(|r| r.latency_ms).sum::<u64>()
+                            / requests.max(1) as u64,
+                        cost_usd: records.iter().map(|r| prices.cost(r)).sum(),
+                    }
+                })
+                .collect()
+        };
+
+        Self {
+            daily: rows(daily),
+            monthly: rows(monthly),
+            month_cost_usd: prices.month_cost(records, today),
+            budget,
+        }
+    }
+}
+
+impl fmt::Display for UsageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn table(f: &mut fmt::Formatter<'_>, title: &str, rows: &[UsageRow]) -> fmt::Result {
+            writeln!(f, "─ {} ──────", title)?;
+            if rows.is_empty() {
+                return writeln!(f, "  (no transcriptions)");
+            }
+            for row in rows {
+                writeln!(
+                    f,
+                    "  {:<10}  {:<28}  {:>4} req{}  {:>7.1} min  {:>7.1} MB  {:>5} ms  ${:.3}",
+                    row.period,
+                    match &row.route {
+                        Some(route) => format!("{}:{} [{}]", row.provider, row.model, route),
+                        None => format!("{}:{}", row.provider, row.model),
+                    },
+                    row.requests,
+                    if row.failed > 0 {
+                        format!(" ({} failed)", row.failed)
+                    } else {
+                        String::new()
+                    },
+                    row.audio_secs / 60.0,
+                    row.bytes as f64 / (1024.0 * 1024.0),
+                    row.avg_latency_ms,
+                    row.cost_usd
+                )?;
+            }
+            Ok(())
+        }
+
+        table(f, "Daily usage", &self.daily)?;
+        table(f, "Monthly usage", &self.monthly)?;
+        write!(f, "This month: ${:.2}", self.month_cost_usd)?;
+        if let Some(soft) = self.budget.soft_usd {
+            write!(f, "  (warn at ${:.2})", soft)?;
+        }
+        if let Some(hard) = self.budget.hard_usd {
+            write!(f, "  (limit ${:.2})", hard)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveTime, TimeZone};
+
+    fn record(date: NaiveDate, model: &str, audio_secs: f64, success: bool) -> UsageRecord {
+        let time = Local
+            .from_local_datetime(&date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap()))
+            .unwrap();
+        UsageRecord {
+            timestamp: time.timestamp() as u64,
+            provider: "openai".to_string(),
+            model: model.to_string(),
+            audio_secs,
+            bytes: 1024,
+            latency_ms: 800,
+            success,
+            route: None,
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_price_lookup() {
+        let prices = PriceTable::default().with_overrides(&BTreeMap::from([
+            ("command".to_string(), 0.0),
+            ("whisper-1".to_string(), 0.01),
+        ]));
+        assert_eq!(prices.per_minute("openai", "whisper-1"), 0.01);
+        assert_eq!(prices.per_minute("openai", "gpt-4o-mini-transcribe"), 0.003);
+        assert_eq!(prices.per_minute("command", "ggml-base.bin"), 0.0);
+        assert_eq!(prices.per_minute("openai", "unknown"), 0.0);
+
+        // 失敗したリクエストは料金に含めない
+        assert_eq!(
+            prices.cost(&record(date(2025, 1, 1), "whisper-1", 120.0, true)),
+            0.02
+        );
+        assert_eq!(
+            prices.cost(&record(date(2025, 1, 1), "whisper-1", 120.0, false)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_build_report() {
+        let records = vec![
+            record(date(2024, 12, 31), "whisper-1", 600.0, true),
+            record(date(2025, 1, 30), "whisper-1", 60.0, true),
+            record(date(2025, 1, 31), "whisper-1", 120.0, true),
+            record(date(2025, 1, 31), "whisper-1", 30.0, false),
+            record(date(2025, 1, 31), "gpt-4o-mini-transcribe", 60.0, true),
+        ];
+        let budget = UsageBudget {
+            soft_usd: Some(1.0),
+            hard_usd: None,
+        };
+        let report = UsageReport::build(
+            &records,
+            &PriceTable::default(),
+            budget,
+            date(2025, 1, 31),
+            1,
+        );
+
+        // 日別は当日分のみ、新しい順
+        assert_eq!(report.daily.len(), 2);
+        let whisper = report
+            .daily
+            .iter()
+            .find(|r| r.model == "whisper-1")
+            .unwrap();
+        assert_eq!(whisper.period, "2025-01-31");
+        assert_eq!((whisper.requests, whisper.failed), (2, 1));
+        assert_eq!(whisper.audio_secs, 150.0);
+        assert!((whisper.cost_usd - 0.012).abs() < 1e-9);
+
+        assert_eq!(report.monthly.len(), 3);
+        assert_eq!(report.monthly[0].period, "2025-01");
+        assert_eq!(report.monthly[2].period, "2024-12");
+        assert!((report.month_cost_usd - (0.006 + 0.012 + 0.003)).abs() < 1e-9);
+        assert_eq!(report.budget, budget);
+        assert!(report.to_string().contains("This month: $0.02"));
+
+        // 振り分けルールごとに分けて集計する
+        let routed = UsageRecord {
+            route: Some("short".to_string()),
+            ..records[2].clone()
+        };
+        let report = UsageReport::build(
+            &[records[2].clone(), routed],
+            &PriceTable::default(),
+            budget,
+            date(2025, 1, 31),
+            1,
+        );
+        assert_eq!(report.daily.len(), 2);
+        assert!(report.to_string().contains("openai:whisper-1 [short]"));
+
+        // 非常に長い期間を指定してもすべての記録を集計する
+        let report = UsageReport::build(
+            &records,
+            &PriceTable::default(),
+            budget,
+            date(2025, 1, 31),
+            u32::MAX,
+        );
+        assert_eq!(report.daily.len(), 4);
+    }
+}
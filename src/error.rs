@@ -24,6 +24,9 @@ pub enum VoiceInputError {
     #[error("Audio backend error: {0}")]
     AudioBackendError(String),
 
+    #[error("Audio file error: {0}")]
+    AudioFileError(String),
+
     // ========================================
     // 転写関連エラー
     // ========================================
@@ -36,6 +39,20 @@ pub enum VoiceInputError {
     #[error("OpenAI configuration error: {0}")]
     OpenAiConfigError(String),
 
+    #[error("API returned status {status}: {message}")]
+    ApiStatus {
+        status: u16,
+        message: String,
+        /// `Retry-After` で指定された待ち時間
+        retry_after: Option<std::time::Duration>,
+    },
+
+    #[error("Request timed out after {0:?}")]
+    RequestTimeout(std::time::Duration),
+
+    #[error("Monthly transcription budget exceeded: ${spent:.2} of ${limit:.2}")]
+    BudgetExceeded { spent: f64, limit: f64 },
+
     // ========================================
     // スタック管理エラー (StackServiceError統合)
     // ========================================
@@ -108,6 +125,9 @@ pub enum VoiceInputError {
     #[error("Configuration missing value: {0}")]
     ConfigMissingValue(String),
 
+    #[error("Unknown profile: {0}")]
+    UnknownProfile(String),
+
     // ========================================
     // ファイルI/O関連エラー
     // ========================================
@@ -135,6 +155,9 @@ pub enum VoiceInputError {
     #[error("System error: {0}")]
     SystemError(String),
 
+    #[error("Queued transcription not found: {0}")]
+    QueuedJobNotFound(String),
+
     #[error("Permission denied: {reason}")]
     PermissionDenied { reason: String },
 
@@ -210,6 +233,33 @@ impl From<crate::shortcut::ShortcutError> for VoiceInputError {
     }
 }
 
+/// AudioError からの変換
+impl From<crate::infrastructure::audio::AudioError> for VoiceInputError {
+    fn from(error: crate::infrastructure::audio::AudioError) -> Self {
+        VoiceInputError::AudioFileError(error.to_string())
+    }
+}
+
+/// OpenAiError からの変換
+impl From<crate::infrastructure::external::openai::OpenAiError> for VoiceInputError {
+    fn from(error: crate::infrastructure::external::openai::OpenAiError) -> Self {
+        use crate::infrastructure::external::openai::OpenAiError;
+        match error {
+            OpenAiError::Request(msg) => VoiceInputError::NetworkError(msg),
+            OpenAiError::Status {
+                status,
+                body,
+                retry_after,
+            } => VoiceInputError::ApiStatus {
+                status,
+                message: body,
+                retry_after,
+            },
+            OpenAiError::InvalidResponse(msg) => VoiceInputError::TranscriptionFailed(msg),
+        }
+    }
+}
+
 /// SubprocessInputError からの変換
 impl From<SubprocessInputError> for VoiceInputError {
     fn from(error: SubprocessInputError) -> Self {
@@ -256,14 +306,50 @@ impl From<VoiceInputError> for String {
 
 impl VoiceInputError {
     /// エラーが再試行可能かどうかを判定
+    ///
+    /// API のステータスエラーは 408 / 429 / 5xx のみ再試行対象とする。
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            VoiceInputError::NetworkError(_)
-                | VoiceInputError::OpenAiApiError(_)
-                | VoiceInputError::IpcConnectionFailed(_)
-                | VoiceInputError::Reqwest(_)
-        )
+        match self {
+            VoiceInputError::ApiStatus { status, .. } => {
+                matches!(status, 408 | 429) || *status >= 500
+            }
+            _ => matches!(
+                self,
+                VoiceInputError::NetworkError(_)
+                    | VoiceInputError::OpenAiApiError(_)
+                    | VoiceInputError::RequestTimeout(_)
+                    | VoiceInputError::IpcConnectionFailed(_)
+                    | VoiceInputError::Reqwest(_)
+            ),
+        }
+    }
+
+    /// 別の転写プロバイダーに切り替えれば解決し得るエラーかどうかを判定
+    ///
+    /// 再試行可能なエラー（障害・レート制限）に加え、認証・課金・設定の問題や
+    /// 月間予算の超過、ローカルコマンドの失敗など、プロバイダー側に起因するエラーを対象とする。
+    /// 音声そのものの問題（400 など）はどのプロバイダーでも失敗するため対象外。
+    pub fn is_provider_failure(&self) -> bool {
+        self.is_retryable()
+            || matches!(
+                self,
+                VoiceInputError::ApiStatus {
+                    status: 401..=404,
+                    ..
+                } | VoiceInputError::TranscriptionFailed(_)
+                    | VoiceInputError::BudgetExceeded { .. }
+                    | VoiceInputError::OpenAiConfigError(_)
+                    | VoiceInputError::ConfigMissingValue(_)
+                    | VoiceInputError::ConfigInitError(_)
+            )
+    }
+
+    /// サーバーから指定された再試行までの待ち時間
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            VoiceInputError::ApiStatus { retry_after, .. } => *retry_after,
+            _ => None,
+        }
     }
 
     /// エラーがユーザーアクションで解決可能かどうかを判定
@@ -272,6 +358,7 @@ impl VoiceInputError {
             self,
             VoiceInputError::ConfigMissingValue(_)
                 | VoiceInputError::OpenAiConfigError(_)
+                | VoiceInputError::BudgetExceeded { .. }
                 | VoiceInputError::PermissionDenied { .. }
                 | VoiceInputError::TextInputHelperNotFound(_)
         )
@@ -286,6 +373,8 @@ impl VoiceInputError {
 
             VoiceInputError::NetworkError(_)
             | VoiceInputError::OpenAiApiError(_)
+            | VoiceInputError::ApiStatus { .. }
+            | VoiceInputError::RequestTimeout(_)
             | VoiceInputError::IpcConnectionFailed(_) => ErrorSeverity::Warning,
 
             VoiceInputError::UiChannelClosed
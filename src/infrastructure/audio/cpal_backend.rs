@@ -1,4 +1,7 @@
-use super::AudioBackend;
+use super::device::{
+    DeviceSelection, INPUT_DEVICE_PRIORITY_ENV, parse_priority_list, pick_input_device,
+};
+use super::{AudioBackend, PcmChunk, wav::WavAudio};
 use cpal::{
     Device, SampleFormat, Stream, StreamConfig,
     traits::{DeviceTrait, HostTrait, StreamTrait},
@@ -16,6 +19,13 @@ use std::{
 #[derive(Debug, Clone)]
 pub struct AudioData(pub Vec<u8>);
 
+impl AudioData {
+    /// WAVヘッダーを解析し、フォーマット情報付きのデータとして取得
+    pub fn parse_wav(&self) -> Result<WavAudio, AudioError> {
+        WavAudio::parse(&self.0)
+    }
+}
+
 /// 録音状態（メモリモード専用）
 struct MemoryRecordingState {
     buffer: Arc<Mutex<Vec<i16>>>,
@@ -24,9 +34,31 @@ struct MemoryRecordingState {
 }
 
 /// Audio processing errors
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum AudioError {
     DataTooLarge(usize),
+    /// 先頭が `RIFF` / `WAVE` ではない
+    NotWav,
+    /// ヘッダーやチャンクの宣言長に対してデータが足りない
+    Truncated {
+        expected: usize,
+        actual: usize,
+    },
+    /// 必須チャンク（`fmt ` / `data`）が見つからない
+    MissingChunk(&'static str),
+    /// fmt チャンクの値が不正（チャンネル数 0、block_align 不一致など）
+    InvalidFormat(String),
+    /// 未対応のエンコーディング（format tag, bits per sample）
+    UnsupportedFormat {
+        format_tag: u16,
+        bits_per_sample: u16,
+    },
+    /// 音声ファイルの読み込みに失敗
+    Io(String),
+    /// 圧縮音声のデコードに失敗
+    Decode(String),
+    /// デコーダーのないコーデック（Opus など）
+    UnsupportedCodec(String),
 }
 
 impl fmt::Display for AudioError {
@@ -35,6 +67,29 @@ impl fmt::Display for AudioError {
             AudioError::DataTooLarge(size) => {
                 write!(f, "PCM data too large: {} bytes exceeds u32 max", size)
             }
+            AudioError::NotWav => write!(f, "not a RIFF/WAVE file"),
+            AudioError::Truncated { expected, actual } => {
+                write!(
+                    f,
+                    "WAV data truncated: expected {} bytes, got {}",
+                    expected, actual
+                )
+            }
+            AudioError::MissingChunk(id) => write!(f, "WAV chunk '{}' not found", id),
+            AudioError::InvalidFormat(msg) => write!(f, "invalid WAV format: {}", msg),
+            AudioError::UnsupportedFormat {
+                format_tag,
+                bits_per_sample,
+            } => write!(
+                f,
+                "unsupported WAV encoding: format tag 0x{:04X}, {} bits",
+                format_tag, bits_per_sample
+            ),
+            AudioError::Io(msg) => write!(f, "audio file I/O error: {}", msg),
+            AudioError::Decode(msg) => write!(f, "audio decode error: {}", msg),
+            AudioError::UnsupportedCodec(codec) => {
+                write!(f, "unsupported audio codec: {}", codec)
+            }
         }
     }
 }
@@ -87,33 +142,33 @@ impl Default for CpalAudioBackend {
 
 /// `INPUT_DEVICE_PRIORITY` 環境変数を解釈し、優先順位の高い入力デバイスを選択します。
 fn select_input_device(host: &cpal::Host) -> Option<Device> {
-    use std::env;
-
     // 1) 優先リスト取得 (カンマ区切り)
-    let priorities: Vec<String> = env::var("INPUT_DEVICE_PRIORITY")
-        .ok()?
-        .split(',')
-        .map(|s| s.trim().to_owned())
-        .filter(|s| !s.is_empty())
-        .collect();
+    let priorities = std::env::var(INPUT_DEVICE_PRIORITY_ENV)
+        .ok()
+        .map(|v| parse_priority_list(&v));
 
     // 2) 利用可能なデバイスを列挙
     let available: Vec<Device> = host.input_devices().ok()?.collect();
+    let names: Vec<String> = available
+        .iter()
+        .map(|d| d.name().unwrap_or_default())
+        .collect();
 
-    // 3) 優先度順に一致デバイスを探す
-    for want in &priorities {
-        if let Some(dev) = available
-            .iter()
-            .find(|d| d.name().map(|n| n == *want).unwrap_or(false))
-        {
+    // 3) 優先度順に一致デバイスを探し、なければデフォルト
+    match pick_input_device(priorities.as_deref(), &names, None) {
+        DeviceSelection::Preferred(want) => {
             println!("🎙️  Using preferred device: {}", want);
-            return Some(dev.clone());
+            names
+                .iter()
+                .position(|n| *n == want)
+                .map(|i| available[i].clone())
         }
+        DeviceSelection::DefaultFallback(_) => {
+            println!("⚠️  No preferred device found, falling back to default input device");
+            host.default_input_device()
+        }
+        DeviceSelection::NotConfigured => None,
     }
-
-    // 4) 見つからなければデフォルト
-    println!("⚠️  No preferred device found, falling back to default input device");
-    host.default_input_device()
 }
 
 // =============== WAVヘッダー生成機能 ================================
@@ -360,6 +415,22 @@ impl AudioBackend for CpalAudioBackend {
     fn is_recording(&self) -> bool {
         self.recording.load(Ordering::SeqCst)
     }
+
+    /// 録音バッファに蓄積されたサンプルを取り出します（連続音声入力用）。
+    fn drain_samples(&self) -> Option<PcmChunk> {
+        if !self.is_recording() {
+            return None;
+        }
+
+        let state = self.recording_state.lock().unwrap();
+        let state = state.as_ref()?;
+        let samples = std::mem::take(&mut *state.buffer.lock().unwrap());
+        Some(PcmChunk {
+            samples,
+            sample_rate: state.sample_rate,
+            channels: state.channels,
+        })
+    }
 }
 
 // #[cfg(test)]
@@ -802,6 +873,45 @@ mod tests {
         assert!(backend.recording_state.lock().unwrap().is_none());
     }
 
+    #[test]
+    fn test_audio_data_parse_wav() {
+        let wav = CpalAudioBackend::combine_wav_data(&[0i16, 100, -100], 16000, 1).unwrap();
+        let parsed = AudioData(wav).parse_wav().unwrap();
+        assert_eq!(parsed.format.sample_rate, 16000);
+        assert_eq!(parsed.samples_i16(), vec![0, 100, -100]);
+
+        assert_eq!(
+            AudioData(b"not a wav file".to_vec()).parse_wav(),
+            Err(AudioError::NotWav)
+        );
+    }
+
+    #[test]
+    fn test_drain_samples() {
+        let backend = CpalAudioBackend::default();
+
+        // 録音していなければ何も返さない
+        assert!(backend.drain_samples().is_none());
+
+        let buffer = Arc::new(Mutex::new(vec![1i16, 2, 3]));
+        *backend.recording_state.lock().unwrap() = Some(MemoryRecordingState {
+            buffer: buffer.clone(),
+            sample_rate: 16000,
+            channels: 1,
+        });
+        backend.recording.store(true, Ordering::SeqCst);
+
+        let chunk = backend.drain_samples().unwrap();
+        assert_eq!(chunk.samples, vec![1, 2, 3]);
+        assert_eq!(chunk.sample_rate, 16000);
+        assert_eq!(chunk.channels, 1);
+
+        // 取り出した分はバッファから消え、停止時のWAVには含まれない
+        buffer.lock().unwrap().push(4);
+        let wav_data = backend.stop_recording().unwrap().0;
+        assert_eq!(wav_data.len(), 44 + 2);
+    }
+
     #[test]
     fn test_memory_usage_30_seconds() {
         // 30秒録音のメモリ使用量テスト
@@ -0,0 +1,400 @@
+//! 音声ファイルのデコード（ファイル文字起こし用）
+//!
+//! MP3 / M4A(AAC) / Ogg Vorbis / FLAC などを pure Rust の symphonia で PCM に変換します。
+//! WAV は `WavAudio` で検証付きで読み込みます。
+//! Opus は symphonia にデコーダーがないため、`opus` フィーチャー有効時のみ libopus で
+//! デコードします（`super::opus`）。無効時は有効化・変換方法を添えた
+//! `AudioError::UnsupportedCodec` を返します。
+
+use super::cpal_backend::{AudioData, AudioError, CpalAudioBackend};
+use super::resample::resample;
+use super::wav::WavAudio;
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, CODEC_TYPE_OPUS, CodecType, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, Packet};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// 文字起こしに渡す音声のサンプリングレート
+pub const TRANSCRIPTION_SAMPLE_RATE: u32 = 16_000;
+
+/// 音声ファイルのコーデック・長さ情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioFileInfo {
+    /// コーデック名（例: mp3, aac, flac, vorbis, pcm_s16le）
+    pub codec: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// 再生時間（コンテナに記録がない場合は `None`）
+    pub duration: Option<Duration>,
+}
+
+impl fmt::Display for AudioFileInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {} Hz, {} ch",
+            self.codec, self.sample_rate, self.channels
+        )?;
+        if let Some(duration) = self.duration {
+            let secs = duration.as_secs_f64();
+            write!(f, ", {}:{:04.1}", (secs / 60.0) as u64, secs % 60.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// デコード済み音声（モノラル f32）
+#[derive(Debug, Clone)]
+pub struct DecodedAudio {
+    pub info: AudioFileInfo,
+    /// 全チャンネルを平均したモノラルサンプル
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl DecodedAudio {
+    /// デコード結果の実際の長さ
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.samples.len() as f64 / self.sample_rate.max(1) as f64)
+    }
+
+    /// 指定レートにリサンプルし、16bit モノラル WAV の `AudioData` に変換
+    pub fn to_wav(&self, target_rate: u32) -> Result<AudioData, AudioError> {
+        let resampled = resample(&self.samples, self.sample_rate, target_rate);
+        let wav = CpalAudioBackend::combine_wav_data(&resampled, target_rate, 1)?;
+        Ok(AudioData(wav))
+    }
+}
+
+/// ヘッダーのみを読み、コーデックと長さを取得
+pub fn probe_file(path: &Path) -> Result<AudioFileInfo, AudioError> {
+    if is_wav(path) {
+        return Ok(decode_wav(path)?.info);
+    }
+
+    let format = open_format(path)?;
+    let track = default_track(format.as_ref())?;
+    let params = &track.codec_params;
+    if !is_decodable(params.codec) {
+        return Err(unsupported_codec(params.codec));
+    }
+    Ok(AudioFileInfo {
+        codec: codec_name(params.codec),
+        sample_rate: params.sample_rate.unwrap_or(0),
+        channels: params.channels.map(|c| c.count() as u16).unwrap_or(0),
+        duration: params
+            .n_frames
+            .zip(params.sample_rate)
+            .map(|(frames, rate)| Duration::from_secs_f64(frames as f64 / rate as f64)),
+    })
+}
+
+/// 音声ファイル全体をデコード
+pub fn decode_file(path: &Path) -> Result<DecodedAudio, AudioError> {
+    if is_wav(path) {
+        return decode_wav(path);
+    }
+
+    let mut format = open_format(path)?;
+    let track = default_track(format.as_ref())?;
+    let track_id = track.id;
+    let params = track.codec_params.clone();
+    let codec = codec_name(params.codec);
+
+    #[cfg(feature = "opus")]
+    if params.codec == CODEC_TYPE_OPUS {
+        return decode_opus(format.as_mut(), track_id, &params, codec);
+    }
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&params, &DecoderOptions::default())
+        .map_err(|_| unsupported_codec(params.codec))?;
+
+    let mut samples = Vec::new();
+    let mut sample_rate = params.sample_rate.unwrap_or(0);
+    let mut channels = params.channels.map(|c| c.count() as u16).unwrap_or(0);
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+
+    while let Some(packet) = next_packet(format.as_mut(), track_id)? {
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // 壊れたフレームは読み飛ばす
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(AudioError::Decode(e.to_string())),
+        };
+
+        let spec = *decoded.spec();
+        sample_rate = spec.rate;
+        channels = spec.channels.count() as u16;
+
+        let needs_alloc = buffer
+            .as_ref()
+            .is_none_or(|b| b.capacity() < decoded.capacity() * spec.channels.count());
+        if needs_alloc {
+            buffer = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+        }
+        let buf = buffer.as_mut().unwrap();
+        buf.copy_interleaved_ref(decoded);
+
+        let ch = channels.max(1) as usize;
+        samples.extend(
+            buf.samples()
+                .chunks_exact(ch)
+                .map(|frame| frame.iter().sum::<f32>() / ch as f32),
+        );
+    }
+
+    if sample_rate == 0 {
+        return Err(AudioError::Decode("sample rate unknown".to_string()));
+    }
+
+    let decoded_duration = Duration::from_secs_f64(samples.len() as f64 / sample_rate as f64);
+    Ok(DecodedAudio {
+        info: AudioFileInfo {
+            codec,
+            sample_rate,
+            channels,
+            duration: Some(decoded_duration),
+        },
+        samples,
+        sample_rate,
+    })
+}
+
+/// 指定トラックの次のパケット（終端なら `None`）
+fn next_packet(format: &mut dyn FormatReader, track_id: u32) -> Result<Option<Packet>, AudioError> {
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                return Ok(None);
+            }
+            Err(SymphoniaError::ResetRequired) => return Ok(None),
+            Err(e) => return Err(AudioError::Decode(e.to_string())),
+        };
+        if packet.track_id() == track_id {
+            return Ok(Some(packet));
+        }
+    }
+}
+
+/// Opus を libopus でデコード（48kHz、先頭の `pre_skip` を除く）
+#[cfg(feature = "opus")]
+fn decode_opus(
+    format: &mut dyn FormatReader,
+    track_id: u32,
+    params: &symphonia::core::codecs::CodecParameters,
+    codec: String,
+) -> Result<DecodedAudio, AudioError> {
+    use super::opus::{OPUS_SAMPLE_RATE, OpusDecoder};
+
+    let mut decoder = OpusDecoder::new()?;
+    while let Some(packet) = next_packet(format, track_id)? {
+        decoder.push(&packet.data);
+    }
+    let samples = decoder.finish(params.delay.unwrap_or(0) as usize);
+
+    let decoded_duration = Duration::from_secs_f64(samples.len() as f64 / OPUS_SAMPLE_RATE as f64);
+    Ok(DecodedAudio {
+        info: AudioFileInfo {
+            codec,
+            sample_rate: OPUS_SAMPLE_RATE,
+            channels: params.channels.map(|c| c.count() as u16).unwrap_or(1),
+            duration: Some(decoded_duration),
+        },
+        samples,
+        sample_rate: OPUS_SAMPLE_RATE,
+    })
+}
+
+/// デコーダーがあるコーデックか（Opus は `opus` フィーチャー有効時のみ）
+fn is_decodable(codec: CodecType) -> bool {
+    if codec == CODEC_TYPE_OPUS {
+        return cfg!(feature = "opus");
+    }
+    symphonia::default::get_codecs().get_codec(codec).is_some()
+}
+
+fn is_wav(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("wav") || e.eq_ignore_ascii_case("wave"))
+}
+
+fn decode_wav(path: &Path) -> Result<DecodedAudio, AudioError> {
+    let bytes = std::fs::read(path).map_err(|e| AudioError::Io(e.to_string()))?;
+    let wav = WavAudio::parse(&bytes)?;
+    let float = wav.format.encoding() == super::wav::SampleEncoding::Float;
+    let codec = format!(
+        "pcm_{}{}le",
+        if float { "f" } else { "s" },
+        wav.format.bits_per_sample
+    );
+    Ok(DecodedAudio {
+        info: AudioFileInfo {
+            codec,
+            sample_rate: wav.format.sample_rate,
+            channels: wav.format.channels,
+            duration: Some(wav.duration()),
+        },
+        samples: wav.to_mono_f32(),
+        sample_rate: wav.format.sample_rate,
+    })
+}
+
+fn open_format(path: &Path) -> Result<Box<dyn FormatReader>, AudioError> {
+    let file =
+        File::open(path).map_err(|e| AudioError::Io(format!("{}: {}", path.display(), e)))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| match e {
+            SymphoniaError::Unsupported(_) => {
+                AudioError::UnsupportedCodec("unknown container format".to_string())
+            }
+            other => AudioError::Decode(other.to_string()),
+        })?;
+    Ok(probed.format)
+}
+
+fn default_track(
+    format: &dyn FormatReader,
+) -> Result<&symphonia::core::formats::Track, AudioError> {
+    format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| AudioError::Decode("no audio track found".to_string()))
+}
+
+/// デコーダーのないコーデックのエラー（Opus には有効化・変換方法を添える）
+fn unsupported_codec(codec: CodecType) -> AudioError {
+    if codec == CODEC_TYPE_OPUS && !cfg!(feature = "opus") {
+        return AudioError::UnsupportedCodec(
+            "opus (build with `--features opus`, or convert it first, e.g. `ffmpeg -i voice.opus voice.flac`)"
+                .to_string(),
+        );
+    }
+    AudioError::UnsupportedCodec(codec_name(codec))
+}
+
+fn codec_name(codec: CodecType) -> String {
+    if codec == CODEC_TYPE_OPUS {
+        return "opus".to_string();
+    }
+    symphonia::default::get_codecs()
+        .get_codec(codec)
+        .map(|d| d.short_name.to_string())
+        .unwrap_or_else(|| format!("unknown({})", codec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_tone(path: &Path, rate: u32, channels: u16, secs: f32) {
+        let frames = (rate as f32 * secs) as usize;
+        let samples: Vec<i16> = (0..frames * channels as usize)
+            .map(|i| {
+                if (i / channels as usize) % 2 == 0 {
+                    8000
+                } else {
+                    -8000
+                }
+            })
+            .collect();
+        let wav = CpalAudioBackend::combine_wav_data(&samples, rate, channels).unwrap();
+        std::fs::write(path, wav).unwrap();
+    }
+
+    #[test]
+    fn test_decode_wav_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("memo.wav");
+        write_tone(&path, 48000, 2, 0.5);
+
+        let info = probe_file(&path).unwrap();
+        assert_eq!(info.codec, "pcm_s16le");
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.duration, Some(Duration::from_millis(500)));
+        assert_eq!(info.to_string(), "pcm_s16le, 48000 Hz, 2 ch, 0:00.5");
+
+        let decoded = decode_file(&path).unwrap();
+        assert_eq!(decoded.samples.len(), 24000);
+        assert_eq!(decoded.duration(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_decode_via_container_probe() {
+        // 拡張子で判別できないファイルは symphonia のプローブで判別する
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("export.bin");
+        write_tone(&path, 44100, 1, 1.0);
+
+        let info = probe_file(&path).unwrap();
+        assert_eq!(info.sample_rate, 44100);
+        assert_eq!(info.channels, 1);
+
+        let decoded = decode_file(&path).unwrap();
+        assert_eq!(decoded.samples.len(), 44100);
+        assert_eq!(decoded.sample_rate, 44100);
+    }
+
+    #[test]
+    fn test_to_wav_resamples_to_mono_16k() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("meeting.wav");
+        write_tone(&path, 48000, 2, 1.0);
+
+        let audio = decode_file(&path)
+            .unwrap()
+            .to_wav(TRANSCRIPTION_SAMPLE_RATE)
+            .unwrap();
+        let wav = audio.parse_wav().unwrap();
+        assert_eq!(wav.format.sample_rate, 16000);
+        assert_eq!(wav.format.channels, 1);
+        assert_eq!(wav.frames(), 16000);
+    }
+
+    #[test]
+    fn test_rejects_missing_and_garbage_files() {
+        let dir = TempDir::new().unwrap();
+        assert!(matches!(
+            decode_file(&dir.path().join("missing.mp3")),
+            Err(AudioError::Io(_))
+        ));
+
+        let path = dir.path().join("garbage.m4a");
+        std::fs::write(&path, b"definitely not audio data").unwrap();
+        assert!(decode_file(&path).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "opus"))]
+    fn test_opus_is_reported_as_unsupported() {
+        assert!(!is_decodable(CODEC_TYPE_OPUS));
+        let message = unsupported_codec(CODEC_TYPE_OPUS).to_string();
+        assert!(message.starts_with("unsupported audio codec: opus"));
+        assert!(message.contains("--features opus"));
+        assert!(message.contains("ffmpeg"));
+    }
+}
@@ -0,0 +1,325 @@
+//! 入力デバイスの詳細情報
+//!
+//! 各デバイスのデフォルト入力設定・対応サンプルレート/フォーマット/チャンネル数と、
+//! `INPUT_DEVICE_PRIORITY` から現在どのデバイスが選ばれるかをまとめて取得します。
+//! マイクの音質やデバイス選択の問題を調べるためのデバッグ用途です。
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// デバイス優先順位を指定する環境変数
+pub const INPUT_DEVICE_PRIORITY_ENV: &str = "INPUT_DEVICE_PRIORITY";
+
+/// デバイス選択の結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeviceSelection {
+    /// 優先リストに一致したデバイス
+    Preferred(String),
+    /// 優先リストに一致せず、デフォルトデバイスにフォールバック（デフォルトもなければ `None`）
+    DefaultFallback(Option<String>),
+    /// `INPUT_DEVICE_PRIORITY` が未設定（録音開始に失敗する）
+    NotConfigured,
+}
+
+impl DeviceSelection {
+    /// 選択されるデバイス名
+    pub fn device_name(&self) -> Option<&str> {
+        match self {
+            DeviceSelection::Preferred(name) => Some(name),
+            DeviceSelection::DefaultFallback(name) => name.as_deref(),
+            DeviceSelection::NotConfigured => None,
+        }
+    }
+}
+
+/// `INPUT_DEVICE_PRIORITY` のカンマ区切りリストを解釈
+pub fn parse_priority_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_owned())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// 優先リストと利用可能なデバイスから、録音に使うデバイスを決定
+pub fn pick_input_device(
+    priorities: Option<&[String]>,
+    available: &[String],
+    default: Option<&str>,
+) -> DeviceSelection {
+    let Some(priorities) = priorities else {
+        return DeviceSelection::NotConfigured;
+    };
+
+    priorities
+        .iter()
+        .find(|want| available.contains(want))
+        .map(|want| DeviceSelection::Preferred(want.clone()))
+        .unwrap_or_else(|| DeviceSelection::DefaultFallback(default.map(str::to_owned)))
+}
+
+/// ストリーム設定（デフォルト入力設定）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamConfigInfo {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub sample_format: String,
+}
+
+/// 対応しているストリーム設定の範囲
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupportedConfigInfo {
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+    pub sample_format: String,
+}
+
+/// 入力デバイス 1 件分の情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceInfo {
+    pub name: String,
+    /// OS のデフォルト入力デバイスか
+    pub is_default: bool,
+    /// 現在の設定で録音に使われるデバイスか
+    pub is_selected: bool,
+    pub default_config: Option<StreamConfigInfo>,
+    pub supported_configs: Vec<SupportedConfigInfo>,
+    /// 設定取得に失敗した場合のエラー
+    pub error: Option<String>,
+}
+
+/// 入力デバイスの一覧と選択状況
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceReport {
+    pub host: String,
+    /// `INPUT_DEVICE_PRIORITY` の内容（未設定なら `None`）
+    pub priority: Option<Vec<String>>,
+    pub selection: DeviceSelection,
+    pub devices: Vec<DeviceInfo>,
+}
+
+impl DeviceReport {
+    /// 現在のホストの入力デバイス情報を取得
+    pub fn query() -> Self {
+        let host = cpal::default_host();
+        let default_name = host.default_input_device().and_then(|d| d.name().ok());
+        let priority = std::env::var(INPUT_DEVICE_PRIORITY_ENV)
+            .ok()
+            .map(|v| parse_priority_list(&v));
+
+        let devices: Vec<cpal::Device> = host
+            .input_devices()
+            .map(|iter| iter.collect())
+            .unwrap_or_default();
+        let names: Vec<String> = devices.iter().filter_map(|d| d.name().ok()).collect();
+        let selection = pick_input_device(priority.as_deref(), &names, default_name.as_deref());
+
+        let devices = devices
+            .iter()
+            .filter_map(|device| {
+                let name = device.name().ok()?;
+                Some(Self::describe(
+                    device,
+                    name.clone(),
+                    default_name.as_deref() == Some(name.as_str()),
+                    selection.device_name() == Some(name.as_str()),
+                ))
+            })
+            .collect();
+
+        Self {
+            host: host.id().name().to_string(),
+            priority,
+            selection,
+            devices,
+        }
+    }
+
+    fn describe(
+        device: &cpal::Device,
+        name: String,
+        is_default: bool,
+        is_selected: bool,
+    ) -> DeviceInfo {
+        let mut errors = Vec::new();
+
+        let default_config = match device.default_input_config() {
+            Ok(config) => Some(StreamConfigInfo {
+                sample_rate: config.sample_rate().0,
+                channels: config.channels(),
+                sample_format: config.sample_format().to_string(),
+            }),
+            Err(e) => {
+                errors.push(e.to_string());
+                None
+            }
+        };
+
+        let supported_configs = match device.supported_input_configs() {
+            Ok(configs) => configs
+                .map(|c| SupportedConfigInfo {
+                    channels: c.channels(),
+                    min_sample_rate: c.min_sample_rate().0,
+                    max_sample_rate: c.max_sample_rate().0,
+                    sample_format: c.sample_format().to_string(),
+                })
+                .collect(),
+            Err(e) => {
+                errors.push(e.to_string());
+                Vec::new()
+            }
+        };
+
+        DeviceInfo {
+            name,
+            is_default,
+            is_selected,
+            default_config,
+            supported_configs,
+            error: (!errors.is_empty()).then(|| errors.join("; ")),
+        }
+    }
+}
+
+impl fmt::Display for DeviceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Host: {}", self.host)?;
+        match &self.priority {
+            Some(list) => writeln!(f, "{}: {}", INPUT_DEVICE_PRIORITY_ENV, list.join(", "))?,
+            None => writeln!(f, "{}: (not set)", INPUT_DEVICE_PRIORITY_ENV)?,
+        }
+        match &self.selection {
+            DeviceSelection::Preferred(name) => writeln!(f, "Selected: {} (priority match)", name)?,
+            DeviceSelection::DefaultFallback(Some(name)) => {
+                writeln!(f, "Selected: {} (no priority match, default device)", name)?
+            }
+            DeviceSelection::DefaultFallback(None) => writeln!(
+                f,
+                "Selected: none (no priority match and no default device)"
+            )?,
+            DeviceSelection::NotConfigured => writeln!(
+                f,
+                "Selected: none ({} is not set; recording will fail)",
+                INPUT_DEVICE_PRIORITY_ENV
+            )?,
+        }
+
+        if self.devices.is_empty() {
+            return write!(f, "\n⚠️  No input devices detected");
+        }
+
+        for device in &self.devices {
+            let marker = if device.is_selected { "*" } else { " " };
+            let mut tags = String::new();
+            if device.is_default {
+                tags.push_str(" [default]");
+            }
+            if device.is_selected {
+                tags.push_str(" [selected]");
+            }
+            writeln!(f, "\n{} {}{}", marker, device.name, tags)?;
+
+            if let Some(config) = &device.default_config {
+                writeln!(
+                    f,
+                    "    default: {} Hz, {} ch, {}",
+                    config.sample_rate, config.channels, config.sample_format
+                )?;
+            }
+            if !device.supported_configs.is_empty() {
+                writeln!(f, "    supported:")?;
+                for c in &device.supported_configs {
+                    let rates = if c.min_sample_rate == c.max_sample_rate {
+                        format!("{} Hz", c.min_sample_rate)
+                    } else {
+                        format!("{}-{} Hz", c.min_sample_rate, c.max_sample_rate)
+                    };
+                    writeln!(f, "      {} ch, {}, {}", c.channels, c.sample_format, rates)?;
+                }
+            }
+            if let Some(error) = &device.error {
+                writeln!(f, "    error: {}", error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_priority_list() {
+        assert_eq!(
+            parse_priority_list(" USB Mic , ,MacBook Pro Microphone"),
+            names(&["USB Mic", "MacBook Pro Microphone"])
+        );
+        assert!(parse_priority_list("").is_empty());
+    }
+
+    #[test]
+    fn test_pick_input_device() {
+        let available = names(&["MacBook Pro Microphone", "USB Mic"]);
+        let default = Some("MacBook Pro Microphone");
+
+        let priority = names(&["Missing", "USB Mic", "MacBook Pro Microphone"]);
+        assert_eq!(
+            pick_input_device(Some(&priority), &available, default),
+            DeviceSelection::Preferred("USB Mic".to_string())
+        );
+
+        let priority = names(&["Missing"]);
+        assert_eq!(
+            pick_input_device(Some(&priority), &available, default),
+            DeviceSelection::DefaultFallback(Some("MacBook Pro Microphone".to_string()))
+        );
+
+        assert_eq!(
+            pick_input_device(None, &available, default),
+            DeviceSelection::NotConfigured
+        );
+    }
+
+    #[test]
+    fn test_report_display_and_serialization() {
+        let report = DeviceReport {
+            host: "CoreAudio".to_string(),
+            priority: Some(names(&["USB Mic"])),
+            selection: DeviceSelection::Preferred("USB Mic".to_string()),
+            devices: vec![DeviceInfo {
+                name: "USB Mic".to_string(),
+                is_default: false,
+                is_selected: true,
+                default_config: Some(StreamConfigInfo {
+                    sample_rate: 48000,
+                    channels: 1,
+                    sample_format: "f32".to_string(),
+                }),
+                supported_configs: vec![SupportedConfigInfo {
+                    channels: 1,
+                    min_sample_rate: 44100,
+                    max_sample_rate: 48000,
+                    sample_format: "f32".to_string(),
+                }],
+                error: None,
+            }],
+        };
+
+        let text = report.to_string();
+        assert!(text.contains("Selected: USB Mic (priority match)"));
+        assert!(text.contains("* USB Mic [selected]"));
+        assert!(text.contains("default: 48000 Hz, 1 ch, f32"));
+        assert!(text.contains("1 ch, f32, 44100-48000 Hz"));
+
+        let json = serde_json::to_string(&report).unwrap();
+        let back: DeviceReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, report);
+    }
+}
@@ -1,7 +1,23 @@
 use std::error::Error;
 
 pub mod cpal_backend;
-pub use cpal_backend::{AudioData, CpalAudioBackend};
+pub mod decoder;
+pub mod device;
+#[cfg(feature = "opus")]
+pub mod opus;
+pub mod resample;
+pub mod vad;
+pub mod wav;
+pub use cpal_backend::{AudioData, AudioError, CpalAudioBackend};
+pub use wav::{WavAudio, WavFormat};
+
+/// 録音中のバッファから取り出したPCMサンプル（インターリーブ済み 16bit）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PcmChunk {
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
 
 /// 録音デバイス抽象。
 /// 実装は `start_recording`→`stop_recording` が 1 対で呼ばれることを前提とする。
@@ -16,4 +32,10 @@ pub trait AudioBackend {
 
     /// 現在録音中であれば `true`。
     fn is_recording(&self) -> bool;
+
+    /// 録音を止めずに、これまでに蓄積したサンプルを取り出します。
+    /// 取り出した分はバッファから削除されます。未対応のバックエンドは `None` を返します。
+    fn drain_samples(&self) -> Option<PcmChunk> {
+        None
+    }
 }
@@ -0,0 +1,180 @@
+//! Opus のデコード（`opus` フィーチャー）
+//!
+//! symphonia は Ogg コンテナから Opus のパケットを取り出せますが、デコーダーがないため
+//! libopus（audiopus）でデコードします。libopus のリンクが必要なため既定では無効です。
+
+use super::cpal_backend::AudioError;
+use audiopus::coder::Decoder;
+use audiopus::packet::Packet;
+use audiopus::{Channels, MutSignals, SampleRate};
+
+/// Opus のデコード後のサンプリングレート（Opus は常に 48kHz で扱う）
+pub const OPUS_SAMPLE_RATE: u32 = 48_000;
+
+/// 1 パケットの最大のフレーム長（48kHz で 120ms）
+const MAX_FRAME_SAMPLES: usize = 5_760;
+
+/// Opus のパケット列をモノラル f32 にデコード
+pub struct OpusDecoder {
+    decoder: Decoder,
+    frame: Vec<f32>,
+    samples: Vec<f32>,
+}
+
+impl OpusDecoder {
+    /// 新しいデコーダーを作成（ステレオは libopus がモノラルにまとめる）
+    pub fn new() -> Result<Self, AudioError> {
+        Ok(Self {
+            decoder: Decoder::new(SampleRate::Hz48000, Channels::Mono).map_err(opus_error)?,
+            frame: vec![0.0; MAX_FRAME_SAMPLES],
+            samples: Vec::new(),
+        })
+    }
+
+    /// パケットを 1 つデコード（壊れたパケットは読み飛ばす）
+    pub fn push(&mut self, packet: &[u8]) {
+        let Ok(packet) = Packet::try_from(packet) else {
+            return;
+        };
+        let Ok(output) = MutSignals::try_from(&mut self.frame[..]) else {
+            return;
+        };
+        if let Ok(n) = self.decoder.decode_float(Some(packet), output, false) {
+            self.samples.extend_from_slice(&self.frame[..n]);
+        }
+    }
+
+    /// デコード結果（先頭のエンコーダーの遅延 `pre_skip` サンプルを除く）
+    pub fn finish(mut self, pre_skip: usize) -> Vec<f32> {
+        let pre_skip = pre_skip.min(self.samples.len());
+        self.samples.drain(..pre_skip);
+        self.samples
+    }
+}
+
+fn opus_error(e: audiopus::Error) -> AudioError {
+    AudioError::Decode(format!("opus: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::audio::decoder::{decode_file, probe_file};
+    use audiopus::Application;
+    use audiopus::coder::Encoder;
+    use tempfile::TempDir;
+
+    /// 20ms ごとの Opus パケット（440Hz の正弦波）
+    fn encode_tone(secs: f32) -> (Vec<Vec<u8>>, u16) {
+        let encoder = Encoder::new(SampleRate::Hz48000, Channels::Mono, Application::Voip).unwrap();
+        let pre_skip = encoder.lookahead().unwrap() as u16;
+        let frames = (OPUS_SAMPLE_RATE as f32 * secs) as usize;
+        let tone: Vec<f32> = (0..frames)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 48_000.0).sin() * 0.5)
+            .collect();
+        let packets = tone
+            .chunks_exact(960)
+            .map(|frame| {
+                let mut out = vec![0u8; 4000];
+                let len = encoder.encode_float(frame, &mut out).unwrap();
+                out.truncate(len);
+                out
+            })
+            .collect();
+        (packets, pre_skip)
+    }
+
+    /// Ogg のページの CRC（多項式 0x04C11DB7、反転なし）
+    fn ogg_crc(data: &[u8]) -> u32 {
+        data.iter().fold(0u32, |crc, &byte| {
+            (0..8).fold(crc ^ ((byte as u32) << 24), |crc, _| {
+                if crc & 0x8000_0000 != 0 {
+                    (crc << 1) ^ 0x04C1_1DB7
+                } else {
+                    crc << 1
+                }
+            })
+        })
+    }
+
+    /// 1 パケットを 1 ページとして書き出す
+    fn ogg_page(out: &mut Vec<u8>, packet: &[u8], header_type: u8, granule: u64, sequence: u32) {
+        let mut lacing = vec![255u8; packet.len() / 255];
+        lacing.push((packet.len() % 255) as u8);
+        let start = out.len();
+        out.extend_from_slice(b"OggS");
+        out.push(0);
+        out.push(header_type);
+        out.extend_from_slice(&granule.to_le_bytes());
+        out.extend_from_slice(&1u32.to_le_bytes());
+        out.extend_from_slice(&sequence.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.push(lacing.len() as u8);
+        out.extend_from_slice(&lacing);
+        out.extend_from_slice(packet);
+        let crc = ogg_crc(&out[start..]);
+        out[start + 22..start + 26].copy_from_slice(&crc.to_le_bytes());
+    }
+
+    /// Ogg Opus のファイルを書き出す
+    fn write_ogg_opus(path: &std::path::Path, packets: &[Vec<u8>], pre_skip: u16) {
+        let mut head = b"OpusHead".to_vec();
+        head.push(1);
+        head.push(1);
+        head.extend_from_slice(&pre_skip.to_le_bytes());
+        head.extend_from_slice(&OPUS_SAMPLE_RATE.to_le_bytes());
+        head.extend_from_slice(&[0, 0, 0]);
+        let mut tags = b"OpusTags".to_vec();
+        tags.extend_from_slice(&4u32.to_le_bytes());
+        tags.extend_from_slice(b"test");
+        tags.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut out = Vec::new();
+        ogg_page(&mut out, &head, 0x02, 0, 0);
+        ogg_page(&mut out, &tags, 0x00, 0, 1);
+        for (n, packet) in packets.iter().enumerate() {
+            let last = n + 1 == packets.len();
+            let granule = (n as u64 + 1) * 960;
+            ogg_page(
+                &mut out,
+                packet,
+                if last { 0x04 } else { 0 },
+                granule,
+                n as u32 + 2,
+            );
+        }
+        std::fs::write(path, out).unwrap();
+    }
+
+    #[test]
+    fn test_decodes_packets_and_drops_pre_skip() {
+        let (packets, pre_skip) = encode_tone(0.5);
+        let mut decoder = OpusDecoder::new().unwrap();
+        for packet in &packets {
+            decoder.push(packet);
+        }
+        // 壊れたパケットは読み飛ばす
+        decoder.push(&[]);
+
+        let samples = decoder.finish(pre_skip as usize);
+        assert_eq!(samples.len(), 24_000 - pre_skip as usize);
+        assert!(samples.iter().any(|s| s.abs() > 0.1));
+    }
+
+    #[test]
+    fn test_decode_ogg_opus_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("voice.opus");
+        let (packets, pre_skip) = encode_tone(1.0);
+        write_ogg_opus(&path, &packets, pre_skip);
+
+        let info = probe_file(&path).unwrap();
+        assert_eq!(info.codec, "opus");
+        assert_eq!(info.channels, 1);
+
+        let decoded = decode_file(&path).unwrap();
+        assert_eq!(decoded.sample_rate, OPUS_SAMPLE_RATE);
+        assert_eq!(decoded.samples.len(), 48_000 - pre_skip as usize);
+        assert_eq!(decoded.info.codec, "opus");
+    }
+}
@@ -0,0 +1,79 @@
+//! モノラル音声の簡易リサンプラー
+//!
+//! 文字起こし用途（16kHz 程度への変換）を想定した軽量実装です。
+//! ダウンサンプル時は出力 1 サンプル分の区間を平均してエイリアシングを抑え、
+//! アップサンプル時は線形補間します。
+
+/// `from_rate` から `to_rate` へサンプリングレートを変換
+pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() || from_rate == 0 || to_rate == 0 {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = ((samples.len() as f64) / ratio).round().max(1.0) as usize;
+    let last = samples.len() - 1;
+
+    (0..out_len)
+        .map(|i| {
+            let center = i as f64 * ratio;
+            if ratio > 1.0 {
+                // 区間平均（ボックスフィルタ）
+                let start = (center - ratio / 2.0).max(0.0).ceil() as usize;
+                let end = ((center + ratio / 2.0).floor() as usize).min(last);
+                if start > end {
+                    return samples[(center.round() as usize).min(last)];
+                }
+                let window = &samples[start..=end];
+                window.iter().sum::<f32>() / window.len() as f32
+            } else {
+                // 線形補間
+                let base = (center.floor() as usize).min(last);
+                let next = (base + 1).min(last);
+                let frac = (center - base as f64) as f32;
+                samples[base] * (1.0 - frac) + samples[next] * frac
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_rate_is_identity() {
+        let input = vec![0.1, -0.2, 0.3];
+        assert_eq!(resample(&input, 16000, 16000), input);
+        assert!(resample(&[], 48000, 16000).is_empty());
+    }
+
+    #[test]
+    fn test_downsample_length_and_dc() {
+        let input = vec![0.5; 48000];
+        let output = resample(&input, 48000, 16000);
+        assert_eq!(output.len(), 16000);
+        assert!(output.iter().all(|s| (s - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_downsample_attenuates_nyquist() {
+        // 入力のナイキスト周波数（交互に ±1）は平均で打ち消される
+        let input: Vec<f32> = (0..4410)
+            .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let output = resample(&input, 44100, 16000);
+        assert_eq!(output.len(), 1600);
+        let peak = output.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(peak < 0.5, "peak {}", peak);
+    }
+
+    #[test]
+    fn test_upsample_interpolates() {
+        let output = resample(&[0.0, 1.0], 8000, 16000);
+        assert_eq!(output.len(), 4);
+        assert_eq!(output[0], 0.0);
+        assert_eq!(output[1], 0.5);
+        assert_eq!(output[2], 1.0);
+    }
+}
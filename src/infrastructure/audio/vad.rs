@@ -0,0 +1,267 @@
+//! エネルギー（RMS）ベースの簡易 VAD（Voice Activity Detection）
+//!
+//! 連続音声入力モードで、マイク入力を発話ごとのセグメントに切り分けるために使用します。
+//! 一定時間の無音で発話の終わりとみなし、短すぎる発話（咳・物音など）は破棄します。
+
+use std::collections::VecDeque;
+
+/// VAD 設定
+#[derive(Clone, Debug, PartialEq)]
+pub struct VadConfig {
+    /// 判定フレーム長（ミリ秒）
+    pub frame_ms: u32,
+    /// 発話とみなす RMS の閾値（16bit サンプル値）
+    pub energy_threshold: f32,
+    /// 発話終了とみなす無音の長さ（ミリ秒）
+    pub silence_ms: u32,
+    /// セグメントとして採用する最小発話時間（ミリ秒）
+    pub min_speech_ms: u32,
+    /// 発話開始直前に含める音声（ミリ秒）。語頭の欠けを防ぐ
+    pub pre_roll_ms: u32,
+    /// 1 セグメントの最大長（秒）。達した時点で強制的に区切る
+    pub max_segment_secs: u32,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            frame_ms: 30,
+            energy_threshold: 500.0,
+            silence_ms: 800,
+            min_speech_ms: 300,
+            pre_roll_ms: 200,
+            max_segment_secs: 30,
+        }
+    }
+}
+
+/// サンプル列の RMS（二乗平均平方根）を計算
+pub fn rms(samples: &[i16]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum / samples.len() as f64).sqrt() as f32
+}
+
+/// 発話とみなせるフレームの合計時間（ミリ秒）
+///
+/// 録音全体に実際の発話が含まれているかの判定（無音時の定型句の検出など）に使う。
+pub fn speech_ms(samples: &[i16], sample_rate: u32, channels: u16, config: &VadConfig) -> u32 {
+    let frame_len =
+        (sample_rate as usize * channels.max(1) as usize * config.frame_ms as usize / 1000).max(1);
+    let frames = samples
+        .chunks(frame_len)
+        .filter(|frame| rms(frame) >= config.energy_threshold)
+        .count();
+    frames as u32 * config.frame_ms
+}
+
+/// 入力サンプルを逐次受け取り、発話セグメントを切り出す検出器
+pub struct VoiceActivityDetector {
+    config: VadConfig,
+    /// 1 フレームあたりのサンプル数（全チャンネル分）
+    frame_len: usize,
+    /// 1 ミリ秒あたりのサンプル数（全チャンネル分）
+    samples_per_ms: usize,
+    /// フレームに満たない端数サンプル
+    pending: Vec<i16>,
+    /// 発話開始前の直近音声
+    pre_roll: VecDeque<i16>,
+    /// 発話中のセグメント
+    segment: Vec<i16>,
+    in_speech: bool,
+    speech_ms: u32,
+    silence_run_ms: u32,
+}
+
+impl VoiceActivityDetector {
+    /// サンプルレートとチャンネル数を指定して検出器を作成
+    pub fn new(config: VadConfig, sample_rate: u32, channels: u16) -> Self {
+        let samples_per_ms = (sample_rate as usize * channels as usize / 1000).max(1);
+        let frame_len = samples_per_ms * config.frame_ms.max(1) as usize;
+        Self {
+            config,
+            frame_len,
+            samples_per_ms,
+            pending: Vec::with_capacity(frame_len),
+            pre_roll: VecDeque::new(),
+            segment: Vec::new(),
+            in_speech: false,
+            speech_ms: 0,
+            silence_run_ms: 0,
+        }
+    }
+
+    /// 発話中かどうか
+    pub fn in_speech(&self) -> bool {
+        self.in_speech
+    }
+
+    /// サンプルを投入し、確定したセグメントを返す
+    pub fn push(&mut self, samples: &[i16]) -> Vec<Vec<i16>> {
+        let mut segments = Vec::new();
+        self.pending.extend_from_slice(samples);
+
+        let mut offset = 0;
+        while self.pending.len() - offset >= self.frame_len {
+            let frame = self.pending[offset..offset + self.frame_len].to_vec();
+            offset += self.frame_len;
+            if let Some(segment) = self.process_frame(&frame) {
+                segments.push(segment);
+            }
+        }
+        self.pending.drain(..offset);
+
+        segments
+    }
+
+    /// 入力終了時に、発話途中のセグメントを確定して返す
+    pub fn flush(&mut self) -> Option<Vec<i16>> {
+        if self.in_speech {
+            let rest = std::mem::take(&mut self.pending);
+            self.segment.extend_from_slice(&rest);
+            self.finish_segment()
+        } else {
+            self.pending.clear();
+            None
+        }
+    }
+
+    fn process_frame(&mut self, frame: &[i16]) -> Option<Vec<i16>> {
+        let is_speech = rms(frame) >= self.config.energy_threshold;
+        let frame_ms = self.config.frame_ms;
+
+        if !self.in_speech {
+            if !is_speech {
+                self.push_pre_roll(frame);
+                return None;
+            }
+            self.in_speech = true;
+            self.segment.extend(self.pre_roll.drain(..));
+            self.speech_ms = 0;
+            self.silence_run_ms = 0;
+        }
+
+        self.segment.extend_from_slice(frame);
+        if is_speech {
+            self.speech_ms += frame_ms;
+            self.silence_run_ms = 0;
+        } else {
+            self.silence_run_ms += frame_ms;
+        }
+
+        let max_len = self.config.max_segment_secs as usize * 1000 * self.samples_per_ms;
+        if self.silence_run_ms >= self.config.silence_ms || self.segment.len() >= max_len {
+            return self.finish_segment();
+        }
+        None
+    }
+
+    fn push_pre_roll(&mut self, frame: &[i16]) {
+        let max = self.config.pre_roll_ms as usize * self.samples_per_ms;
+        self.pre_roll.extend(frame.iter().copied());
+        while self.pre_roll.len() > max {
+            self.pre_roll.pop_front();
+        }
+    }
+
+    fn finish_segment(&mut self) -> Option<Vec<i16>> {
+        let segment = std::mem::take(&mut self.segment);
+        let long_enough = self.speech_ms >= self.config.min_speech_ms;
+        self.in_speech = false;
+        self.speech_ms = 0;
+        self.silence_run_ms = 0;
+        long_enough.then_some(segment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16000;
+
+    fn tone(ms: usize) -> Vec<i16> {
+        (0..ms * 16)
+            .map(|i| if i % 2 == 0 { 3000 } else { -3000 })
+            .collect()
+    }
+
+    fn silence(ms: usize) -> Vec<i16> {
+        vec![0; ms * 16]
+    }
+
+    #[test]
+    fn test_rms() {
+        assert_eq!(rms(&[]), 0.0);
+        assert_eq!(rms(&[100, -100, 100, -100]), 100.0);
+    }
+
+    #[test]
+    fn test_speech_ms() {
+        let config = VadConfig::default();
+        assert_eq!(speech_ms(&silence(1000), RATE, 1, &config), 0);
+
+        let mut input = silence(300);
+        input.extend(tone(600));
+        input.extend(silence(300));
+        assert_eq!(speech_ms(&input, RATE, 1, &config), 600);
+    }
+
+    #[test]
+    fn test_splits_utterances_on_silence() {
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), RATE, 1);
+
+        let mut input = silence(300);
+        input.extend(tone(600));
+        input.extend(silence(900));
+        input.extend(tone(450));
+        input.extend(silence(900));
+
+        let segments = vad.push(&input);
+        assert_eq!(segments.len(), 2);
+        // pre-roll 200ms + 発話 + 無音 800ms 程度を含む
+        assert!(segments[0].len() >= tone(600).len() + silence(800).len());
+        assert!(!vad.in_speech());
+        assert!(vad.flush().is_none());
+    }
+
+    #[test]
+    fn test_discards_short_noise() {
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), RATE, 1);
+
+        let mut input = tone(90);
+        input.extend(silence(900));
+        assert!(vad.push(&input).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_push_and_flush() {
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), RATE, 1);
+
+        // フレーム境界に揃わない細切れ入力でも検出できる
+        for chunk in tone(500).chunks(77) {
+            assert!(vad.push(chunk).is_empty());
+        }
+        assert!(vad.in_speech());
+
+        let segment = vad.flush().unwrap();
+        assert_eq!(segment.len(), tone(500).len());
+        assert!(!vad.in_speech());
+    }
+
+    #[test]
+    fn test_max_segment_length() {
+        let config = VadConfig {
+            max_segment_secs: 1,
+            ..Default::default()
+        };
+        let mut vad = VoiceActivityDetector::new(config, RATE, 1);
+
+        let segments = vad.push(&tone(2500));
+        assert_eq!(segments.len(), 2);
+        // 最大長に達したフレームで区切られる
+        assert!(segments.iter().all(|s| s.len() < RATE as usize + 30 * 16));
+    }
+}
@@ -0,0 +1,518 @@
+//! WAV（RIFF/WAVE）パーサー
+//!
+//! `CpalAudioBackend::combine_wav_data` で生成したデータだけでなく、外部ファイルの
+//! WAVE_FORMAT_EXTENSIBLE や `LIST` などの追加チャンクを含むデータも読み込めます。
+//! 各チャンクの宣言長を検証し、壊れたデータは `AudioError` で拒否します。
+
+use super::cpal_backend::{AudioError, Sample};
+use std::time::Duration;
+
+pub const WAVE_FORMAT_PCM: u16 = 0x0001;
+pub const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+pub const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// 長さ不明（ストリーミング書き出し）を表すチャンクサイズ
+const UNKNOWN_SIZE: u32 = u32::MAX;
+
+/// サンプルのエンコーディング
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleEncoding {
+    /// 整数 PCM（8bit は unsigned、それ以外は signed）
+    Pcm,
+    /// IEEE 浮動小数点
+    Float,
+}
+
+/// fmt チャンクの内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WavFormat {
+    /// 実際のフォーマット（EXTENSIBLE の場合は SubFormat の値）
+    pub format_tag: u16,
+    /// WAVE_FORMAT_EXTENSIBLE で記述されていたか
+    pub extensible: bool,
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub byte_rate: u32,
+    pub block_align: u16,
+    pub bits_per_sample: u16,
+    /// 有効ビット数（EXTENSIBLE 以外は `bits_per_sample` と同じ）
+    pub valid_bits_per_sample: u16,
+    /// スピーカー配置（EXTENSIBLE のみ）
+    pub channel_mask: Option<u32>,
+}
+
+impl WavFormat {
+    /// サンプルのエンコーディング
+    pub fn encoding(&self) -> SampleEncoding {
+        if self.format_tag == WAVE_FORMAT_IEEE_FLOAT {
+            SampleEncoding::Float
+        } else {
+            SampleEncoding::Pcm
+        }
+    }
+
+    /// 1 サンプルあたりのバイト数
+    pub fn bytes_per_sample(&self) -> usize {
+        self.bits_per_sample as usize / 8
+    }
+}
+
+/// 解析済みの WAV データ（フォーマット情報 + data チャンクの中身）
+#[derive(Debug, Clone, PartialEq)]
+pub struct WavAudio {
+    pub format: WavFormat,
+    /// data チャンクのバイト列（インターリーブ）
+    pub data: Vec<u8>,
+}
+
+impl WavAudio {
+    /// WAV バイト列を解析します。
+    ///
+    /// # Errors
+    /// - `AudioError::NotWav` - RIFF/WAVE ヘッダーではない
+    /// - `AudioError::Truncated` - 宣言された長さに対してデータが不足
+    /// - `AudioError::MissingChunk` - `fmt ` または `data` チャンクがない
+    /// - `AudioError::InvalidFormat` - fmt の値が矛盾している
+    /// - `AudioError::UnsupportedFormat` - PCM / IEEE float 以外
+    pub fn parse(bytes: &[u8]) -> Result<Self, AudioError> {
+        if bytes.len() < 12 {
+            return Err(AudioError::Truncated {
+                expected: 12,
+                actual: bytes.len(),
+            });
+        }
+        if &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(AudioError::NotWav);
+        }
+
+        let riff_size = read_u32(bytes, 4);
+        let end = if riff_size == 0 || riff_size == UNKNOWN_SIZE {
+            bytes.len()
+        } else {
+            let declared = riff_size as usize + 8;
+            if declared > bytes.len() {
+                return Err(AudioError::Truncated {
+                    expected: declared,
+                    actual: bytes.len(),
+                });
+            }
+            declared
+        };
+
+        let mut format = None;
+        let mut data = None;
+        let mut pos = 12;
+        while pos + 8 <= end {
+            let id = &bytes[pos..pos + 4];
+            let size = read_u32(bytes, pos + 4);
+            let body_start = pos + 8;
+
+            let body_end = if id == b"data" && size == UNKNOWN_SIZE {
+                end
+            } else {
+                let body_end = body_start + size as usize;
+                if body_end > end {
+                    return Err(AudioError::Truncated {
+                        expected: body_end,
+                        actual: end,
+                    });
+                }
+                body_end
+            };
+
+            match id {
+                b"fmt " => format = Some(parse_fmt(&bytes[body_start..body_end])?),
+                b"data" => data = Some(&bytes[body_start..body_end]),
+                _ => {} // LIST, fact, cue など
+            }
+
+            // チャンクは偶数境界に揃えられる
+            pos = body_end + (body_end - body_start) % 2;
+        }
+
+        let format = format.ok_or(AudioError::MissingChunk("fmt "))?;
+        let data = data.ok_or(AudioError::MissingChunk("data"))?;
+
+        if data.len() % format.block_align as usize != 0 {
+            return Err(AudioError::InvalidFormat(format!(
+                "data length {} is not a multiple of block_align {}",
+                data.len(),
+                format.block_align
+            )));
+        }
+
+        Ok(Self {
+            format,
+            data: data.to_vec(),
+        })
+    }
+
+    /// フレーム数（チャンネルをまとめた 1 時点分を 1 フレームとする）
+    pub fn frames(&self) -> usize {
+        self.data.len() / self.format.block_align as usize
+    }
+
+    /// 再生時間
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.frames() as f64 / self.format.sample_rate as f64)
+    }
+
+    /// サンプルを -1.0〜1.0 の f32 に変換（インターリーブ）
+    pub fn samples_f32(&self) -> Vec<f32> {
+        let width = self.format.bytes_per_sample();
+        let encoding = self.format.encoding();
+        self.data
+            .chunks_exact(width)
+            .map(|b| match (encoding, width) {
+                (SampleEncoding::Float, 4) => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+                (SampleEncoding::Float, _) => {
+                    f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32
+                }
+                (SampleEncoding::Pcm, 1) => (b[0] as f32 - 128.0) / 128.0,
+                (SampleEncoding::Pcm, 2) => i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+                (SampleEncoding::Pcm, 3) => read_i24(b) as f32 / 8_388_608.0,
+                (SampleEncoding::Pcm, _) => {
+                    i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0
+                }
+            })
+            .collect()
+    }
+
+    /// サンプルを 16bit 整数に変換（インターリーブ）
+    pub fn samples_i16(&self) -> Vec<i16> {
+        let width = self.format.bytes_per_sample();
+        match self.format.encoding() {
+            SampleEncoding::Float => self.samples_f32().iter().map(|s| s.to_i16()).collect(),
+            SampleEncoding::Pcm => self
+                .data
+                .chunks_exact(width)
+                .map(|b| match width {
+                    1 => ((b[0] as i16) - 128) << 8,
+                    2 => i16::from_le_bytes([b[0], b[1]]),
+                    3 => (read_i24(b) >> 8) as i16,
+                    _ => (i32::from_le_bytes([b[0], b[1], b[2], b[3]]) >> 16) as i16,
+                })
+                .collect(),
+        }
+    }
+
+    /// 全チャンネルを平均してモノラルの f32 サンプルに変換
+    pub fn to_mono_f32(&self) -> Vec<f32> {
+        let channels = self.format.channels as usize;
+        self.samples_f32()
+            .chunks_exact(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect()
+    }
+}
+
+fn parse_fmt(body: &[u8]) -> Result<WavFormat, AudioError> {
+    if body.len() < 16 {
+        return Err(AudioError::Truncated {
+            expected: 16,
+            actual: body.len(),
+        });
+    }
+
+    let raw_tag = read_u16(body, 0);
+    let channels = read_u16(body, 2);
+    let sample_rate = read_u32(body, 4);
+    let byte_rate = read_u32(body, 8);
+    let block_align = read_u16(body, 12);
+    let bits_per_sample = read_u16(body, 14);
+
+    let (format_tag, valid_bits_per_sample, channel_mask) = if raw_tag == WAVE_FORMAT_EXTENSIBLE {
+        // cbSize(2) + wValidBitsPerSample(2) + dwChannelMask(4) + SubFormat GUID(16)
+        if body.len() < 40 {
+            return Err(AudioError::Truncated {
+                expected: 40,
+                actual: body.len(),
+            });
+        }
+        let valid_bits = read_u16(body, 18);
+        let mask = read_u32(body, 20);
+        // GUID の先頭 2 バイトが実際のフォーマットタグ
+        let sub_format = read_u16(body, 24);
+        let valid_bits = if valid_bits == 0 {
+            bits_per_sample
+        } else {
+            valid_bits
+        };
+        (sub_format, valid_bits, Some(mask))
+    } else {
+        (raw_tag, bits_per_sample, None)
+    };
+
+    if channels == 0 {
+        return Err(AudioError::InvalidFormat("channel count is 0".to_string()));
+    }
+    if sample_rate == 0 {
+        return Err(AudioError::InvalidFormat("sample rate is 0".to_string()));
+    }
+
+    let supported = match format_tag {
+        WAVE_FORMAT_PCM => matches!(bits_per_sample, 8 | 16 | 24 | 32),
+        WAVE_FORMAT_IEEE_FLOAT => matches!(bits_per_sample, 32 | 64),
+        _ => false,
+    };
+    if !supported {
+        return Err(AudioError::UnsupportedFormat {
+            format_tag,
+            bits_per_sample,
+        });
+    }
+
+    let expected_align = channels as u32 * bits_per_sample as u32 / 8;
+    if block_align as u32 != expected_align {
+        return Err(AudioError::InvalidFormat(format!(
+            "block_align {} does not match {} channels x {} bits",
+            block_align, channels, bits_per_sample
+        )));
+    }
+    if valid_bits_per_sample > bits_per_sample {
+        return Err(AudioError::InvalidFormat(format!(
+            "valid bits {} exceed container size {}",
+            valid_bits_per_sample, bits_per_sample
+        )));
+    }
+
+    Ok(WavFormat {
+        format_tag,
+        extensible: raw_tag == WAVE_FORMAT_EXTENSIBLE,
+        channels,
+        sample_rate,
+        byte_rate,
+        block_align,
+        bits_per_sample,
+        valid_bits_per_sample,
+        channel_mask,
+    })
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+/// 24bit リトルエンディアンを符号拡張して i32 に変換
+fn read_i24(b: &[u8]) -> i32 {
+    (((b[0] as u32) << 8 | (b[1] as u32) << 16 | (b[2] as u32) << 24) as i32) >> 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::audio::cpal_backend::CpalAudioBackend;
+
+    /// 任意の fmt / 追加チャンクから WAV を組み立てる
+    fn build_wav(fmt: &[u8], extra: &[(&[u8; 4], &[u8])], data: &[u8]) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(b"WAVE");
+        let mut push_chunk = |id: &[u8; 4], content: &[u8]| {
+            body.extend_from_slice(id);
+            body.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            body.extend_from_slice(content);
+            if content.len() % 2 == 1 {
+                body.push(0);
+            }
+        };
+        push_chunk(b"fmt ", fmt);
+        for (id, content) in extra {
+            push_chunk(id, content);
+        }
+        push_chunk(b"data", data);
+
+        let mut wav = b"RIFF".to_vec();
+        wav.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&body);
+        wav
+    }
+
+    fn fmt_chunk(tag: u16, channels: u16, rate: u32, bits: u16) -> Vec<u8> {
+        let align = channels * bits / 8;
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&tag.to_le_bytes());
+        fmt.extend_from_slice(&channels.to_le_bytes());
+        fmt.extend_from_slice(&rate.to_le_bytes());
+        fmt.extend_from_slice(&(rate * align as u32).to_le_bytes());
+        fmt.extend_from_slice(&align.to_le_bytes());
+        fmt.extend_from_slice(&bits.to_le_bytes());
+        fmt
+    }
+
+    fn extensible_fmt(channels: u16, rate: u32, bits: u16, valid: u16, sub: u16) -> Vec<u8> {
+        let mut fmt = fmt_chunk(WAVE_FORMAT_EXTENSIBLE, channels, rate, bits);
+        fmt.extend_from_slice(&22u16.to_le_bytes()); // cbSize
+        fmt.extend_from_slice(&valid.to_le_bytes());
+        fmt.extend_from_slice(&0x3u32.to_le_bytes()); // FL | FR
+        fmt.extend_from_slice(&sub.to_le_bytes());
+        fmt.extend_from_slice(&[
+            0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71,
+        ]);
+        fmt
+    }
+
+    #[test]
+    fn test_roundtrip_with_writer() {
+        let samples: Vec<i16> = vec![0, 1000, -1000, i16::MAX, i16::MIN, 42];
+        let wav = CpalAudioBackend::combine_wav_data(&samples, 48000, 2).unwrap();
+
+        let parsed = WavAudio::parse(&wav).unwrap();
+        assert_eq!(parsed.format.format_tag, WAVE_FORMAT_PCM);
+        assert!(!parsed.format.extensible);
+        assert_eq!(parsed.format.channels, 2);
+        assert_eq!(parsed.format.sample_rate, 48000);
+        assert_eq!(parsed.format.bits_per_sample, 16);
+        assert_eq!(parsed.frames(), 3);
+        assert_eq!(parsed.samples_i16(), samples);
+        assert_eq!(parsed.duration(), Duration::from_secs_f64(3.0 / 48000.0));
+    }
+
+    #[test]
+    fn test_extensible_with_extra_chunks() {
+        let fmt = extensible_fmt(2, 44100, 24, 24, WAVE_FORMAT_PCM);
+        // 24bit: 1 フレーム = L(+0.5) R(-0.5)
+        let data = [0x00, 0x00, 0x40, 0x00, 0x00, 0xC0];
+        let wav = build_wav(
+            &fmt,
+            &[(b"LIST", b"INFOodd"), (b"fact", &[1, 0, 0, 0])],
+            &data,
+        );
+
+        let parsed = WavAudio::parse(&wav).unwrap();
+        assert!(parsed.format.extensible);
+        assert_eq!(parsed.format.format_tag, WAVE_FORMAT_PCM);
+        assert_eq!(parsed.format.channel_mask, Some(0x3));
+        assert_eq!(parsed.format.valid_bits_per_sample, 24);
+        assert_eq!(parsed.samples_f32(), vec![0.5, -0.5]);
+        assert_eq!(parsed.samples_i16(), vec![0x4000, -0x4000]);
+        assert_eq!(parsed.to_mono_f32(), vec![0.0]);
+    }
+
+    #[test]
+    fn test_float_and_8bit() {
+        let data: Vec<u8> = [0.25f32, -1.0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let wav = build_wav(&fmt_chunk(WAVE_FORMAT_IEEE_FLOAT, 1, 16000, 32), &[], &data);
+        let parsed = WavAudio::parse(&wav).unwrap();
+        assert_eq!(parsed.format.encoding(), SampleEncoding::Float);
+        assert_eq!(parsed.samples_f32(), vec![0.25, -1.0]);
+
+        let wav = build_wav(&fmt_chunk(WAVE_FORMAT_PCM, 1, 8000, 8), &[], &[128, 255, 0]);
+        let parsed = WavAudio::parse(&wav).unwrap();
+        assert_eq!(parsed.samples_i16(), vec![0, 127 << 8, -128 << 8]);
+    }
+
+    #[test]
+    fn test_rejects_non_wav() {
+        assert_eq!(
+            WavAudio::parse(b"RIFF\0\0\0\0AVI "),
+            Err(AudioError::NotWav)
+        );
+        assert_eq!(
+            WavAudio::parse(b"RIF"),
+            Err(AudioError::Truncated {
+                expected: 12,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_rejects_truncated_data() {
+        let samples: Vec<i16> = vec![1, 2, 3, 4];
+        let wav = CpalAudioBackend::combine_wav_data(&samples, 16000, 1).unwrap();
+
+        // 末尾が欠けたデータ
+        let cut = &wav[..wav.len() - 2];
+        assert!(matches!(
+            WavAudio::parse(cut),
+            Err(AudioError::Truncated { .. })
+        ));
+
+        // RIFF サイズは正しいが data チャンクの宣言長が大きすぎる
+        let mut broken = wav.clone();
+        broken[40..44].copy_from_slice(&100u32.to_le_bytes());
+        assert!(matches!(
+            WavAudio::parse(&broken),
+            Err(AudioError::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_missing_chunks() {
+        let mut no_data = b"RIFF".to_vec();
+        let fmt = fmt_chunk(WAVE_FORMAT_PCM, 1, 16000, 16);
+        no_data.extend_from_slice(&(4 + 8 + fmt.len() as u32).to_le_bytes());
+        no_data.extend_from_slice(b"WAVEfmt ");
+        no_data.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+        no_data.extend_from_slice(&fmt);
+        assert_eq!(
+            WavAudio::parse(&no_data),
+            Err(AudioError::MissingChunk("data"))
+        );
+
+        let mut no_fmt = b"RIFF".to_vec();
+        no_fmt.extend_from_slice(&12u32.to_le_bytes());
+        no_fmt.extend_from_slice(b"WAVEdata\0\0\0\0");
+        assert_eq!(
+            WavAudio::parse(&no_fmt),
+            Err(AudioError::MissingChunk("fmt "))
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_format() {
+        // block_align が channels * bits と一致しない
+        let mut fmt = fmt_chunk(WAVE_FORMAT_PCM, 2, 16000, 16);
+        fmt[12..14].copy_from_slice(&2u16.to_le_bytes());
+        let wav = build_wav(&fmt, &[], &[0, 0, 0, 0]);
+        assert!(matches!(
+            WavAudio::parse(&wav),
+            Err(AudioError::InvalidFormat(_))
+        ));
+
+        // data 長が block_align の倍数でない
+        let wav = build_wav(&fmt_chunk(WAVE_FORMAT_PCM, 2, 16000, 16), &[], &[0, 0, 0]);
+        assert!(matches!(
+            WavAudio::parse(&wav),
+            Err(AudioError::InvalidFormat(_))
+        ));
+
+        // チャンネル数 0
+        let wav = build_wav(&fmt_chunk(WAVE_FORMAT_PCM, 0, 16000, 16), &[], &[]);
+        assert!(matches!(
+            WavAudio::parse(&wav),
+            Err(AudioError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unsupported_encoding() {
+        // μ-law
+        let wav = build_wav(&fmt_chunk(0x0007, 1, 8000, 8), &[], &[0]);
+        assert_eq!(
+            WavAudio::parse(&wav),
+            Err(AudioError::UnsupportedFormat {
+                format_tag: 0x0007,
+                bits_per_sample: 8
+            })
+        );
+
+        // EXTENSIBLE の SubFormat が PCM / float 以外
+        let fmt = extensible_fmt(1, 16000, 16, 16, 0x0002);
+        let wav = build_wav(&fmt, &[], &[0, 0]);
+        assert!(matches!(
+            WavAudio::parse(&wav),
+            Err(AudioError::UnsupportedFormat {
+                format_tag: 0x0002,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,358 @@
+//! 転写結果のディスクキャッシュ
+//!
+//! 音声データ・モデル・言語・プロンプトのハッシュをキーに、転写結果（辞書変換前）を
+//! JSON ファイルとして保存します。同じ音声の再転写（再試行・同じファイルの再実行など）では
+//! API を呼ばずにキャッシュを返します。
+//!
+//! 合計サイズが上限を超えた場合は、最後に使われた時刻（ファイルの更新時刻）が古いものから削除します。
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use sha2::{Digest, Sha256};
+
+use crate::application::TranscriptionOptions;
+use crate::domain::transcript::Transcript;
+use crate::infrastructure::config::default_cache_dir;
+
+/// 既定のキャッシュサイズ上限（MB）
+pub const DEFAULT_CACHE_MAX_MB: u64 = 100;
+
+/// キャッシュ設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheConfig {
+    /// キャッシュを使うか
+    pub enabled: bool,
+    /// 保存先（`None` ならデータディレクトリ配下の `cache/`）
+    pub dir: Option<PathBuf>,
+    /// 合計サイズの上限（バイト）
+    pub max_bytes: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            max_bytes: DEFAULT_CACHE_MAX_MB * 1024 * 1024,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// 環境変数から設定を読み込む
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("VOICE_INPUT_CACHE")
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(defaults.enabled),
+            dir: std::env::var("VOICE_INPUT_CACHE_DIR")
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .map(PathBuf::from),
+            max_bytes: std::env::var("VOICE_INPUT_CACHE_MAX_MB")
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .map(|mb| mb * 1024 * 1024)
+                .unwrap_or(defaults.max_bytes),
+        }
+    }
+
+    /// キャッシュの保存先
+    pub fn dir(&self) -> PathBuf {
+        self.dir.clone().unwrap_or_else(default_cache_dir)
+    }
+
+    /// 設定に従ってキャッシュを開く
+    pub fn open(&self) -> TranscriptionCache {
+        TranscriptionCache::new(self.dir(), self.max_bytes)
+    }
+}
+
+/// キャッシュの利用状況
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheStats {
+    /// 保存されている転写結果の数
+    pub entries: usize,
+    /// 合計サイズ（バイト）
+    pub bytes: u64,
+}
+
+/// 転写結果のディスクキャッシュ
+#[derive(Debug, Clone)]
+pub struct TranscriptionCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    /// 転写モデルの識別子（プロバイダー・モデル・エンドポイントが変われば別のキーになる）
+    namespace: String,
+    /// プロファイルもキーに含めるか（プロファイルで転写するプロバイダーが変わる場合）
+    by_profile: bool,
+}
+
+impl TranscriptionCache {
+    /// 保存先とサイズ上限を指定して作成
+    pub fn new(dir: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes,
+            namespace: String::new(),
+            by_profile: false,
+        }
+    }
+
+    /// 転写モデルの識別子を設定
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// プロファイルもキーに含める（振り分け方針でプロファイルごとにプロバイダーを選ぶ場合）
+    pub fn with_profile_key(mut self, by_profile: bool) -> Self {
+        self.by_profile = by_profile;
+        self
+    }
+
+    /// 保存先ディレクトリ
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// サイズ上限（バイト）
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// 音声データと転写オプションからキャッシュキーを計算
+    ///
+    /// 言語は API に送る言語コード（自動判定なら空）で区別する。
+    pub fn key(&self, audio: &[u8], options: &TranscriptionOptions) -> String {
+        let mut fields = vec![
+            self.namespace.as_bytes(),
+            options.language_code().unwrap_or("").as_bytes(),
+            options.prompt.as_deref().unwrap_or("").as_bytes(),
+        ];
+        if self.by_profile {
+            fields.push(options.profile.as_deref().unwrap_or("").as_bytes());
+        }
+        fields.push(audio);
+
+        let mut hasher = Sha256::new();
+        for field in fields {
+            // 区切りの曖昧さをなくすため長さを前置する
+            hasher.update((field.len() as u64).to_le_bytes());
+            hasher.update(field);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// キャッシュされた転写結果を取得（読み込めないエントリは削除して `None`）
+    pub fn get(&self, key: &str) -> Option<Transcript> {
+        let path = self.entry_path(key);
+        let data = fs::read(&path).ok()?;
+        match serde_json::from_slice(&data) {
+            Ok(transcript) => {
+                // 最近使われたものを残すため更新時刻を進める
+                let _ = fs::File::options()
+                    .write(true)
+                    .open(&path)
+                    .and_then(|f| f.set_modified(SystemTime::now()));
+                Some(transcript)
+            }
+            Err(_) => {
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// 転写結果を保存し、上限を超えた分を古い順に削除
+    pub fn put(&self, key: &str, transcript: &Transcript) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.entry_path(key);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(transcript)?)?;
+        fs::rename(tmp, &path)?;
+        self.evict()
+    }
+
+    /// 利用状況を取得
+    pub fn stats(&self) -> io::Result<CacheStats> {
+        let entries = self.entries()?;
+        Ok(CacheStats {
+            entries: entries.len(),
+            bytes: entries.iter().map(|(_, _, len)| len).sum(),
+        })
+    }
+
+    /// すべてのエントリを削除し、削除した数を返す
+    pub fn clear(&self) -> io::Result<usize> {
+        let entries = self.entries()?;
+        for (path, _, _) in &entries {
+            fs::remove_file(path)?;
+        }
+        Ok(entries.len())
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// 合計サイズが上限以下になるまで最終使用時刻の古いものから削除
+    fn evict(&self) -> io::Result<()> {
+        let mut entries = self.entries()?;
+        let mut total: u64 = entries.iter().map(|(_, _, len)| len).sum();
+        if total <= self.max_bytes {
+            return Ok(());
+        }
+        entries.sort_by_key(|(_, modified, _)| *modified);
+        for (path, _, len) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            fs::remove_file(&path)?;
+            total -= len;
+        }
+        Ok(())
+    }
+
+    /// エントリの一覧（パス・更新時刻・サイズ）
+    fn entries(&self) -> io::Result<Vec<(PathBuf, SystemTime, u64)>> {
+        let dir = match fs::read_dir(&self.dir) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut entries = Vec::new();
+        for entry in dir {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let meta = fs::metadata(&path)?;
+            let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            entries.push((path, modified, meta.len()));
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn options(language: &str, prompt: Option<&str>) -> TranscriptionOptions {
+        TranscriptionOptions {
+            language: Some(language.to_string()),
+            prompt: prompt.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_key_depends_on_audio_model_language_and_prompt() {
+        let cache = TranscriptionCache::new("/tmp/unused", 0).with_namespace("openai:whisper-1");
+        let base = cache.key(b"audio", &options("ja", None));
+
+        assert_eq!(base, cache.key(b"audio", &options("ja", None)));
+        assert_ne!(base, cache.key(b"other", &options("ja", None)));
+        assert_ne!(base, cache.key(b"audio", &options("en", None)));
+        assert_ne!(base, cache.key(b"audio", &options("ja", Some("議事録"))));
+        assert_ne!(
+            base,
+            cache
+                .clone()
+                .with_namespace("openai:gpt-4o-transcribe")
+                .key(b"audio", &options("ja", None))
+        );
+        // 自動判定の指定方法の違いは同じキーになる
+        assert_eq!(
+            cache.key(b"audio", &options("auto", None)),
+            cache.key(b"audio", &TranscriptionOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_key_depends_on_profile_when_routed() {
+        let with_profile = |profile: &str| TranscriptionOptions {
+            profile: Some(profile.to_string()),
+            ..options("ja", None)
+        };
+        let cache = TranscriptionCache::new("/tmp/unused", 0);
+        assert_eq!(
+            cache.key(b"audio", &with_profile("chat")),
+            cache.key(b"audio", &with_profile("docs"))
+        );
+
+        let cache = cache.with_profile_key(true);
+        assert_ne!(
+            cache.key(b"audio", &with_profile("chat")),
+            cache.key(b"audio", &with_profile("docs"))
+        );
+    }
+
+    #[test]
+    fn test_put_get_and_clear() {
+        let tmp = TempDir::new().unwrap();
+        let cache = TranscriptionCache::new(tmp.path().join("cache"), 1024 * 1024);
+        let key = cache.key(b"audio", &options("ja", None));
+
+        assert_eq!(cache.get(&key), None);
+        assert_eq!(cache.stats().unwrap().entries, 0);
+
+        let transcript = Transcript::new("こんにちは").with_provider("openai");
+        cache.put(&key, &transcript).unwrap();
+        assert_eq!(cache.get(&key), Some(transcript));
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.entries, 1);
+        assert!(stats.bytes > 0);
+
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert_eq!(cache.get(&key), None);
+    }
+
+    #[test]
+    fn test_corrupted_entry_is_discarded() {
+        let tmp = TempDir::new().unwrap();
+        let cache = TranscriptionCache::new(tmp.path(), 1024 * 1024);
+        fs::write(cache.entry_path("broken"), b"{not json").unwrap();
+
+        assert_eq!(cache.get("broken"), None);
+        assert_eq!(cache.stats().unwrap().entries, 0);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let tmp = TempDir::new().unwrap();
+        let entry_size = serde_json::to_vec(&Transcript::new("first")).unwrap().len() as u64;
+        // 2 件分だけ入る上限
+        let cache = TranscriptionCache::new(tmp.path(), entry_size * 2);
+        let age = |key: &str, secs: u64| {
+            fs::File::options()
+                .write(true)
+                .open(cache.entry_path(key))
+                .unwrap()
+                .set_modified(SystemTime::now() - Duration::from_secs(secs))
+                .unwrap();
+        };
+
+        cache.put("a", &Transcript::new("first")).unwrap();
+        age("a", 60);
+        cache.put("b", &Transcript::new("secnd")).unwrap();
+        age("b", 30);
+
+        // a を使うと b が最も古くなる
+        assert!(cache.get("a").is_some());
+        cache.put("c", &Transcript::new("third")).unwrap();
+
+        assert!(cache.get("a").is_some());
+        assert_eq!(cache.get("b"), None);
+        assert!(cache.get("c").is_some());
+        assert_eq!(cache.stats().unwrap().entries, 2);
+    }
+}
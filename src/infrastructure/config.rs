@@ -1,11 +1,29 @@
+use crate::domain::pipeline::StageSpec;
+use crate::domain::profile::Profile;
+use crate::domain::routing::RoutingPolicy;
 use crate::utils::config::EnvConfig;
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
-use std::{fs, io, path::PathBuf};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct AppConfig {
     pub dict_path: Option<String>,
+    /// 名前付きの出力プロファイル
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// 転写の料金表（モデル名またはプロバイダー名 → USD / 音声 1 分）。既定の料金表を上書きする
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prices: BTreeMap<String, f64>,
+    /// 無音時の定型句として組み込みの一覧に追加する文言
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phantom_phrases: Vec<String>,
+    /// 転写リクエストごとのモデル・プロバイダーの振り分け方針
+    #[serde(default, skip_serializing_if = "RoutingPolicy::is_empty")]
+    pub routing: RoutingPolicy,
+    /// 転写後のテキスト処理の段（省略時は辞書変換・書き直し・翻訳の既定のパイプライン）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<Vec<StageSpec>>,
 }
 
 fn data_dir() -> PathBuf {
@@ -31,6 +49,18 @@ pub fn default_dict_path() -> PathBuf {
     data_dir().join("dictionary.json")
 }
 
+pub fn default_cache_dir() -> PathBuf {
+    data_dir().join("cache")
+}
+
+pub fn default_queue_dir() -> PathBuf {
+    data_dir().join("queue")
+}
+
+pub fn default_usage_path() -> PathBuf {
+    data_dir().join("usage.jsonl")
+}
+
 impl AppConfig {
     pub fn load() -> Self {
         let path = config_path();
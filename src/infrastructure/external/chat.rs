@@ -0,0 +1,253 @@
+//! Chat Completions 互換 API のクライアント
+//!
+//! 転写後のテキストを LLM で加工する処理（翻訳・書き直し）で使います。
+//! `VOICE_INPUT_LLM_BASE_URL` を指定すると、OpenAI 互換のセルフホストサーバー
+//! （Ollama, LM Studio, vLLM 等）に送信先を切り替えられます。
+//! 未指定の項目は転写と同じ OpenAI 互換 API の設定を使います。
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::application::traits::{Rewriter, Translator};
+use crate::error::{Result, VoiceInputError};
+use crate::infrastructure::external::openai::{DEFAULT_BASE_URL, OpenAiError, parse_retry_after};
+use crate::utils::config::EnvConfig;
+
+/// デフォルトのチャットモデル
+pub const DEFAULT_CHAT_MODEL: &str = "gpt-4o-mini";
+
+/// Chat Completions API の接続設定
+#[derive(Debug, Clone)]
+pub struct ChatConfig {
+    /// API のベース URL（例: `http://localhost:11434/v1`）
+    pub base_url: String,
+    /// API キー（セルフホストサーバーでは不要な場合がある）
+    pub api_key: Option<String>,
+    /// チャットモデル
+    pub model: String,
+    /// API キーを送るヘッダー名（`Authorization` の場合のみ `Bearer` を付与）
+    pub auth_header: String,
+    /// 1 回のリクエストのタイムアウト
+    pub timeout: Duration,
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: None,
+            model: DEFAULT_CHAT_MODEL.to_string(),
+            auth_header: "Authorization".to_string(),
+            timeout: Duration::from_secs(20),
+        }
+    }
+}
+
+impl ChatConfig {
+    /// 環境変数から設定を読み込む
+    pub fn from_env() -> Self {
+        let env = EnvConfig::get();
+        let defaults = Self::default();
+        let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+        Self {
+            base_url: non_empty(std::env::var("VOICE_INPUT_LLM_BASE_URL").ok())
+                .or_else(|| non_empty(env.openai_base_url.clone()))
+                .unwrap_or(defaults.base_url),
+            api_key: non_empty(std::env::var("VOICE_INPUT_LLM_API_KEY").ok())
+                .or_else(|| env.openai_api_key.clone()),
+            model: non_empty(std::env::var("VOICE_INPUT_LLM_MODEL").ok()).unwrap_or(defaults.model),
+            auth_header: non_empty(env.openai_auth_header.clone()).unwrap_or(defaults.auth_header),
+            timeout: std::env::var("VOICE_INPUT_LLM_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.timeout),
+        }
+    }
+
+    /// モデルを上書き
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// OpenAI 公式 API を利用する設定か
+    pub fn is_official(&self) -> bool {
+        self.base_url.trim_end_matches('/') == DEFAULT_BASE_URL
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Chat Completions 互換 API のクライアント
+pub struct ChatClient {
+    config: ChatConfig,
+    client: reqwest::Client,
+}
+
+impl ChatClient {
+    /// 接続設定を指定して作成（公式 API では API キーが必須）
+    pub fn new(config: ChatConfig) -> Result<Self> {
+        if config.api_key.is_none() && config.is_official() {
+            return Err(VoiceInputError::OpenAiConfigError(
+                "OPENAI_API_KEY environment variable is not set".to_string(),
+            ));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| VoiceInputError::OpenAiConfigError(e.to_string()))?;
+        Ok(Self { config, client })
+    }
+
+    /// 接続設定
+    pub fn config(&self) -> &ChatConfig {
+        &self.config
+    }
+
+    /// システムメッセージとユーザーメッセージを送り、応答テキストを返す
+    pub async fn complete(&self, system: &str, user: &str) -> Result<String> {
+        let url = format!(
+            "{}/chat/completions",
+            self.config.base_url.trim_end_matches('/')
+        );
+        let body = json!({
+            "model": self.config.model,
+            "temperature": 0,
+            "messages": [
+                {"role": "system", "content": system},
+                {"role": "user", "content": user},
+            ],
+        });
+
+        let mut request = self.client.post(url).json(&body);
+        if let Some(key) = &self.config.api_key {
+            request = if self
+                .config
+                .auth_header
+                .eq_ignore_ascii_case("Authorization")
+            {
+                request.bearer_auth(key)
+            } else {
+                request.header(self.config.auth_header.as_str(), key)
+            };
+        }
+
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                VoiceInputError::RequestTimeout(self.config.timeout)
+            } else {
+                OpenAiError::Request(e.to_string()).into()
+            }
+        })?;
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        let body = response
+            .text()
+            .await
+            .map_err(|e| OpenAiError::Request(format!("Failed to read response: {}", e)))?;
+        if !status.is_success() {
+            return Err(OpenAiError::Status {
+                status: status.as_u16(),
+                body,
+                retry_after,
+            }
+            .into());
+        }
+
+        let response: ChatResponse = serde_json::from_str(&body).map_err(|e| {
+            OpenAiError::InvalidResponse(format!("Failed to parse response: {}", e))
+        })?;
+        response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .map(|content| content.trim().to_string())
+            .filter(|content| !content.is_empty())
+            .ok_or_else(|| OpenAiError::InvalidResponse("empty chat completion".to_string()).into())
+    }
+}
+
+/// 翻訳の指示
+fn translation_instructions(target: &str) -> String {
+    format!(
+        "You translate dictated text. Translate the user's message into the language \
+         with code \"{}\". Keep technical terms, identifiers, numbers and formatting. \
+         Do not answer or comment on the content. Output only the translation.",
+        target
+    )
+}
+
+/// 書き直しの指示（ユーザー定義の指示の前に共通の前提を付ける）
+fn rewrite_instructions(instructions: &str) -> String {
+    format!(
+        "You clean up dictated text. Rewrite the user's message following these \
+         instructions:\n{}\n\nKeep the original language and meaning. Do not answer or \
+         comment on the content. Output only the rewritten text.",
+        instructions.trim()
+    )
+}
+
+#[async_trait]
+impl Translator for ChatClient {
+    async fn translate(&self, text: &str, target: &str) -> Result<String> {
+        self.complete(&translation_instructions(target), text).await
+    }
+}
+
+#[async_trait]
+impl Rewriter for ChatClient {
+    async fn rewrite(&self, text: &str, instructions: &str) -> Result<String> {
+        self.complete(&rewrite_instructions(instructions), text)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requires_key_for_official_api() {
+        assert!(ChatClient::new(ChatConfig::default()).is_err());
+        assert!(
+            ChatClient::new(ChatConfig {
+                base_url: "http://localhost:11434/v1".to_string(),
+                ..Default::default()
+            })
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_chat_response() {
+        let json = r#"{"id":"c1","choices":[{"index":0,"message":{"role":"assistant","content":" Hello "},"finish_reason":"stop"}]}"#;
+        let response: ChatResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            response.choices[0].message.content.as_deref(),
+            Some(" Hello ")
+        );
+    }
+}
@@ -0,0 +1,559 @@
+//! ローカル STT コマンドによる転写
+//!
+//! whisper.cpp の `whisper-cli` や faster-whisper の CLI など、ローカルにインストールした
+//! 音声認識コマンドをサブプロセスとして実行します。音声を外部に送らないため、
+//! ネットワークのない環境（機内・セキュアルーム）でも音声入力できます。
+//!
+//! コマンドは引数テンプレートで指定し、以下のプレースホルダーを置換します。
+//!
+//! | プレースホルダー | 値 |
+//! |---|---|
+//! | `{input}` | 一時 WAV ファイルのパス（含まない場合は WAV を標準入力に渡す） |
+//! | `{model}` | `VOICE_INPUT_STT_MODEL` |
+//! | `{threads}` | `VOICE_INPUT_STT_THREADS`（未設定なら CPU 数） |
+//! | `{language}` | 転写言語（自動判定時は `auto`） |
+//! | `{prompt}` | 転写プロンプト（なければ空文字） |
+
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::application::traits::TranscriptionClient;
+use crate::application::{Transcript, TranscriptionOptions};
+use crate::domain::transcript::{TranscriptSegment, join_segments};
+use crate::error::{Result, VoiceInputError};
+use crate::infrastructure::audio::cpal_backend::AudioData;
+
+/// whisper.cpp（`whisper-cli`）の既定コマンド
+pub const WHISPER_CPP_COMMAND: &str =
+    "whisper-cli -m {model} -t {threads} -l {language} --prompt {prompt} -nt -np -f {input}";
+
+/// コマンドの出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// 標準出力のテキストをそのまま転写結果とする
+    Text,
+    /// 標準出力の JSON（`{"text": ...}` または whisper.cpp の `transcription` 配列）
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" | "txt" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!("unknown output format: {}", other)),
+        }
+    }
+}
+
+/// ローカル STT コマンドの設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandSttConfig {
+    /// プログラムと引数のテンプレート
+    pub command: Vec<String>,
+    /// モデルファイルのパス
+    pub model: Option<String>,
+    /// 推論スレッド数
+    pub threads: usize,
+    /// 出力形式
+    pub output: OutputFormat,
+}
+
+impl CommandSttConfig {
+    /// コマンド文字列（シェル風のクォートに対応）から作成
+    pub fn new(command: &str) -> Result<Self> {
+        let command = split_command(command)?;
+        if command.is_empty() {
+            return Err(VoiceInputError::ConfigMissingValue(
+                "VOICE_INPUT_STT_COMMAND".to_string(),
+            ));
+        }
+        Ok(Self {
+            command,
+            model: None,
+            threads: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4),
+            output: OutputFormat::Text,
+        })
+    }
+
+    /// 環境変数から設定を読み込む（`default_command` はコマンド未指定時に使う）
+    pub fn from_env(default_command: Option<&str>) -> Result<Self> {
+        let command = std::env::var("VOICE_INPUT_STT_COMMAND")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .or_else(|| default_command.map(str::to_string))
+            .ok_or_else(|| {
+                VoiceInputError::ConfigMissingValue("VOICE_INPUT_STT_COMMAND".to_string())
+            })?;
+
+        let mut config = Self::new(&command)?;
+        config.model = std::env::var("VOICE_INPUT_STT_MODEL")
+            .ok()
+            .filter(|s| !s.trim().is_empty());
+        if let Some(threads) = std::env::var("VOICE_INPUT_STT_THREADS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.threads = threads;
+        }
+        if let Ok(output) = std::env::var("VOICE_INPUT_STT_OUTPUT") {
+            config.output = output.parse().map_err(VoiceInputError::ConfigInitError)?;
+        }
+        Ok(config)
+    }
+
+    /// モデルパスを指定
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// スレッド数を指定
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// 出力形式を指定
+    pub fn with_output(mut self, output: OutputFormat) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// 実行するプログラム名
+    pub fn program(&self) -> &str {
+        &self.command[0]
+    }
+
+    /// WAV を一時ファイル経由で渡すか（`false` なら標準入力）
+    pub fn uses_input_file(&self) -> bool {
+        self.command.iter().any(|arg| arg.contains("{input}"))
+    }
+
+    /// 実行ファイルとモデルファイルが存在するか確認（ヘルスチェック用）
+    pub fn check(&self) -> std::result::Result<(), String> {
+        self.validate().map_err(|e| e.to_string())?;
+        let program = PathBuf::from(self.program());
+        let found = if program.components().count() > 1 {
+            program.is_file()
+        } else {
+            std::env::var_os("PATH").is_some_and(|paths| {
+                std::env::split_paths(&paths).any(|dir| dir.join(&program).is_file())
+            })
+        };
+        if !found {
+            return Err(format!("{} not found", self.program()));
+        }
+        match &self.model {
+            Some(model) if !PathBuf::from(model).is_file() => {
+                Err(format!("model not found: {}", model))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 設定の整合性を確認
+    fn validate(&self) -> Result<()> {
+        if self.model.is_none() && self.command.iter().any(|arg| arg.contains("{model}")) {
+            return Err(VoiceInputError::ConfigMissingValue(
+                "VOICE_INPUT_STT_MODEL".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// プレースホルダーを置換した引数
+    fn render_args(&self, input: Option<&str>, options: &TranscriptionOptions) -> Vec<String> {
+        let threads = self.threads.to_string();
+        self.command[1..]
+            .iter()
+            .map(|arg| {
+                arg.replace("{input}", input.unwrap_or(""))
+                    .replace("{model}", self.model.as_deref().unwrap_or(""))
+                    .replace("{threads}", &threads)
+                    .replace("{language}", options.language_code().unwrap_or("auto"))
+                    .replace("{prompt}", options.prompt.as_deref().unwrap_or(""))
+            })
+            .collect()
+    }
+}
+
+/// ローカル STT コマンドを実行する転写クライアント
+pub struct CommandTranscriptionClient {
+    config: CommandSttConfig,
+}
+
+impl CommandTranscriptionClient {
+    /// 新しいクライアントを作成（`{model}` を使うのにモデル未指定ならエラー）
+    pub fn new(config: CommandSttConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    /// 設定を取得
+    pub fn config(&self) -> &CommandSttConfig {
+        &self.config
+    }
+
+    async fn run(&self, audio: AudioData, options: &TranscriptionOptions) -> Result<String> {
+        // {input} を使う場合は一時ファイルに書き出す（drop 時に削除される）
+        let temp_file = if self.config.uses_input_file() {
+            Some(write_temp_wav(&audio.0)?)
+        } else {
+            None
+        };
+        let temp_path = temp_file.as_ref().map(|f| f.path());
+
+        let input = temp_path.map(|p| p.to_string_lossy().into_owned());
+        let mut child = Command::new(self.config.program())
+            .args(self.config.render_args(input.as_deref(), options))
+            .stdin(if temp_path.is_some() {
+                Stdio::null()
+            } else {
+                Stdio::piped()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                VoiceInputError::TranscriptionFailed(format!(
+                    "failed to run {}: {}",
+                    self.config.program(),
+                    e
+                ))
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // 出力の読み取りと並行して書き込まないとパイプが詰まるため別タスクで渡す
+            let wav = audio.0;
+            tokio::spawn(async move {
+                let _ = stdin.write_all(&wav).await;
+            });
+        }
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(VoiceInputError::TranscriptionFailed(format!(
+                "{} exited with {}: {}",
+                self.config.program(),
+                output.status,
+                stderr.trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+#[async_trait]
+impl TranscriptionClient for CommandTranscriptionClient {
+    async fn transcribe(
+        &self,
+        audio: AudioData,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcript> {
+        let stdout = self.run(audio, options).await?;
+        match self.config.output {
+            OutputFormat::Text => Ok(Transcript::new(join_segments(stdout.lines()))),
+            OutputFormat::Json => parse_json_output(&stdout),
+        }
+    }
+}
+
+/// コマンドの JSON 出力
+#[derive(Debug, Deserialize)]
+struct JsonOutput {
+    text: Option<String>,
+    language: Option<String>,
+    /// OpenAI `verbose_json` 形式のセグメント（時刻は秒）
+    #[serde(default)]
+    segments: Vec<JsonTimedSegment>,
+    /// whisper.cpp（`-oj`）形式のセグメント
+    #[serde(default)]
+    transcription: Vec<JsonSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonTimedSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+    #[serde(default)]
+    no_speech_prob: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonSegment {
+    text: String,
+    /// whisper.cpp のセグメント位置（ミリ秒）
+    offsets: Option<JsonOffsets>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonOffsets {
+    from: u64,
+    to: u64,
+}
+
+fn parse_json_output(stdout: &str) -> Result<Transcript> {
+    let output: JsonOutput = serde_json::from_str(stdout.trim()).map_err(|e| {
+        VoiceInputError::TranscriptionFailed(format!("failed to parse command output: {}", e))
+    })?;
+    let text = match output.text {
+        Some(text) => text.trim().to_string(),
+        None if !output.segments.is_empty() => {
+            join_segments(output.segments.iter().map(|s| s.text.as_str()))
+        }
+        None => join_segments(output.transcription.iter().map(|s| s.text.as_str())),
+    };
+    let segments = if output.segments.is_empty() {
+        output
+            .transcription
+            .into_iter()
+            .filter_map(|s| {
+                let offsets = s.offsets?;
+                Some(TranscriptSegment::new(
+                    offsets.from as f64 / 1000.0,
+                    offsets.to as f64 / 1000.0,
+                    s.text.trim(),
+                ))
+            })
+            .collect()
+    } else {
+        output
+            .segments
+            .into_iter()
+            .map(|s| TranscriptSegment {
+                start: s.start,
+                end: s.end,
+                text: s.text.trim().to_string(),
+                avg_logprob: s.avg_logprob,
+                no_speech_prob: s.no_speech_prob,
+            })
+            .collect()
+    };
+    Ok(Transcript {
+        text,
+        language: output.language,
+        segments,
+        ..Default::default()
+    })
+}
+
+/// 音声を一時 WAV ファイルに書き出す
+///
+/// 共有の一時ディレクトリに他のユーザーが置いたシンボリックリンクをたどらないよう、
+/// 推測できない名前で新規に作成する（Unix では所有者だけが読み書きできる 0600）。
+fn write_temp_wav(audio: &[u8]) -> Result<tempfile::NamedTempFile> {
+    let write_error = |source| VoiceInputError::FileWriteError {
+        path: std::env::temp_dir().display().to_string(),
+        source,
+    };
+    let mut file = tempfile::Builder::new()
+        .prefix("voice_input_stt_")
+        .suffix(".wav")
+        .tempfile()
+        .map_err(write_error)?;
+    std::io::Write::write_all(&mut file, audio).map_err(write_error)?;
+    Ok(file)
+}
+
+/// シェル風にコマンド文字列を分割（クォートとバックスラッシュに対応）
+pub(crate) fn split_command(command: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_arg = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(VoiceInputError::ConfigInitError(format!(
+            "unterminated quote in command: {}",
+            command
+        )));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command(r#"whisper-cli -m "/models/ggml large.bin" -f {input} -l 'ja' a\ b ''"#)
+                .unwrap(),
+            vec![
+                "whisper-cli",
+                "-m",
+                "/models/ggml large.bin",
+                "-f",
+                "{input}",
+                "-l",
+                "ja",
+                "a b",
+                ""
+            ]
+        );
+        assert!(split_command("stt \"unterminated").is_err());
+        assert!(split_command("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_render_args() {
+        let config = CommandSttConfig::new(WHISPER_CPP_COMMAND)
+            .unwrap()
+            .with_model("/models/ggml-base.bin")
+            .with_threads(2);
+        assert!(config.uses_input_file());
+
+        let args = config.render_args(Some("/tmp/a.wav"), &TranscriptionOptions::default());
+        assert_eq!(
+            args,
+            vec![
+                "-m",
+                "/models/ggml-base.bin",
+                "-t",
+                "2",
+                "-l",
+                "auto",
+                "--prompt",
+                "",
+                "-nt",
+                "-np",
+                "-f",
+                "/tmp/a.wav"
+            ]
+        );
+
+        let options = TranscriptionOptions {
+            language: Some("ja".to_string()),
+            prompt: Some("議事録".to_string()),
+            ..Default::default()
+        };
+        let args = config.render_args(Some("/tmp/a.wav"), &options);
+        assert!(args.contains(&"ja".to_string()));
+        assert!(args.contains(&"議事録".to_string()));
+    }
+
+    #[test]
+    fn test_requires_model_when_referenced() {
+        let config = CommandSttConfig::new(WHISPER_CPP_COMMAND).unwrap();
+        assert!(matches!(
+            CommandTranscriptionClient::new(config),
+            Err(VoiceInputError::ConfigMissingValue(_))
+        ));
+
+        let stdin_only = CommandSttConfig::new("my-stt --lang {language}").unwrap();
+        assert!(!stdin_only.uses_input_file());
+        assert!(CommandTranscriptionClient::new(stdin_only).is_ok());
+    }
+
+    #[test]
+    fn test_check_reports_missing_program_and_model() {
+        let missing = CommandSttConfig::new("voice-input-no-such-stt {input}").unwrap();
+        assert!(missing.check().unwrap_err().contains("not found"));
+
+        let config = CommandSttConfig::new("sh -c 'cat > /dev/null'")
+            .unwrap()
+            .with_model("/no/such/model.bin");
+        assert!(config.check().unwrap_err().contains("model not found"));
+    }
+
+    #[test]
+    fn test_parse_json_output() {
+        let transcript = parse_json_output(r#"{"text":" hello ","language":"en"}"#).unwrap();
+        assert_eq!(transcript.text, "hello");
+        assert_eq!(transcript.language.as_deref(), Some("en"));
+
+        let whisper_cpp = r#"{"transcription":[{"text":" 音声"},{"text":"入力"}]}"#;
+        assert_eq!(parse_json_output(whisper_cpp).unwrap().text, "音声入力");
+
+        assert!(parse_json_output("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_json_output_segments() {
+        let whisper_cpp = r#"{"transcription":[
+            {"offsets":{"from":0,"to":1200},"text":" こんにちは。"},
+            {"offsets":{"from":1200,"to":3500},"text":" 音声入力です。"}
+        ]}"#;
+        let transcript = parse_json_output(whisper_cpp).unwrap();
+        assert_eq!(transcript.text, "こんにちは。音声入力です。");
+        assert_eq!(
+            transcript.segments,
+            vec![
+                TranscriptSegment::new(0.0, 1.2, "こんにちは。"),
+                TranscriptSegment::new(1.2, 3.5, "音声入力です。"),
+            ]
+        );
+
+        let verbose = r#"{"language":"en","segments":[
+            {"start":0.0,"end":1.5,"text":" Hello.","avg_logprob":-0.4},
+            {"start":1.5,"end":2.0,"text":" Bye."}
+        ]}"#;
+        let transcript = parse_json_output(verbose).unwrap();
+        assert_eq!(transcript.text, "Hello. Bye.");
+        assert_eq!(transcript.segments[0].avg_logprob, Some(-0.4));
+        assert_eq!(transcript.end_time(), Some(2.0));
+    }
+
+    #[test]
+    fn test_temp_wav_is_private_and_removed() {
+        let file = write_temp_wav(b"RIFF").unwrap();
+        let path = file.path().to_path_buf();
+        assert_eq!(std::fs::read(&path).unwrap(), b"RIFF");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        drop(file);
+        assert!(!path.exists());
+    }
+}
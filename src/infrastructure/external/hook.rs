@@ -0,0 +1,138 @@
+//! 外部コマンドによるテキスト処理（パイプラインのフック段）
+//!
+//! 転写テキストを標準入力で渡し、標準出力（前後の空白を除く）を処理結果とします。
+//! 環境変数 `VOICE_INPUT_LANGUAGE`・`VOICE_INPUT_PROFILE` で転写言語とプロファイル名を渡します。
+//! 失敗・タイムアウトした場合はテキストを変更しません。
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::application::Transcript;
+use crate::application::pipeline::ProcessContext;
+use crate::application::traits::TextProcessor;
+use crate::error::{Result, VoiceInputError};
+use crate::infrastructure::external::command_stt::split_command;
+
+/// 外部コマンドを実行する段
+pub struct CommandHookProcessor {
+    /// プログラムと引数
+    command: Vec<String>,
+    timeout: Duration,
+}
+
+impl CommandHookProcessor {
+    /// コマンド文字列（シェル風のクォートに対応）から作成
+    pub fn new(command: &str, timeout: Duration) -> Result<Self> {
+        let command = split_command(command)?;
+        if command.is_empty() {
+            return Err(VoiceInputError::ConfigMissingValue(
+                "pipeline hook command".to_string(),
+            ));
+        }
+        Ok(Self { command, timeout })
+    }
+
+    async fn run(&self, text: &str, context: &ProcessContext<'_>) -> Result<String> {
+        let program = &self.command[0];
+        let mut child = Command::new(program)
+            .args(&self.command[1..])
+            .env(
+                "VOICE_INPUT_LANGUAGE",
+                context.options.language_code().unwrap_or("auto"),
+            )
+            .env("VOICE_INPUT_PROFILE", context.profile_name().unwrap_or(""))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                VoiceInputError::SystemError(format!("failed to run {}: {}", program, e))
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // 出力の読み取りと並行して書き込まないとパイプが詰まるため別タスクで渡す
+            let text = text.to_string();
+            tokio::spawn(async move {
+                let _ = stdin.write_all(text.as_bytes()).await;
+            });
+        }
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(VoiceInputError::SystemError(format!(
+                "{} exited with {}: {}",
+                program,
+                output.status,
+                stderr.trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[async_trait]
+impl TextProcessor for CommandHookProcessor {
+    async fn process(
+        &self,
+        transcript: &mut Transcript,
+        context: &ProcessContext<'_>,
+    ) -> Result<()> {
+        transcript.text = tokio::time::timeout(self.timeout, self.run(&transcript.text, context))
+            .await
+            .unwrap_or(Err(VoiceInputError::RequestTimeout(self.timeout)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::TranscriptionOptions;
+    use crate::domain::profile::DictionaryStage;
+
+    async fn run_hook(command: &str, timeout: Duration) -> (Transcript, Result<()>) {
+        let options = TranscriptionOptions {
+            language: Some("ja".to_string()),
+            ..Default::default()
+        };
+        let context = ProcessContext {
+            options: &options,
+            profile: None,
+            translate: None,
+            dictionary_stage: DictionaryStage::Before,
+            dry_run: false,
+        };
+        let mut transcript = Transcript::new("hello world");
+        let hook = CommandHookProcessor::new(command, timeout).unwrap();
+        let result = hook.process(&mut transcript, &context).await;
+        (transcript, result)
+    }
+
+    #[tokio::test]
+    async fn test_hook_replaces_text_with_stdout() {
+        let (transcript, result) = run_hook(
+            r#"sh -c 'printf "%s [%s]\n" "$(tr a-z A-Z)" "$VOICE_INPUT_LANGUAGE"'"#,
+            Duration::from_secs(5),
+        )
+        .await;
+        result.unwrap();
+        assert_eq!(transcript.text, "HELLO WORLD [ja]");
+    }
+
+    #[tokio::test]
+    async fn test_hook_failure_keeps_text() {
+        let (transcript, result) = run_hook("sh -c 'exit 3'", Duration::from_secs(5)).await;
+        assert!(result.is_err());
+        assert_eq!(transcript.text, "hello world");
+
+        let (transcript, result) = run_hook("sleep 5", Duration::from_millis(50)).await;
+        assert!(matches!(result, Err(VoiceInputError::RequestTimeout(_))));
+        assert_eq!(transcript.text, "hello world");
+    }
+}
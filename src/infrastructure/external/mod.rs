@@ -1,6 +1,11 @@
+pub mod chat;
 pub mod clipboard;
+pub mod command_stt;
+pub mod hook;
 pub mod openai;
 pub mod openai_adapter;
+pub mod realtime;
 pub mod sound;
+pub mod stt_provider;
 pub mod text_input;
 pub mod text_input_subprocess;
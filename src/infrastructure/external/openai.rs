@@ -1,14 +1,104 @@
 //! OpenAI STT API ラッパ。
 //! WAV ファイルを multipart/form-data で転写エンドポイントに送信します。
+//! `OPENAI_BASE_URL` を指定すると、OpenAI 互換のセルフホストサーバー
+//! （faster-whisper-server, LocalAI, プロキシ等）に送信先を切り替えられます。
 use crate::infrastructure::audio::cpal_backend::AudioData;
 use crate::utils::config::EnvConfig;
 use reqwest::multipart;
 use serde::Deserialize;
+use std::time::Duration;
+
+/// OpenAI 公式 API のベース URL
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// デフォルトの転写モデル
+pub const DEFAULT_TRANSCRIBE_MODEL: &str = "gpt-4o-mini-transcribe";
+
+/// デフォルトの認証ヘッダー名
+const DEFAULT_AUTH_HEADER: &str = "Authorization";
+
+/// 転写 API の prompt パラメータで考慮されるトークン数の上限
+const MAX_PROMPT_TOKENS: usize = 224;
+
+/// prompt に付与する前置き
+const PROMPT_PREFIX: &str = "The following text provides relevant context. Please consider this when creating the transcription: ";
 
 /// STT API のレスポンス JSON。
 #[derive(Debug, Deserialize)]
-struct TranscriptionResponse {
+pub struct TranscriptionResponse {
+    pub text: String,
+    /// 検出された言語（`verbose_json` 形式の場合のみ返る）
+    #[serde(default)]
+    pub language: Option<String>,
+    /// セグメント単位の結果（`verbose_json` 形式の場合のみ返る）
+    #[serde(default)]
+    pub segments: Vec<ResponseSegment>,
+    /// 単語単位の結果（`timestamp_granularities[]=word` を指定した場合のみ返る）
+    #[serde(default)]
+    pub words: Vec<ResponseWord>,
+    /// トークン単位の対数確率（`include[]=logprobs` を指定した場合のみ返る）
+    #[serde(default)]
+    pub logprobs: Vec<ResponseLogprob>,
+}
+
+/// `verbose_json` のセグメント（時刻は秒）
+#[derive(Debug, Deserialize)]
+pub struct ResponseSegment {
+    pub start: f64,
+    pub end: f64,
     pub text: String,
+    #[serde(default)]
+    pub avg_logprob: Option<f64>,
+    #[serde(default)]
+    pub no_speech_prob: Option<f64>,
+}
+
+/// `verbose_json` の単語（時刻は秒）
+#[derive(Debug, Deserialize)]
+pub struct ResponseWord {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// `include[]=logprobs` のトークン
+#[derive(Debug, Deserialize)]
+pub struct ResponseLogprob {
+    #[serde(default)]
+    pub token: String,
+    pub logprob: f64,
+}
+
+/// 転写 API 呼び出しのエラー
+///
+/// リトライ判定のため、HTTP ステータスと `Retry-After` を保持する。
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum OpenAiError {
+    /// 接続失敗・送受信エラーなど、レスポンスを得られなかった
+    #[error("Request failed: {0}")]
+    Request(String),
+    /// API がエラーステータスを返した
+    #[error("API request failed with status {status}: {body}")]
+    Status {
+        status: u16,
+        body: String,
+        /// `Retry-After` ヘッダーで指定された待ち時間
+        retry_after: Option<Duration>,
+    },
+    /// レスポンスを解釈できなかった
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+}
+
+/// `Retry-After` ヘッダー（秒数または HTTP 日付）を待ち時間に変換
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
 }
 
 /// Dictionary suggestion (surface -> replacement)
@@ -18,96 +108,270 @@ pub struct WordSuggestion {
     pub replacement: String,
 }
 
+/// OpenAI 互換 API の接続設定
+#[derive(Debug, Clone)]
+pub struct OpenAiConfig {
+    /// API のベース URL（例: `http://localhost:8000/v1`）
+    pub base_url: String,
+    /// API キー（セルフホストサーバーでは不要な場合がある）
+    pub api_key: Option<String>,
+    /// 転写モデル
+    pub model: String,
+    /// API キーを送るヘッダー名（`Authorization` の場合のみ `Bearer` を付与）
+    pub auth_header: String,
+    /// 単語単位のタイムスタンプを要求するか（`verbose_json` 対応モデルのみ）
+    pub word_timestamps: bool,
+}
+
+impl Default for OpenAiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: None,
+            model: DEFAULT_TRANSCRIBE_MODEL.to_string(),
+            auth_header: DEFAULT_AUTH_HEADER.to_string(),
+            word_timestamps: false,
+        }
+    }
+}
+
+impl OpenAiConfig {
+    /// 環境変数から設定を読み込む
+    pub fn from_env() -> Self {
+        let env = EnvConfig::get();
+        let defaults = Self::default();
+        Self {
+            base_url: env
+                .openai_base_url
+                .clone()
+                .filter(|url| !url.trim().is_empty())
+                .unwrap_or(defaults.base_url),
+            api_key: env.openai_api_key.clone(),
+            model: std::env::var("OPENAI_TRANSCRIBE_MODEL").unwrap_or(defaults.model),
+            auth_header: env
+                .openai_auth_header
+                .clone()
+                .filter(|header| !header.trim().is_empty())
+                .unwrap_or(defaults.auth_header),
+            word_timestamps: std::env::var("OPENAI_WORD_TIMESTAMPS")
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(defaults.word_timestamps),
+        }
+    }
+
+    /// OpenAI 公式 API を利用する設定か
+    pub fn is_official(&self) -> bool {
+        self.base_url.trim_end_matches('/') == DEFAULT_BASE_URL
+    }
+
+    /// ベース URL にパスを連結したエンドポイント URL
+    pub fn endpoint(&self, path: &str) -> String {
+        format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+}
+
 /// OpenAI API client
 pub struct OpenAiClient {
-    api_key: String,
-    model: String,
+    config: OpenAiConfig,
     client: reqwest::Client,
 }
 
 impl OpenAiClient {
     /// Create a new OpenAI client
     pub fn new() -> Result<Self, String> {
-        let config = EnvConfig::get();
-        let api_key = config
-            .openai_api_key
-            .clone()
-            .ok_or("OPENAI_API_KEY environment variable is not set")?;
+        Self::with_config(OpenAiConfig::from_env())
+    }
 
-        let model = std::env::var("OPENAI_TRANSCRIBE_MODEL")
-            .unwrap_or_else(|_| "gpt-4o-mini-transcribe".to_string());
+    /// 接続設定を指定して作成
+    ///
+    /// 公式 API では API キーが必須。セルフホストサーバーではキーなしでも作成できる。
+    pub fn with_config(config: OpenAiConfig) -> Result<Self, String> {
+        if config.api_key.is_none() && config.is_official() {
+            return Err("OPENAI_API_KEY environment variable is not set".to_string());
+        }
 
         Ok(Self {
-            api_key,
-            model,
+            config,
             client: reqwest::Client::new(),
         })
     }
 
+    /// 接続先のベース URL
+    pub fn base_url(&self) -> &str {
+        &self.config.base_url
+    }
+
+    /// API キーがあれば設定された認証ヘッダーを付与
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.config.api_key {
+            Some(key)
+                if self
+                    .config
+                    .auth_header
+                    .eq_ignore_ascii_case(DEFAULT_AUTH_HEADER) =>
+            {
+                request.bearer_auth(key)
+            }
+            Some(key) => request.header(self.config.auth_header.as_str(), key),
+            None => request,
+        }
+    }
+
+    /// `/models` にアクセスして API に接続できるか確認
+    pub async fn check_connection(&self) -> Result<(), String> {
+        let response = self
+            .authorize(self.client.get(self.config.endpoint("models")))
+            .send()
+            .await
+            .map_err(|e| format!("error({})", e))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("fail({})", response.status()))
+        }
+    }
+
     /// AudioDataから直接転写を実行
-    pub async fn transcribe_audio(&self, audio_data: AudioData) -> Result<String, String> {
+    ///
+    /// `language` が `None` の場合は言語パラメータを送らず、API に自動判定させる。
+    /// `prompt`（選択テキストや `--prompt`）は API の上限トークン数に収まるよう末尾を残して切り詰める。
+    pub async fn transcribe_audio(
+        &self,
+        audio_data: AudioData,
+        language: Option<&str>,
+        prompt: Option<&str>,
+    ) -> Result<TranscriptionResponse, OpenAiError> {
         let wav_data = audio_data.0;
 
         let part = multipart::Part::bytes(wav_data)
             .file_name("audio.wav")
             .mime_str("audio/wav")
-            .map_err(|e| format!("Failed to create multipart: {}", e))?;
+            .map_err(|e| OpenAiError::Request(format!("Failed to create multipart: {}", e)))?;
 
         // 既存の転写処理を実行
-        self.transcribe_with_part(part, None).await
+        self.transcribe_with_part(part, language, prompt).await
+    }
+
+    /// 検出言語を含む `verbose_json` 形式に対応したモデルか
+    fn supports_verbose_json(&self) -> bool {
+        self.config.model.starts_with("whisper")
+    }
+
+    /// トークンの対数確率（`include[]=logprobs`）に対応したモデルか
+    fn supports_logprobs(&self) -> bool {
+        self.config.model.starts_with("gpt-4o")
     }
 
     /// 共通の転写処理
     async fn transcribe_with_part(
         &self,
         file_part: multipart::Part,
+        language: Option<&str>,
         prompt: Option<&str>,
-    ) -> Result<String, String> {
-        let url = "https://api.openai.com/v1/audio/transcriptions";
+    ) -> Result<TranscriptionResponse, OpenAiError> {
+        let url = self.config.endpoint("audio/transcriptions");
 
         // multipart/form-data
         let mut form = multipart::Form::new()
             .part("file", file_part)
-            .text("model", self.model.clone())
-            .text("language", "ja");
+            .text("model", self.config.model.clone());
+
+        if let Some(language) = language {
+            form = form.text("language", language.to_string());
+        }
+
+        // 検出言語とタイムスタンプを受け取れる形式を要求（対応モデルのみ）
+        if self.supports_verbose_json() {
+            form = form
+                .text("response_format", "verbose_json")
+                .text("timestamp_granularities[]", "segment");
+            if self.config.word_timestamps {
+                form = form.text("timestamp_granularities[]", "word");
+            }
+        } else if self.supports_logprobs() {
+            // 信頼度の推定に使う
+            form = form.text("include[]", "logprobs");
+        }
 
-        if let Some(prompt_text) = prompt {
+        if let Some(prompt_text) = prompt.map(str::trim).filter(|p| !p.is_empty()) {
+            // 前置きと引用符の分を差し引いた残りを本文に割り当てる
+            let budget = MAX_PROMPT_TOKENS.saturating_sub(estimate_tokens(PROMPT_PREFIX) + 2);
             let formatted_prompt = format!(
-                "The following text provides relevant context. Please consider this when creating the transcription: {:?}",
-                prompt_text
+                "{}{:?}",
+                PROMPT_PREFIX,
+                truncate_prompt(prompt_text, budget)
             );
             form = form.text("prompt", formatted_prompt);
         }
 
         // 送信
         let response = self
-            .client
-            .post(url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
+            .authorize(self.client.post(url))
             .multipart(form)
             .send()
             .await
-            .map_err(|e| format!("Failed to send request: {}", e))?;
+            .map_err(|e| OpenAiError::Request(e.to_string()))?;
 
         let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
         let body = response
             .text()
             .await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
+            .map_err(|e| OpenAiError::Request(format!("Failed to read response: {}", e)))?;
 
         if !status.is_success() {
-            return Err(format!(
-                "API request failed with status {}: {}",
-                status, body
-            ));
+            return Err(OpenAiError::Status {
+                status: status.as_u16(),
+                body,
+                retry_after,
+            });
         }
 
-        let transcription: TranscriptionResponse =
-            serde_json::from_str(&body).map_err(|e| format!("Failed to parse response: {}", e))?;
-        Ok(transcription.text)
+        serde_json::from_str(&body)
+            .map_err(|e| OpenAiError::InvalidResponse(format!("Failed to parse response: {}", e)))
     }
 }
 
+/// トークン数の概算（ASCII は 4 文字で 1 トークン、それ以外は 1 文字 1 トークン）
+fn estimate_tokens(text: &str) -> usize {
+    let (ascii, other) = text.chars().fold((0usize, 0), |(a, o), c| {
+        if c.is_ascii() { (a + 1, o) } else { (a, o + 1) }
+    });
+    ascii.div_ceil(4) + other
+}
+
+/// 概算トークン数が `max_tokens` に収まるよう、末尾（直近の文脈）を残して切り詰める
+fn truncate_prompt(prompt: &str, max_tokens: usize) -> &str {
+    if estimate_tokens(prompt) <= max_tokens {
+        return prompt;
+    }
+
+    let mut ascii = 0usize;
+    let mut other = 0;
+    let mut start = prompt.len();
+    for (idx, c) in prompt.char_indices().rev() {
+        if c.is_ascii() {
+            ascii += 1;
+        } else {
+            other += 1;
+        }
+        if ascii.div_ceil(4) + other > max_tokens {
+            break;
+        }
+        start = idx;
+    }
+    &prompt[start..]
+}
+
 // === Unit tests ==========================================================
 #[cfg(test)]
 mod tests {
@@ -119,6 +383,102 @@ mod tests {
         let json = r#"{"text":"こんにちは"}"#;
         let resp: TranscriptionResponse = serde_json::from_str(json).unwrap();
         assert_eq!(resp.text, "こんにちは");
+        assert_eq!(resp.language, None);
+    }
+
+    #[test]
+    fn parse_verbose_transcription_response_json() {
+        let json = r#"{"task":"transcribe","language":"english","duration":1.5,"text":"Fix typo"}"#;
+        let resp: TranscriptionResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.text, "Fix typo");
+        assert_eq!(resp.language.as_deref(), Some("english"));
+        assert!(resp.segments.is_empty());
+    }
+
+    #[test]
+    fn parse_verbose_transcription_response_with_timestamps() {
+        let json = r#"{
+            "task":"transcribe","language":"japanese","duration":3.2,"text":"こんにちは。元気です。",
+            "segments":[
+                {"id":0,"seek":0,"start":0.0,"end":1.4,"text":"こんにちは。","tokens":[1,2],
+                 "temperature":0.0,"avg_logprob":-0.21,"compression_ratio":0.9,"no_speech_prob":0.01},
+                {"id":1,"seek":0,"start":1.4,"end":3.2,"text":"元気です。"}
+            ],
+            "words":[{"word":"こんにちは","start":0.0,"end":1.1}]
+        }"#;
+        let resp: TranscriptionResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.segments.len(), 2);
+        assert_eq!(resp.segments[0].end, 1.4);
+        assert_eq!(resp.segments[0].avg_logprob, Some(-0.21));
+        assert_eq!(resp.segments[1].no_speech_prob, None);
+        assert_eq!(resp.words[0].word, "こんにちは");
+    }
+
+    #[test]
+    fn parse_transcription_response_with_logprobs() {
+        let json = r#"{"text":"Hi","logprobs":[{"token":"Hi","logprob":-0.05,"bytes":[72,105]}]}"#;
+        let resp: TranscriptionResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.logprobs.len(), 1);
+        assert_eq!(resp.logprobs[0].token, "Hi");
+        assert_eq!(resp.logprobs[0].logprob, -0.05);
+    }
+
+    #[test]
+    fn test_truncate_prompt_keeps_tail() {
+        assert_eq!(truncate_prompt("短い文脈", 10), "短い文脈");
+
+        let japanese = "あ".repeat(300) + "最後の文";
+        let truncated = truncate_prompt(&japanese, 224);
+        assert_eq!(estimate_tokens(truncated), 224);
+        assert!(truncated.ends_with("最後の文"));
+
+        let english = "word ".repeat(400);
+        let truncated = truncate_prompt(&english, 100);
+        assert!(estimate_tokens(truncated) <= 100);
+        assert!(english.ends_with(truncated));
+    }
+
+    #[test]
+    fn test_prompt_budget_fits_limit() {
+        let budget = MAX_PROMPT_TOKENS - estimate_tokens(PROMPT_PREFIX) - 2;
+        let prompt = "x".repeat(10_000);
+        let formatted = format!("{}{:?}", PROMPT_PREFIX, truncate_prompt(&prompt, budget));
+        assert!(estimate_tokens(&formatted) <= MAX_PROMPT_TOKENS);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(parse_retry_after(" 0 "), Some(Duration::ZERO));
+        // 過去の日付は待ち時間 0
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(120)).to_rfc2822();
+        let wait = parse_retry_after(&future).unwrap();
+        assert!(wait > Duration::from_secs(100) && wait <= Duration::from_secs(120));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn test_config_endpoint_and_key_requirement() {
+        let config = OpenAiConfig::default();
+        assert!(config.is_official());
+        assert_eq!(
+            config.endpoint("audio/transcriptions"),
+            "https://api.openai.com/v1/audio/transcriptions"
+        );
+        // 公式 API はキーが必須
+        assert!(OpenAiClient::with_config(config).is_err());
+
+        let local = OpenAiConfig {
+            base_url: "http://localhost:8000/v1/".to_string(),
+            ..Default::default()
+        };
+        assert!(!local.is_official());
+        assert_eq!(local.endpoint("/models"), "http://localhost:8000/v1/models");
+        assert!(OpenAiClient::with_config(local).is_ok());
     }
 
     #[tokio::test]
@@ -171,7 +531,7 @@ mod tests {
         let audio_data = AudioData(wav_data);
 
         // This will fail with the actual API, but we're testing the method exists
-        let result = client.transcribe_audio(audio_data).await;
+        let result = client.transcribe_audio(audio_data, Some("ja"), None).await;
 
         // We expect an error since we're using a test API key
         assert!(result.is_err());
@@ -194,7 +554,7 @@ mod tests {
         let audio_data = AudioData(test_data);
 
         // This will fail because the file doesn't exist, but we're testing the method exists
-        let result = client.transcribe_audio(audio_data).await;
+        let result = client.transcribe_audio(audio_data, Some("ja"), None).await;
 
         // We expect an error since the file doesn't exist
         assert!(result.is_err());
@@ -2,9 +2,11 @@
 //! Application層のTranscriptionClientトレイトを実装
 
 use crate::application::traits::TranscriptionClient;
+use crate::application::{Transcript, TranscriptionOptions};
+use crate::domain::transcript::{TranscriptSegment, TranscriptWord};
 use crate::error::Result;
 use crate::infrastructure::audio::cpal_backend::AudioData;
-use crate::infrastructure::external::openai::OpenAiClient;
+use crate::infrastructure::external::openai::{OpenAiClient, OpenAiConfig};
 use async_trait::async_trait;
 
 /// OpenAI APIのアダプター
@@ -19,14 +21,57 @@ impl OpenAiTranscriptionAdapter {
             client: OpenAiClient::new()?,
         })
     }
+
+    /// 接続設定を指定して作成
+    pub fn with_config(config: OpenAiConfig) -> Result<Self> {
+        Ok(Self {
+            client: OpenAiClient::with_config(config)?,
+        })
+    }
 }
 
 #[async_trait]
 impl TranscriptionClient for OpenAiTranscriptionAdapter {
-    async fn transcribe(&self, audio: AudioData, _language: &str) -> Result<String> {
-        self.client
-            .transcribe_audio(audio)
-            .await
-            .map_err(crate::error::VoiceInputError::TranscriptionFailed)
+    async fn transcribe(
+        &self,
+        audio: AudioData,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcript> {
+        let response = self
+            .client
+            .transcribe_audio(audio, options.language_code(), options.prompt.as_deref())
+            .await?;
+        let segments = response
+            .segments
+            .into_iter()
+            .map(|s| TranscriptSegment {
+                start: s.start,
+                end: s.end,
+                text: s.text,
+                avg_logprob: s.avg_logprob,
+                no_speech_prob: s.no_speech_prob,
+            })
+            .collect();
+        let words = response
+            .words
+            .into_iter()
+            .map(|w| TranscriptWord {
+                word: w.word,
+                start: w.start,
+                end: w.end,
+            })
+            .collect();
+        Ok(Transcript {
+            text: response.text,
+            // 言語を指定した場合は検出結果ではないため記録しない
+            language: response
+                .language
+                .filter(|_| options.language_code().is_none()),
+            provider: None,
+            segments,
+            words,
+            logprobs: response.logprobs.into_iter().map(|l| l.logprob).collect(),
+            ..Default::default()
+        })
     }
 }
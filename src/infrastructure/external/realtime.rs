@@ -0,0 +1,510 @@
+//! Realtime 形式の WebSocket によるストリーミング転写
+//!
+//! OpenAI Realtime API の転写セッション（`intent=transcription`）と同じプロトコルで、
+//! 録音中の音声を `input_audio_buffer.append` で逐次送り、サーバー側 VAD が区切った
+//! 発話ごとの途中結果（`delta`）と確定結果（`completed`）を受け取ります。
+//! 停止時は残りの音声をコミットし、すべての発話の確定を待って結果を返します。
+//!
+//! `VOICE_INPUT_REALTIME_URL` で互換サーバー（ローカルの中継サーバー等）に切り替えられます。
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine;
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::application::TranscriptionOptions;
+use crate::application::traits::{StreamingTranscriptionClient, TranscriptionStream};
+use crate::domain::transcript::{Transcript, join_segments};
+use crate::error::{Result, VoiceInputError};
+use crate::infrastructure::audio::PcmChunk;
+use crate::infrastructure::audio::resample::resample;
+use crate::utils::config::EnvConfig;
+
+/// OpenAI Realtime API の転写セッション URL
+pub const DEFAULT_REALTIME_URL: &str = "wss://api.openai.com/v1/realtime?intent=transcription";
+
+/// デフォルトのストリーミング転写モデル
+pub const DEFAULT_REALTIME_MODEL: &str = "gpt-4o-transcribe";
+
+/// 送信する音声のサンプリングレート（pcm16 モノラル）
+pub const REALTIME_SAMPLE_RATE: u32 = 24_000;
+
+/// 音声が空のままコミットした場合のエラーコード
+const COMMIT_EMPTY_CODE: &str = "input_audio_buffer_commit_empty";
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// ストリーミング転写の接続設定
+#[derive(Debug, Clone)]
+pub struct RealtimeConfig {
+    /// WebSocket の URL（`ws://` または `wss://`）
+    pub url: String,
+    /// API キー（ローカルの互換サーバーでは不要な場合がある）
+    pub api_key: Option<String>,
+    /// 転写モデル
+    pub model: String,
+    /// API キーを送るヘッダー名（`Authorization` の場合のみ `Bearer` を付与）
+    pub auth_header: String,
+    /// 接続と、停止から確定結果までの待ち時間の上限
+    pub timeout: Duration,
+}
+
+impl Default for RealtimeConfig {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_REALTIME_URL.to_string(),
+            api_key: None,
+            model: DEFAULT_REALTIME_MODEL.to_string(),
+            auth_header: "Authorization".to_string(),
+            timeout: Duration::from_secs(15),
+        }
+    }
+}
+
+impl RealtimeConfig {
+    /// 環境変数から設定を読み込む
+    pub fn from_env() -> Self {
+        let env = EnvConfig::get();
+        let defaults = Self::default();
+        Self {
+            url: std::env::var("VOICE_INPUT_REALTIME_URL")
+                .ok()
+                .filter(|url| !url.trim().is_empty())
+                .unwrap_or(defaults.url),
+            api_key: env.openai_api_key.clone(),
+            model: std::env::var("VOICE_INPUT_REALTIME_MODEL")
+                .ok()
+                .filter(|model| !model.trim().is_empty())
+                .unwrap_or(defaults.model),
+            auth_header: env
+                .openai_auth_header
+                .clone()
+                .filter(|header| !header.trim().is_empty())
+                .unwrap_or(defaults.auth_header),
+            timeout: defaults.timeout,
+        }
+    }
+
+    /// OpenAI 公式 API を利用する設定か
+    pub fn is_official(&self) -> bool {
+        self.url.starts_with("wss://api.openai.com/")
+    }
+}
+
+/// Realtime 形式の WebSocket で転写するクライアント
+pub struct RealtimeTranscriptionClient {
+    config: RealtimeConfig,
+}
+
+impl RealtimeTranscriptionClient {
+    /// 接続設定を指定して作成（公式 API では API キーが必須）
+    pub fn new(config: RealtimeConfig) -> Result<Self> {
+        if config.api_key.is_none() && config.is_official() {
+            return Err(VoiceInputError::OpenAiConfigError(
+                "OPENAI_API_KEY environment variable is not set".to_string(),
+            ));
+        }
+        Ok(Self { config })
+    }
+
+    /// 認証ヘッダー付きの接続リクエストを作成
+    fn request(&self) -> Result<tokio_tungstenite::tungstenite::handshake::client::Request> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            VoiceInputError::OpenAiConfigError(format!("invalid realtime request: {}", e))
+        };
+        let mut request = self
+            .config
+            .url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| invalid(&e))?;
+        let headers = request.headers_mut();
+        if let Some(key) = &self.config.api_key {
+            let (name, value) = if self
+                .config
+                .auth_header
+                .eq_ignore_ascii_case("Authorization")
+            {
+                ("Authorization", format!("Bearer {}", key))
+            } else {
+                (self.config.auth_header.as_str(), key.clone())
+            };
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(&e))?,
+                HeaderValue::from_str(&value).map_err(|e| invalid(&e))?,
+            );
+        }
+        headers.insert("OpenAI-Beta", HeaderValue::from_static("realtime=v1"));
+        Ok(request)
+    }
+
+    /// 転写セッションの設定イベント
+    fn session_update(&self, options: &TranscriptionOptions) -> Value {
+        let mut transcription = json!({ "model": self.config.model });
+        if let Some(language) = options.language_code() {
+            transcription["language"] = json!(language);
+        }
+        if let Some(prompt) = options
+            .prompt
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            transcription["prompt"] = json!(prompt);
+        }
+        json!({
+            "type": "transcription_session.update",
+            "session": {
+                "input_audio_format": "pcm16",
+                "input_audio_transcription": transcription,
+                "turn_detection": { "type": "server_vad" },
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl StreamingTranscriptionClient for RealtimeTranscriptionClient {
+    async fn open(
+        &self,
+        options: &TranscriptionOptions,
+        partials: mpsc::UnboundedSender<String>,
+    ) -> Result<Box<dyn TranscriptionStream>> {
+        let request = self.request()?;
+        let (socket, _) = tokio::time::timeout(
+            self.config.timeout,
+            tokio_tungstenite::connect_async(request),
+        )
+        .await
+        .map_err(|_| VoiceInputError::RequestTimeout(self.config.timeout))?
+        .map_err(|e| VoiceInputError::NetworkError(format!("realtime connect failed: {}", e)))?;
+
+        let (mut sink, stream) = socket.split();
+        send_event(&mut sink, &self.session_update(options)).await?;
+
+        let (finish_tx, finish_rx) = oneshot::channel();
+        let reader = tokio::spawn(read_events(stream, partials, finish_rx));
+        Ok(Box::new(RealtimeStream {
+            sink,
+            reader,
+            finish_tx,
+            timeout: self.config.timeout,
+        }))
+    }
+}
+
+/// 接続中のストリーミング転写セッション
+struct RealtimeStream {
+    sink: SplitSink<WsStream, Message>,
+    /// 受信イベントを処理し、確定結果を返すタスク
+    reader: JoinHandle<Result<Transcript>>,
+    finish_tx: oneshot::Sender<()>,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl TranscriptionStream for RealtimeStream {
+    async fn send_audio(&mut self, chunk: &PcmChunk) -> Result<()> {
+        if chunk.samples.is_empty() {
+            return Ok(());
+        }
+        let audio = base64::engine::general_purpose::STANDARD.encode(to_pcm16_mono(chunk));
+        send_event(
+            &mut self.sink,
+            &json!({ "type": "input_audio_buffer.append", "audio": audio }),
+        )
+        .await
+    }
+
+    async fn finish(self: Box<Self>) -> Result<Transcript> {
+        let RealtimeStream {
+            mut sink,
+            reader,
+            finish_tx,
+            timeout,
+        } = *self;
+
+        // コミットへの応答より先に受信タスクが終了を知るよう、通知してからコミットする
+        let _ = finish_tx.send(());
+        send_event(&mut sink, &json!({ "type": "input_audio_buffer.commit" })).await?;
+
+        let result = tokio::time::timeout(timeout, reader).await;
+        let _ = sink.send(Message::Close(None)).await;
+        match result {
+            Ok(Ok(transcript)) => transcript,
+            Ok(Err(e)) => Err(VoiceInputError::TranscriptionFailed(format!(
+                "realtime session task failed: {}",
+                e
+            ))),
+            Err(_) => Err(VoiceInputError::RequestTimeout(timeout)),
+        }
+    }
+}
+
+async fn send_event(sink: &mut SplitSink<WsStream, Message>, event: &Value) -> Result<()> {
+    sink.send(Message::Text(event.to_string()))
+        .await
+        .map_err(|e| VoiceInputError::NetworkError(format!("realtime send failed: {}", e)))
+}
+
+/// サーバーからのイベントを処理し、終了要求後にすべての発話が確定したら結果を返す
+async fn read_events(
+    mut stream: SplitStream<WsStream>,
+    partials: mpsc::UnboundedSender<String>,
+    mut finish_rx: oneshot::Receiver<()>,
+) -> Result<Transcript> {
+    let mut session = SessionState::default();
+    loop {
+        tokio::select! {
+            biased;
+            finished = &mut finish_rx, if !session.finishing => {
+                if finished.is_err() {
+                    // 終了せずにセッションが破棄された
+                    return Err(VoiceInputError::TranscriptionFailed(
+                        "realtime session was abandoned".to_string(),
+                    ));
+                }
+                session.finishing = true;
+            }
+            message = stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let event: Value = serde_json::from_str(&text).map_err(|e| {
+                        VoiceInputError::TranscriptionFailed(format!("invalid realtime event: {}", e))
+                    })?;
+                    if let Some(partial) = session.handle(&event)? {
+                        let _ = partials.send(partial);
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => {
+                    return Err(VoiceInputError::NetworkError(
+                        "realtime connection closed before the transcript was complete".to_string(),
+                    ));
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    return Err(VoiceInputError::NetworkError(format!("realtime receive failed: {}", e)));
+                }
+            },
+        }
+
+        if session.is_complete() {
+            return Ok(session.transcript());
+        }
+    }
+}
+
+/// 発話（コミットされた音声）ごとの転写状況
+#[derive(Debug, Default)]
+struct SessionState {
+    /// コミット順の発話 ID
+    items: Vec<String>,
+    /// 確定したテキスト
+    completed: HashMap<String, String>,
+    /// 受信途中のテキスト
+    pending: HashMap<String, String>,
+    /// 録音停止（最終コミット）が要求されたか
+    finishing: bool,
+    /// サーバー側 VAD が発話終了を検出し、まだコミットが届いていない数
+    vad_commits: usize,
+    /// 最終コミットへの応答を受け取ったか
+    final_commit_acked: bool,
+}
+
+impl SessionState {
+    /// イベントを反映し、表示中のテキストが変わった場合は全文を返す
+    fn handle(&mut self, event: &Value) -> Result<Option<String>> {
+        let field = |name: &str| event[name].as_str().unwrap_or_default().to_string();
+        match event["type"].as_str().unwrap_or_default() {
+            "input_audio_buffer.speech_stopped" => {
+                self.vad_commits += 1;
+                Ok(None)
+            }
+            "input_audio_buffer.committed" => {
+                self.track(field("item_id"));
+                // 発話終了の検出に続くコミットはサーバー側 VAD によるもの
+                if self.vad_commits > 0 {
+                    self.vad_commits -= 1;
+                } else if self.finishing {
+                    self.final_commit_acked = true;
+                }
+                Ok(None)
+            }
+            "conversation.item.input_audio_transcription.delta" => {
+                let item = field("item_id");
+                self.track(item.clone());
+                self.pending
+                    .entry(item)
+                    .or_default()
+                    .push_str(&field("delta"));
+                Ok(Some(self.text()))
+            }
+            "conversation.item.input_audio_transcription.completed" => {
+                let item = field("item_id");
+                self.track(item.clone());
+                self.pending.remove(&item);
+                self.completed.insert(item, field("transcript"));
+                Ok(Some(self.text()))
+            }
+            "conversation.item.input_audio_transcription.failed" | "error" => {
+                let error = &event["error"];
+                if error["code"].as_str() == Some(COMMIT_EMPTY_CODE) {
+                    // 直前にサーバー側 VAD がコミット済みで、残りの音声がなかった
+                    if self.finishing {
+                        self.final_commit_acked = true;
+                    }
+                    return Ok(None);
+                }
+                Err(VoiceInputError::TranscriptionFailed(format!(
+                    "realtime transcription failed: {}",
+                    error["message"].as_str().unwrap_or("unknown error")
+                )))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn track(&mut self, item: String) {
+        if !self.items.contains(&item) {
+            self.items.push(item);
+        }
+    }
+
+    /// 確定済みと受信途中のテキストを発話順に連結
+    fn text(&self) -> String {
+        join_segments(self.items.iter().filter_map(|item| {
+            self.completed
+                .get(item)
+                .or_else(|| self.pending.get(item))
+                .map(String::as_str)
+        }))
+    }
+
+    /// 最終コミット後、すべての発話が確定したか
+    fn is_complete(&self) -> bool {
+        self.final_commit_acked
+            && self
+                .items
+                .iter()
+                .all(|item| self.completed.contains_key(item))
+    }
+
+    fn transcript(&self) -> Transcript {
+        Transcript::new(self.text()).with_provider("realtime")
+    }
+}
+
+/// 録音バッファの音声を 24kHz モノラルの pcm16（リトルエンディアン）に変換
+fn to_pcm16_mono(chunk: &PcmChunk) -> Vec<u8> {
+    let channels = chunk.channels.max(1) as usize;
+    let mono: Vec<f32> = chunk
+        .samples
+        .chunks(channels)
+        .map(|frame| frame.iter().map(|&s| s as f32).sum::<f32>() / frame.len() as f32)
+        .collect();
+    resample(&mono, chunk.sample_rate, REALTIME_SAMPLE_RATE)
+        .into_iter()
+        .flat_map(|s| (s.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16).to_le_bytes())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_state_orders_items_and_waits_for_final_commit() {
+        let mut session = SessionState::default();
+        let committed = |id: &str| json!({"type": "input_audio_buffer.committed", "item_id": id});
+        let delta = |id: &str, text: &str| json!({"type": "conversation.item.input_audio_transcription.delta", "item_id": id, "delta": text});
+        let completed = |id: &str, text: &str| json!({"type": "conversation.item.input_audio_transcription.completed", "item_id": id, "transcript": text});
+
+        session.handle(&committed("a")).unwrap();
+        assert_eq!(
+            session.handle(&delta("a", "こんに")).unwrap().as_deref(),
+            Some("こんに")
+        );
+        session.handle(&committed("b")).unwrap();
+        assert_eq!(
+            session
+                .handle(&completed("b", "音声入力です。"))
+                .unwrap()
+                .as_deref(),
+            Some("こんに音声入力です。")
+        );
+        assert_eq!(
+            session
+                .handle(&completed("a", "こんにちは。"))
+                .unwrap()
+                .as_deref(),
+            Some("こんにちは。音声入力です。")
+        );
+        // 停止前はすべて確定していても完了しない
+        assert!(!session.is_complete());
+
+        session.finishing = true;
+        // 停止と前後して届いたサーバー側 VAD のコミットは最終コミットへの応答ではない
+        let speech_stopped = json!({"type": "input_audio_buffer.speech_stopped"});
+        session.handle(&speech_stopped).unwrap();
+        session.handle(&committed("b2")).unwrap();
+        session.handle(&completed("b2", "それと、")).unwrap();
+        assert!(!session.is_complete());
+        session.handle(&committed("c")).unwrap();
+        assert!(!session.is_complete());
+        session.handle(&completed("c", "以上です。")).unwrap();
+        assert!(session.is_complete());
+        assert_eq!(
+            session.transcript().text,
+            "こんにちは。音声入力です。それと、以上です。"
+        );
+    }
+
+    #[test]
+    fn test_session_state_errors() {
+        let mut session = SessionState {
+            finishing: true,
+            ..Default::default()
+        };
+        let empty = json!({"type": "error", "error": {"code": COMMIT_EMPTY_CODE, "message": "buffer too small"}});
+        assert_eq!(session.handle(&empty).unwrap(), None);
+        assert!(session.is_complete());
+
+        let failure =
+            json!({"type": "error", "error": {"code": "invalid_api_key", "message": "bad key"}});
+        let err = session.handle(&failure).unwrap_err();
+        assert!(err.to_string().contains("bad key"));
+        assert!(err.is_provider_failure());
+    }
+
+    #[test]
+    fn test_to_pcm16_mono() {
+        let chunk = PcmChunk {
+            samples: vec![100, 300, -200, -400],
+            sample_rate: REALTIME_SAMPLE_RATE,
+            channels: 2,
+        };
+        let bytes = to_pcm16_mono(&chunk);
+        assert_eq!(
+            bytes,
+            [200i16.to_le_bytes(), (-300i16).to_le_bytes()].concat()
+        );
+
+        // 48kHz は半分のサンプル数になる
+        let chunk = PcmChunk {
+            samples: vec![0; 4800],
+            sample_rate: 48_000,
+            channels: 1,
+        };
+        assert_eq!(to_pcm16_mono(&chunk).len(), 2400 * 2);
+    }
+}
@@ -0,0 +1,283 @@
+//! 転写プロバイダーの選択
+//!
+//! `VOICE_INPUT_STT_PROVIDER` で転写に使うバックエンドを切り替えます。
+//!
+//! - `openai`（既定）: OpenAI API または OpenAI 互換サーバー
+//! - `whisper-cpp`: ローカルの whisper.cpp（`whisper-cli`）
+//! - `command`: `VOICE_INPUT_STT_COMMAND` で指定した任意のコマンド
+//!
+//! `VOICE_INPUT_STT_FALLBACK` には失敗時に順に試すプロバイダーをカンマ区切りで指定します。
+//! `openai:whisper-1` のように `:` の後ろでモデルを上書きできます。
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::application::traits::TranscriptionClient;
+use crate::error::{Result, VoiceInputError};
+use crate::infrastructure::external::command_stt::{
+    CommandSttConfig, CommandTranscriptionClient, WHISPER_CPP_COMMAND,
+};
+use crate::infrastructure::external::openai::OpenAiConfig;
+use crate::infrastructure::external::openai_adapter::OpenAiTranscriptionAdapter;
+
+/// 転写プロバイダー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SttProvider {
+    /// OpenAI API（互換サーバーを含む）
+    #[default]
+    OpenAi,
+    /// ローカルの whisper.cpp
+    WhisperCpp,
+    /// 任意のローカルコマンド
+    Command,
+}
+
+impl SttProvider {
+    /// `VOICE_INPUT_STT_PROVIDER` から読み込む（未設定なら OpenAI）
+    ///
+    /// 不正な値はエラーにする（打ち間違いで音声がクラウドに送られないようにするため）。
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_value(std::env::var("VOICE_INPUT_STT_PROVIDER").ok().as_deref())
+    }
+
+    fn from_env_value(value: Option<&str>) -> Result<Self> {
+        match value.filter(|s| !s.trim().is_empty()) {
+            Some(value) => value.parse().map_err(|e| {
+                VoiceInputError::ConfigInitError(format!("VOICE_INPUT_STT_PROVIDER: {}", e))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// ローカルで完結するプロバイダーか
+    pub fn is_local(&self) -> bool {
+        !matches!(self, SttProvider::OpenAi)
+    }
+
+    /// ローカルコマンドの設定（OpenAI の場合は `None`）
+    pub fn command_config(&self) -> Result<Option<CommandSttConfig>> {
+        match self {
+            SttProvider::OpenAi => Ok(None),
+            SttProvider::WhisperCpp => {
+                CommandSttConfig::from_env(Some(WHISPER_CPP_COMMAND)).map(Some)
+            }
+            SttProvider::Command => CommandSttConfig::from_env(None).map(Some),
+        }
+    }
+
+    /// ローカルコマンドの実行ファイルとモデルを確認し、プログラム名を返す（OpenAI の場合は `None`）
+    pub fn check_command(&self) -> Option<std::result::Result<String, String>> {
+        match self.command_config() {
+            Ok(Some(config)) => Some(config.check().map(|_| config.program().to_string())),
+            Ok(None) => None,
+            Err(e) => Some(Err(e.to_string())),
+        }
+    }
+
+    /// 転写クライアントを作成
+    pub fn create_client(&self) -> Result<Box<dyn TranscriptionClient>> {
+        ProviderSpec::from(*self).create_client()
+    }
+}
+
+/// モデルの上書きを含むプロバイダー指定（`openai:whisper-1` など）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderSpec {
+    pub provider: SttProvider,
+    /// OpenAI ではモデル名、ローカルコマンドでは `{model}` に渡すパス
+    pub model: Option<String>,
+}
+
+impl ProviderSpec {
+    /// `VOICE_INPUT_STT_FALLBACK` からフォールバック先を読み込む（不正な値は警告して無視）
+    pub fn fallback_from_env() -> Vec<Self> {
+        std::env::var("VOICE_INPUT_STT_FALLBACK")
+            .map(|value| {
+                value
+                    .split(',')
+                    .filter(|s| !s.trim().is_empty())
+                    .filter_map(|s| match s.parse() {
+                        Ok(spec) => Some(spec),
+                        Err(e) => {
+                            eprintln!("Ignoring VOICE_INPUT_STT_FALLBACK entry: {}", e);
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 転写クライアントを作成
+    pub fn create_client(&self) -> Result<Box<dyn TranscriptionClient>> {
+        match (self.provider.command_config()?, self.model.clone()) {
+            (Some(config), Some(model)) => Ok(Box::new(CommandTranscriptionClient::new(
+                config.with_model(model),
+            )?)),
+            (Some(config), None) => Ok(Box::new(CommandTranscriptionClient::new(config)?)),
+            (None, Some(model)) => Ok(Box::new(OpenAiTranscriptionAdapter::with_config(
+                OpenAiConfig {
+                    model,
+                    ..OpenAiConfig::from_env()
+                },
+            )?)),
+            (None, None) => Ok(Box::new(OpenAiTranscriptionAdapter::new()?)),
+        }
+    }
+
+    /// 実際に使われるモデル名（利用記録・料金表の参照用）
+    ///
+    /// OpenAI では未指定なら `OPENAI_TRANSCRIBE_MODEL` の値、ローカルコマンドでは
+    /// `{model}` に渡す値（未設定なら空）を返す。
+    pub fn model_name(&self) -> String {
+        if let Some(model) = &self.model {
+            return model.clone();
+        }
+        match self.provider.command_config() {
+            Ok(Some(config)) => config.model.unwrap_or_default(),
+            Ok(None) => OpenAiConfig::from_env().model,
+            Err(_) => String::new(),
+        }
+    }
+
+    /// 転写キャッシュのキーに含める識別子（実際に使われるエンドポイント・コマンドとモデル）
+    pub fn cache_namespace(&self) -> String {
+        match self.provider.command_config() {
+            Ok(Some(config)) => format!(
+                "{}|{}|{}",
+                self.provider,
+                config.command.join(" "),
+                self.model
+                    .as_deref()
+                    .or(config.model.as_deref())
+                    .unwrap_or("")
+            ),
+            Ok(None) => {
+                let config = OpenAiConfig::from_env();
+                format!(
+                    "{}|{}|{}",
+                    self.provider,
+                    config.base_url.trim_end_matches('/'),
+                    self.model.as_deref().unwrap_or(&config.model)
+                )
+            }
+            Err(_) => self.to_string(),
+        }
+    }
+}
+
+impl From<SttProvider> for ProviderSpec {
+    fn from(provider: SttProvider) -> Self {
+        Self {
+            provider,
+            model: None,
+        }
+    }
+}
+
+impl FromStr for ProviderSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (provider, model) = match s.split_once(':') {
+            Some((provider, model)) => (provider, Some(model.trim().to_string())),
+            None => (s, None),
+        };
+        Ok(Self {
+            provider: provider.parse()?,
+            model: model.filter(|m| !m.is_empty()),
+        })
+    }
+}
+
+impl fmt::Display for ProviderSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.model {
+            Some(model) => write!(f, "{}:{}", self.provider, model),
+            None => write!(f, "{}", self.provider),
+        }
+    }
+}
+
+impl FromStr for SttProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "openai" => Ok(Self::OpenAi),
+            "whisper-cpp" | "whisper.cpp" | "whispercpp" => Ok(Self::WhisperCpp),
+            "command" => Ok(Self::Command),
+            other => Err(format!("unknown STT provider: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for SttProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SttProvider::OpenAi => "openai",
+            SttProvider::WhisperCpp => "whisper-cpp",
+            SttProvider::Command => "command",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_provider() {
+        assert_eq!("openai".parse(), Ok(SttProvider::OpenAi));
+        assert_eq!(" Whisper.cpp ".parse(), Ok(SttProvider::WhisperCpp));
+        assert_eq!("command".parse(), Ok(SttProvider::Command));
+        assert!("deepgram".parse::<SttProvider>().is_err());
+
+        for provider in [
+            SttProvider::OpenAi,
+            SttProvider::WhisperCpp,
+            SttProvider::Command,
+        ] {
+            assert_eq!(provider.to_string().parse(), Ok(provider));
+        }
+        assert!(!SttProvider::OpenAi.is_local());
+        assert!(SttProvider::WhisperCpp.is_local());
+    }
+
+    #[test]
+    fn test_provider_from_env_value() {
+        assert_eq!(
+            SttProvider::from_env_value(None).unwrap(),
+            SttProvider::OpenAi
+        );
+        assert_eq!(
+            SttProvider::from_env_value(Some(" ")).unwrap(),
+            SttProvider::OpenAi
+        );
+        assert_eq!(
+            SttProvider::from_env_value(Some("whisper-cpp")).unwrap(),
+            SttProvider::WhisperCpp
+        );
+        // 打ち間違いはクラウドに切り替えずエラーにする
+        let err = SttProvider::from_env_value(Some("whisper-cp")).unwrap_err();
+        assert!(matches!(err, VoiceInputError::ConfigInitError(_)));
+    }
+
+    #[test]
+    fn test_parse_provider_spec() {
+        let spec: ProviderSpec = "openai:whisper-1".parse().unwrap();
+        assert_eq!(spec.provider, SttProvider::OpenAi);
+        assert_eq!(spec.model.as_deref(), Some("whisper-1"));
+        assert_eq!(spec.to_string(), "openai:whisper-1");
+
+        let spec: ProviderSpec = " whisper-cpp:/models/ggml-base.bin".parse().unwrap();
+        assert_eq!(spec.provider, SttProvider::WhisperCpp);
+        assert_eq!(spec.model.as_deref(), Some("/models/ggml-base.bin"));
+
+        let spec: ProviderSpec = "command:".parse().unwrap();
+        assert_eq!(spec, ProviderSpec::from(SttProvider::Command));
+        assert_eq!(spec.to_string(), "command");
+
+        assert!("deepgram:nova".parse::<ProviderSpec>().is_err());
+    }
+}
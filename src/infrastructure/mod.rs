@@ -1,5 +1,8 @@
 pub mod audio;
+pub mod cache;
 pub mod config;
 pub mod dict;
 pub mod external;
+pub mod queue;
 pub mod ui;
+pub mod usage;
@@ -0,0 +1,435 @@
+//! 転写に失敗した録音のオフラインキュー
+//!
+//! ネットワーク障害などで転写できなかった録音を、音声・転写オプション・本来の出力先とともに
+//! ディスクへ保存します。ジョブごとにメタデータ（`<id>.json`）と音声（`<id>.wav`）の
+//! 2 ファイルを作り、再実行に成功したら削除します。
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::TranscriptionOptions;
+use crate::infrastructure::config::default_queue_dir;
+
+/// 自動再実行の間隔の既定値（秒）
+pub const DEFAULT_QUEUE_RETRY_SECS: u64 = 60;
+
+/// 自動再実行するジョブの失敗回数の上限（達したジョブは `queue retry` で再実行する）
+pub const MAX_AUTO_RETRY_ATTEMPTS: u32 = 10;
+
+/// 自動再実行の間隔を失敗回数に応じて延ばすときの上限
+pub const MAX_AUTO_RETRY_BACKOFF: Duration = Duration::from_secs(3600);
+
+/// キュー設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueConfig {
+    /// 失敗した転写をキューに保存するか
+    pub enabled: bool,
+    /// 保存先（`None` ならデータディレクトリ配下の `queue/`）
+    pub dir: Option<PathBuf>,
+    /// 自動再実行の間隔（`None` なら自動では再実行しない）
+    pub retry_interval: Option<Duration>,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: None,
+            retry_interval: Some(Duration::from_secs(DEFAULT_QUEUE_RETRY_SECS)),
+        }
+    }
+}
+
+impl QueueConfig {
+    /// 環境変数から設定を読み込む
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("VOICE_INPUT_QUEUE")
+                .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+                .unwrap_or(defaults.enabled),
+            dir: std::env::var("VOICE_INPUT_QUEUE_DIR")
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .map(PathBuf::from),
+            retry_interval: match std::env::var("VOICE_INPUT_QUEUE_RETRY_SECS")
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+            {
+                Some(0) => None,
+                Some(secs) => Some(Duration::from_secs(secs)),
+                None => defaults.retry_interval,
+            },
+        }
+    }
+
+    /// キューの保存先
+    pub fn dir(&self) -> PathBuf {
+        self.dir.clone().unwrap_or_else(default_queue_dir)
+    }
+
+    /// 設定に従ってキューを開く
+    pub fn open(&self) -> TranscriptionQueue {
+        TranscriptionQueue::new(self.dir())
+    }
+}
+
+/// 録音時に意図していた出力先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    /// フォーカス中のウィンドウへ入力
+    Input,
+    /// スタックへ保存
+    Stack,
+    /// 出力しない
+    None,
+}
+
+impl fmt::Display for OutputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputMode::Input => "input",
+            OutputMode::Stack => "stack",
+            OutputMode::None => "none",
+        })
+    }
+}
+
+/// キューに保存された転写ジョブ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedJob {
+    /// ジョブ ID（保存日時から採番）
+    pub id: String,
+    /// 保存した時刻（UNIX 秒）
+    pub created_at: u64,
+    /// 録音時間（ミリ秒）
+    pub duration_ms: u64,
+    /// 転写オプション
+    pub options: TranscriptionOptions,
+    /// 録音時に意図していた出力先
+    pub output: OutputMode,
+    /// 失敗した回数（最初の失敗を含む）
+    pub attempts: u32,
+    /// 最後のエラー
+    pub last_error: String,
+    /// 最後のエラーが一時的なもの（ネットワーク障害など）か
+    #[serde(default)]
+    pub retryable: bool,
+    /// 最後に失敗した時刻（UNIX 秒）
+    #[serde(default)]
+    pub failed_at: u64,
+}
+
+impl QueuedJob {
+    /// 自動再実行の対象か
+    ///
+    /// 一時的なエラーで失敗し、上限回数に達していないジョブだけを対象とする。
+    /// 失敗するたびに間隔を `interval` から倍々に延ばす（認証エラーや予算超過は手動の再実行に任せる）。
+    pub fn due_for_auto_retry(&self, interval: Duration, now: u64) -> bool {
+        let backoff = interval
+            .saturating_mul(1 << self.attempts.saturating_sub(1).min(16))
+            .min(MAX_AUTO_RETRY_BACKOFF);
+        self.retryable
+            && self.attempts < MAX_AUTO_RETRY_ATTEMPTS
+            && now >= self.failed_at.saturating_add(backoff.as_secs())
+    }
+}
+
+/// キューの再実行結果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueueRetryReport {
+    /// 転写できたジョブ数
+    pub completed: usize,
+    /// 再び失敗したジョブ数
+    pub failed: usize,
+    /// キューに残っているジョブ数
+    pub remaining: usize,
+}
+
+impl fmt::Display for QueueRetryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "🔁 Queue retry: {} completed, {} failed, {} remaining",
+            self.completed, self.failed, self.remaining
+        )
+    }
+}
+
+/// 転写に失敗した録音のオフラインキュー
+#[derive(Debug, Clone)]
+pub struct TranscriptionQueue {
+    dir: PathBuf,
+}
+
+impl TranscriptionQueue {
+    /// 保存先を指定して作成
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// 保存先ディレクトリ
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 失敗した録音を保存
+    pub fn enqueue(
+        &self,
+        audio: &[u8],
+        duration_ms: u64,
+        options: TranscriptionOptions,
+        output: OutputMode,
+        error: &str,
+        retryable: bool,
+    ) -> io::Result<QueuedJob> {
+        fs::create_dir_all(&self.dir)?;
+        let now = SystemTime::now();
+        let id = self.next_id(now);
+        let created_at = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let job = QueuedJob {
+            id,
+            created_at,
+            duration_ms,
+            options,
+            output,
+            attempts: 1,
+            last_error: error.to_string(),
+            retryable,
+            failed_at: created_at,
+        };
+        // 音声を先に書き、メタデータの作成をもってジョブの追加とする
+        fs::write(self.audio_path(&job.id), audio)?;
+        self.write_job(&job)?;
+        Ok(job)
+    }
+
+    /// 保存されているジョブ（古い順）
+    pub fn list(&self) -> io::Result<Vec<QueuedJob>> {
+        let dir = match fs::read_dir(&self.dir) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut jobs = Vec::new();
+        for entry in dir {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            match serde_json::from_slice::<QueuedJob>(&fs::read(&path)?) {
+                Ok(job) => jobs.push(job),
+                Err(e) => eprintln!("Skipping unreadable queued job {}: {}", path.display(), e),
+            }
+        }
+        jobs.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Ok(jobs)
+    }
+
+    /// ID を指定してジョブを取得
+    pub fn get(&self, id: &str) -> io::Result<Option<QueuedJob>> {
+        match fs::read(self.job_path(id)) {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// ジョブの音声を読み込む
+    pub fn audio(&self, id: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.audio_path(id))
+    }
+
+    /// 再実行に失敗したことを記録
+    pub fn record_failure(
+        &self,
+        job: &mut QueuedJob,
+        error: &str,
+        retryable: bool,
+    ) -> io::Result<()> {
+        job.attempts += 1;
+        job.last_error = error.to_string();
+        job.retryable = retryable;
+        job.failed_at = now_secs();
+        self.write_job(job)
+    }
+
+    /// ジョブを削除し、存在したかを返す
+    pub fn remove(&self, id: &str) -> io::Result<bool> {
+        let existed = match fs::remove_file(self.job_path(id)) {
+            Ok(()) => true,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => false,
+            Err(e) => return Err(e),
+        };
+        match fs::remove_file(self.audio_path(id)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        Ok(existed)
+    }
+
+    /// すべてのジョブを削除し、削除した数を返す
+    pub fn clear(&self) -> io::Result<usize> {
+        let jobs = self.list()?;
+        for job in &jobs {
+            self.remove(&job.id)?;
+        }
+        Ok(jobs.len())
+    }
+
+    fn job_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    fn audio_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.wav", id))
+    }
+
+    /// メタデータを一時ファイル経由で書き込む
+    fn write_job(&self, job: &QueuedJob) -> io::Result<()> {
+        let path = self.job_path(&job.id);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(job)?)?;
+        fs::rename(tmp, path)
+    }
+
+    /// 保存日時から重複しない ID を採番（例: `20250101-093000`、重複時は `-2` 以降を付加）
+    fn next_id(&self, now: SystemTime) -> String {
+        let base = chrono::DateTime::<chrono::Local>::from(now)
+            .format("%Y%m%d-%H%M%S")
+            .to_string();
+        let mut id = base.clone();
+        let mut n = 1;
+        while self.job_path(&id).exists() || self.audio_path(&id).exists() {
+            n += 1;
+            id = format!("{}-{}", base, n);
+        }
+        id
+    }
+}
+
+/// 現在時刻（UNIX 秒）
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn options() -> TranscriptionOptions {
+        TranscriptionOptions {
+            language: Some("en".to_string()),
+            translate: Some("ja".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_enqueue_list_and_remove() {
+        let tmp = TempDir::new().unwrap();
+        let queue = TranscriptionQueue::new(tmp.path().join("queue"));
+        assert!(queue.list().unwrap().is_empty());
+
+        let first = queue
+            .enqueue(b"wav1", 1500, options(), OutputMode::Input, "offline", true)
+            .unwrap();
+        let second = queue
+            .enqueue(
+                b"wav2",
+                800,
+                Default::default(),
+                OutputMode::Stack,
+                "offline",
+                true,
+            )
+            .unwrap();
+        assert_ne!(first.id, second.id);
+
+        let jobs = queue.list().unwrap();
+        assert_eq!(jobs, vec![first.clone(), second.clone()]);
+        assert_eq!(jobs[0].options.translate.as_deref(), Some("ja"));
+        assert_eq!(queue.audio(&first.id).unwrap(), b"wav1");
+
+        assert!(queue.remove(&first.id).unwrap());
+        assert!(!queue.remove(&first.id).unwrap());
+        assert_eq!(queue.get(&first.id).unwrap(), None);
+        assert!(queue.audio(&first.id).is_err());
+
+        assert_eq!(queue.clear().unwrap(), 1);
+        assert!(queue.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_record_failure() {
+        let tmp = TempDir::new().unwrap();
+        let queue = TranscriptionQueue::new(tmp.path());
+        let mut job = queue
+            .enqueue(b"wav", 1000, options(), OutputMode::None, "offline", true)
+            .unwrap();
+
+        queue.record_failure(&mut job, "HTTP 401", false).unwrap();
+        let stored = queue.get(&job.id).unwrap().unwrap();
+        assert_eq!(stored.attempts, 2);
+        assert_eq!(stored.last_error, "HTTP 401");
+        assert!(!stored.retryable);
+    }
+
+    #[test]
+    fn test_due_for_auto_retry() {
+        let interval = Duration::from_secs(60);
+        let job = QueuedJob {
+            id: "job".to_string(),
+            created_at: 0,
+            duration_ms: 1000,
+            options: options(),
+            output: OutputMode::Input,
+            attempts: 1,
+            last_error: "offline".to_string(),
+            retryable: true,
+            failed_at: 1000,
+        };
+        assert!(!job.due_for_auto_retry(interval, 1059));
+        assert!(job.due_for_auto_retry(interval, 1060));
+
+        // 失敗するたびに間隔を延ばす
+        let third = QueuedJob {
+            attempts: 3,
+            ..job.clone()
+        };
+        assert!(!third.due_for_auto_retry(interval, 1239));
+        assert!(third.due_for_auto_retry(interval, 1240));
+
+        // 上限回数に達したジョブ・一時的でないエラーのジョブは自動では再実行しない
+        let exhausted = QueuedJob {
+            attempts: MAX_AUTO_RETRY_ATTEMPTS,
+            ..job.clone()
+        };
+        assert!(!exhausted.due_for_auto_retry(interval, u64::MAX));
+        let denied = QueuedJob {
+            retryable: false,
+            ..job
+        };
+        assert!(!denied.due_for_auto_retry(interval, u64::MAX));
+    }
+
+    #[test]
+    fn test_output_mode_serialization() {
+        assert_eq!(
+            serde_json::to_string(&OutputMode::Stack).unwrap(),
+            r#""stack""#
+        );
+        assert_eq!(OutputMode::Input.to_string(), "input");
+    }
+}
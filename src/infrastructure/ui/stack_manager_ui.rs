@@ -42,8 +42,11 @@ impl StackManagerApp {
                 }
                 // ハイライトタイマーの設定
                 self.on_stack_accessed(id);
+                // スタックを確認したら警告を消す
+                self.state.warning.clear();
             }
             UiNotification::StacksCleared => {
+                self.state.warning.clear();
                 self.state.stacks.clear();
                 self.state.total_count = 0;
                 self.state.last_accessed_id = None;
@@ -56,6 +59,15 @@ impl StackManagerApp {
                     self.state.last_accessed_id = None;
                 }
             }
+            UiNotification::ContinuousModeChanged(enabled) => {
+                self.state.continuous_mode = enabled;
+            }
+            UiNotification::PartialTranscript(text) => {
+                self.state.partial_text = text;
+            }
+            UiNotification::Warning(text) => {
+                self.state.warning = text;
+            }
         }
     }
 
@@ -81,6 +93,33 @@ impl StackManagerApp {
                 };
                 ui.label(mode_indicator);
 
+                // 連続音声入力中の表示
+                if self.state.continuous_mode {
+                    ui.label(
+                        RichText::new("🎙️ Continuous Dictation")
+                            .color(Color32::from_rgb(255, 165, 0))
+                            .font(FontId::new(14.0, FontFamily::Proportional)),
+                    );
+                }
+
+                // ストリーミング転写の途中結果
+                if !self.state.partial_text.is_empty() {
+                    ui.label(
+                        RichText::new(format!("✏️ {}", self.state.partial_text))
+                            .color(Color32::LIGHT_GRAY)
+                            .font(FontId::new(13.0, FontFamily::Proportional)),
+                    );
+                }
+
+                // 警告（信頼度の低い転写結果など）
+                if !self.state.warning.is_empty() {
+                    ui.label(
+                        RichText::new(format!("⚠️ {}", self.state.warning))
+                            .color(Color32::YELLOW)
+                            .font(FontId::new(13.0, FontFamily::Proportional)),
+                    );
+                }
+
                 ui.separator();
 
                 // スタック件数表示
@@ -494,6 +533,50 @@ mod tests {
         assert!(app.state.total_count > 9);
     }
 
+    #[test]
+    fn test_continuous_mode_notification() {
+        let (_tx, rx) = mpsc::unbounded_channel();
+        let mut app = StackManagerApp::new(rx);
+        assert!(!app.state.continuous_mode);
+
+        app.handle_notification(UiNotification::ContinuousModeChanged(true));
+        assert!(app.state.continuous_mode);
+
+        // スタックモードの切替は連続音声入力の表示に影響しない
+        app.handle_notification(UiNotification::ModeChanged(false));
+        assert!(app.state.continuous_mode);
+
+        app.handle_notification(UiNotification::ContinuousModeChanged(false));
+        assert!(!app.state.continuous_mode);
+    }
+
+    #[test]
+    fn test_partial_transcript_notification() {
+        let (_tx, rx) = mpsc::unbounded_channel();
+        let mut app = StackManagerApp::new(rx);
+
+        app.handle_notification(UiNotification::PartialTranscript("こんにちは".to_string()));
+        assert_eq!(app.state.partial_text, "こんにちは");
+
+        // 確定後は空文字で表示を消す
+        app.handle_notification(UiNotification::PartialTranscript(String::new()));
+        assert!(app.state.partial_text.is_empty());
+    }
+
+    #[test]
+    fn test_warning_notification() {
+        let (_tx, rx) = mpsc::unbounded_channel();
+        let mut app = StackManagerApp::new(rx);
+
+        app.handle_notification(UiNotification::Warning(
+            "Low confidence, saved to stack 1".to_string(),
+        ));
+        assert_eq!(app.state.warning, "Low confidence, saved to stack 1");
+
+        app.handle_notification(UiNotification::Warning(String::new()));
+        assert!(app.state.warning.is_empty());
+    }
+
     #[test]
     fn test_esc_key_guidance() {
         let (_tx, rx) = mpsc::unbounded_channel();
@@ -24,6 +24,14 @@ pub struct UiState {
     pub stacks: Vec<StackDisplayInfo>,
     pub total_count: usize,
     pub last_accessed_id: Option<u32>,
+    #[serde(default)]
+    pub continuous_mode: bool,
+    /// ストリーミング転写の途中結果（録音中のみ）
+    #[serde(default)]
+    pub partial_text: String,
+    /// 直近の警告（信頼度の低い転写結果など）
+    #[serde(default)]
+    pub warning: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +40,11 @@ pub enum UiNotification {
     StackAccessed(u32),
     StacksCleared,
     ModeChanged(bool),
+    ContinuousModeChanged(bool),
+    /// ストリーミング転写の途中結果（空文字で表示を消す）
+    PartialTranscript(String),
+    /// 警告（空文字で表示を消す）
+    Warning(String),
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,161 @@
+//! 転写の利用記録
+//!
+//! API に送った転写リクエストごとに、音声の長さ・送信バイト数・プロバイダー・モデル・応答時間を
+//! JSON Lines 形式で追記します。集計（日別・月別・推定料金）は読み込み時に料金表を使って行うため、
+//! 料金表を変更すると過去の記録の推定料金にも反映されます。
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::domain::usage::{PriceTable, UsageBudget, UsageRecord};
+use crate::infrastructure::config::{AppConfig, default_usage_path};
+
+/// 利用記録の設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageConfig {
+    /// 利用状況を記録するか
+    pub enabled: bool,
+    /// 記録ファイル（`None` ならデータディレクトリ配下の `usage.jsonl`）
+    pub path: Option<PathBuf>,
+    /// 料金表
+    pub prices: PriceTable,
+    /// 月間予算
+    pub budget: UsageBudget,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+            prices: PriceTable::default(),
+            budget: UsageBudget::default(),
+        }
+    }
+}
+
+impl UsageConfig {
+    /// 環境変数と設定ファイル（config.json の `prices`）から読み込む
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let usd = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.trim().parse::<f64>().ok())
+        };
+        Self {
+            enabled: std::env::var("VOICE_INPUT_USAGE")
+                .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+                .unwrap_or(defaults.enabled),
+            path: std::env::var("VOICE_INPUT_USAGE_FILE")
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .map(PathBuf::from),
+            prices: defaults.prices.with_overrides(&AppConfig::load().prices),
+            budget: UsageBudget {
+                soft_usd: usd("VOICE_INPUT_BUDGET_SOFT_USD"),
+                hard_usd: usd("VOICE_INPUT_BUDGET_HARD_USD"),
+            },
+        }
+    }
+
+    /// 記録ファイルのパス
+    pub fn path(&self) -> PathBuf {
+        self.path.clone().unwrap_or_else(default_usage_path)
+    }
+
+    /// 設定に従って記録ファイルを開く
+    pub fn open(&self) -> UsageLog {
+        UsageLog::new(self.path())
+    }
+}
+
+/// 利用記録ファイル（JSON Lines）
+#[derive(Debug, Clone)]
+pub struct UsageLog {
+    path: PathBuf,
+}
+
+impl UsageLog {
+    /// 記録ファイルを指定して作成
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 記録ファイルのパス
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 記録を 1 件追記
+    pub fn append(&self, record: &UsageRecord) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(&line)
+    }
+
+    /// すべての記録を読み込む（読めない行は無視）
+    pub fn load(&self) -> io::Result<Vec<UsageRecord>> {
+        let data = match fs::read_to_string(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        Ok(data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_load() {
+        let tmp = TempDir::new().unwrap();
+        let log = UsageLog::new(tmp.path().join("usage").join("usage.jsonl"));
+        assert!(log.load().unwrap().is_empty());
+
+        let record = UsageRecord {
+            timestamp: 1_700_000_000,
+            provider: "openai".to_string(),
+            model: "whisper-1".to_string(),
+            audio_secs: 12.5,
+            bytes: 400_044,
+            latency_ms: 900,
+            success: true,
+            route: None,
+        };
+        log.append(&record).unwrap();
+        log.append(&UsageRecord {
+            success: false,
+            ..record.clone()
+        })
+        .unwrap();
+
+        // 壊れた行は読み飛ばす
+        fs::OpenOptions::new()
+            .append(true)
+            .open(log.path())
+            .unwrap()
+            .write_all(b"{broken\n")
+            .unwrap();
+
+        let records = log.load().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], record);
+        assert!(!records[1].success);
+    }
+}
@@ -21,6 +21,15 @@ pub enum IpcCmd {
         paste: bool,
         prompt: Option<String>,
         direct_input: bool,
+        /// 転写言語（省略時はデーモンの既定言語、`"auto"` で自動判定）
+        #[serde(default)]
+        language: Option<String>,
+        /// 翻訳先の言語（省略時はプロファイル・デーモンの既定値、`"off"` で翻訳しない）
+        #[serde(default)]
+        translate: Option<String>,
+        /// プロファイル名（省略時はデーモンの既定のプロファイル）
+        #[serde(default)]
+        profile: Option<String>,
     },
     /// 録音停止
     Stop,
@@ -29,10 +38,21 @@ pub enum IpcCmd {
         paste: bool,
         prompt: Option<String>,
         direct_input: bool,
+        /// 転写言語（省略時はデーモンの既定言語、`"auto"` で自動判定）
+        #[serde(default)]
+        language: Option<String>,
+        /// 翻訳先の言語（省略時はプロファイル・デーモンの既定値、`"off"` で翻訳しない）
+        #[serde(default)]
+        translate: Option<String>,
+        /// プロファイル名（省略時はデーモンの既定のプロファイル）
+        #[serde(default)]
+        profile: Option<String>,
     },
     /// ステータス取得
     Status,
     ListDevices,
+    /// 入力デバイスの詳細（対応設定・選択デバイス）を取得。応答は `IpcResp::payload` で `DeviceReport` として読む
+    ListDevicesVerbose,
     Health,
     /// スタックモードを有効化
     EnableStackMode,
@@ -46,6 +66,78 @@ pub enum IpcCmd {
     ListStacks,
     /// 全スタックをクリア
     ClearStacks,
+    /// push-to-talk 録音開始（キー押下時）
+    StartPtt {
+        paste: bool,
+        prompt: Option<String>,
+        direct_input: bool,
+        /// 転写言語（省略時はデーモンの既定言語、`"auto"` で自動判定）
+        #[serde(default)]
+        language: Option<String>,
+        /// 翻訳先の言語（省略時はプロファイル・デーモンの既定値、`"off"` で翻訳しない）
+        #[serde(default)]
+        translate: Option<String>,
+        /// プロファイル名（省略時はデーモンの既定のプロファイル）
+        #[serde(default)]
+        profile: Option<String>,
+    },
+    /// push-to-talk 録音停止（キー解放時）
+    StopPtt,
+    /// 連続音声入力開始（発話ごとに区切って転写）
+    StartContinuous {
+        paste: bool,
+        prompt: Option<String>,
+        direct_input: bool,
+        /// 転写言語（省略時はデーモンの既定言語、`"auto"` で自動判定）
+        #[serde(default)]
+        language: Option<String>,
+        /// 翻訳先の言語（省略時はプロファイル・デーモンの既定値、`"off"` で翻訳しない）
+        #[serde(default)]
+        translate: Option<String>,
+        /// プロファイル名（省略時はデーモンの既定のプロファイル）
+        #[serde(default)]
+        profile: Option<String>,
+    },
+    /// 連続音声入力停止
+    StopContinuous,
+    /// 音声ファイルを文字起こしし、テキストを返す
+    TranscribeFile {
+        path: String,
+        /// 転写言語（省略時はデーモンの既定言語、`"auto"` で自動判定）
+        #[serde(default)]
+        language: Option<String>,
+        /// 翻訳先の言語（省略時はプロファイル・デーモンの既定値、`"off"` で翻訳しない）
+        #[serde(default)]
+        translate: Option<String>,
+        /// プロファイル名（省略時はデーモンの既定のプロファイル）
+        #[serde(default)]
+        profile: Option<String>,
+    },
+    /// 転写に失敗してキューに保存された録音を再実行。応答は `IpcResp::payload` で `QueueRetryReport` として読む
+    QueueRetry {
+        /// 再実行するジョブ ID（省略時はすべて）
+        #[serde(default)]
+        id: Option<String>,
+    },
+    /// 利用状況と推定料金の集計。応答は `IpcResp::payload` で `UsageReport` として読む
+    Usage {
+        /// 日別に集計する日数（省略時は 7 日）
+        #[serde(default)]
+        days: Option<u32>,
+    },
+    /// テキストを転写後の処理パイプラインに通す。応答は `IpcResp::payload` で `PipelineReport` として読む
+    PipelineTest {
+        text: String,
+        /// 転写言語（省略時はデーモンの既定言語）
+        #[serde(default)]
+        language: Option<String>,
+        /// 翻訳先の言語（省略時はプロファイル・デーモンの既定値、`"off"` で翻訳しない）
+        #[serde(default)]
+        translate: Option<String>,
+        /// プロファイル名（省略時はデーモンの既定のプロファイル）
+        #[serde(default)]
+        profile: Option<String>,
+    },
 }
 
 /// デーモンからの汎用レスポンス。
@@ -55,6 +147,21 @@ pub struct IpcResp {
     pub msg: String,
 }
 
+impl IpcResp {
+    /// 型付きのペイロードを JSON として `msg` に格納したレスポンスを作成
+    pub fn with_payload<T: Serialize>(payload: &T) -> serde_json::Result<Self> {
+        Ok(Self {
+            ok: true,
+            msg: serde_json::to_string(payload)?,
+        })
+    }
+
+    /// `msg` に格納された型付きペイロードを取り出す
+    pub fn payload<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_str(&self.msg)
+    }
+}
+
 /// シリアライズ可能な音声データ（メモリモード専用）
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AudioDataDto(pub Vec<u8>);
@@ -210,6 +317,9 @@ mod tests {
             paste: true,
             prompt: Some("test prompt".to_string()),
             direct_input: false,
+            language: None,
+            translate: None,
+            profile: None,
         };
 
         let json = serde_json::to_string(&cmd).unwrap();
@@ -220,6 +330,7 @@ mod tests {
                 paste,
                 prompt,
                 direct_input,
+                ..
             } => {
                 assert!(paste);
                 assert_eq!(prompt, Some("test prompt".to_string()));
@@ -277,6 +388,140 @@ mod tests {
         assert!(matches!(deserialized, IpcCmd::ClearStacks));
     }
 
+    #[test]
+    fn test_ptt_commands_serialization() {
+        let cmd = IpcCmd::StartPtt {
+            paste: false,
+            prompt: None,
+            direct_input: true,
+            language: None,
+            translate: None,
+            profile: None,
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        let deserialized: IpcCmd = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, cmd);
+
+        let cmd = IpcCmd::StopPtt;
+        let json = serde_json::to_string(&cmd).unwrap();
+        let deserialized: IpcCmd = serde_json::from_str(&json).unwrap();
+        assert!(matches!(deserialized, IpcCmd::StopPtt));
+    }
+
+    #[test]
+    fn test_language_is_optional() {
+        // language・translate・profile を含まない旧クライアントのメッセージも受け付ける
+        let json = r#"{"Start":{"paste":true,"prompt":null,"direct_input":true}}"#;
+        match serde_json::from_str::<IpcCmd>(json).unwrap() {
+            IpcCmd::Start {
+                language,
+                translate,
+                profile,
+                ..
+            } => {
+                assert_eq!(language, None);
+                assert_eq!(translate, None);
+                assert_eq!(profile, None);
+            }
+            _ => panic!("Expected Start command"),
+        }
+
+        let cmd = IpcCmd::Toggle {
+            paste: false,
+            prompt: None,
+            direct_input: true,
+            language: Some("auto".to_string()),
+            translate: Some("en".to_string()),
+            profile: Some("pr".to_string()),
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert_eq!(serde_json::from_str::<IpcCmd>(&json).unwrap(), cmd);
+    }
+
+    #[test]
+    fn test_usage_days_is_optional() {
+        let json = r#"{"Usage":{}}"#;
+        assert_eq!(
+            serde_json::from_str::<IpcCmd>(json).unwrap(),
+            IpcCmd::Usage { days: None }
+        );
+    }
+
+    #[test]
+    fn test_pipeline_test_options_are_optional() {
+        let json = r#"{"PipelineTest":{"text":"えー テスト"}}"#;
+        assert_eq!(
+            serde_json::from_str::<IpcCmd>(json).unwrap(),
+            IpcCmd::PipelineTest {
+                text: "えー テスト".to_string(),
+                language: None,
+                translate: None,
+                profile: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_queue_retry_id_is_optional() {
+        let json = r#"{"QueueRetry":{}}"#;
+        assert_eq!(
+            serde_json::from_str::<IpcCmd>(json).unwrap(),
+            IpcCmd::QueueRetry { id: None }
+        );
+    }
+
+    #[test]
+    fn test_typed_payload_roundtrip() {
+        use crate::infrastructure::audio::device::{DeviceReport, DeviceSelection};
+
+        let report = DeviceReport {
+            host: "CoreAudio".to_string(),
+            priority: None,
+            selection: DeviceSelection::NotConfigured,
+            devices: vec![],
+        };
+        let resp = IpcResp::with_payload(&report).unwrap();
+        assert!(resp.ok);
+
+        // ワイヤー上は通常の IpcResp として送受信される
+        let json = serde_json::to_string(&resp).unwrap();
+        let received: IpcResp = serde_json::from_str(&json).unwrap();
+        assert_eq!(received.payload::<DeviceReport>().unwrap(), report);
+    }
+
+    #[test]
+    fn test_transcribe_file_serialization() {
+        let cmd = IpcCmd::TranscribeFile {
+            path: "/tmp/memo.m4a".to_string(),
+            language: None,
+            translate: None,
+            profile: None,
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        let deserialized: IpcCmd = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, cmd);
+    }
+
+    #[test]
+    fn test_continuous_commands_serialization() {
+        let cmd = IpcCmd::StartContinuous {
+            paste: true,
+            prompt: Some("議事録".to_string()),
+            direct_input: false,
+            language: None,
+            translate: None,
+            profile: None,
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        let deserialized: IpcCmd = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, cmd);
+
+        let cmd = IpcCmd::StopContinuous;
+        let json = serde_json::to_string(&cmd).unwrap();
+        let deserialized: IpcCmd = serde_json::from_str(&json).unwrap();
+        assert!(matches!(deserialized, IpcCmd::StopContinuous));
+    }
+
     #[test]
     fn test_ipc_stack_resp_serialization() {
         use crate::domain::stack::StackInfo;
@@ -311,6 +556,9 @@ mod tests {
             paste: true,
             prompt: None,
             direct_input: false,
+            language: None,
+            translate: None,
+            profile: None,
         };
         let json = serde_json::to_string(&cmd).unwrap();
         assert!(json.contains("Start"));
@@ -325,6 +573,9 @@ mod tests {
             paste: false,
             prompt: Some("test".to_string()),
             direct_input: true,
+            language: None,
+            translate: None,
+            profile: None,
         };
         let json = serde_json::to_string(&cmd).unwrap();
         let deserialized: IpcCmd = serde_json::from_str(&json).unwrap();
@@ -333,6 +584,7 @@ mod tests {
                 paste,
                 prompt,
                 direct_input,
+                ..
             } => {
                 assert!(!paste);
                 assert_eq!(prompt, Some("test".to_string()));
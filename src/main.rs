@@ -2,10 +2,18 @@
 //! `Start` / `Stop` / `Toggle` / `Status` の各コマンドを `ipc::send_cmd` で送信します。
 use clap::Parser;
 use voice_input::{
-    cli::{Cli, Cmd, ConfigCmd, ConfigField, DictCmd, InputMode, StackModeCmd, resolve_input_mode},
+    cli::{
+        CacheCmd, Cli, Cmd, ConfigCmd, ConfigField, ContinuousCmd, DictCmd, InputMode, PipelineCmd,
+        PttCmd, QueueCmd, StackModeCmd, resolve_input_mode,
+    },
     domain::dict::{DictRepository, EntryStatus, WordEntry},
+    domain::pipeline::PipelineReport,
+    domain::usage::UsageReport,
+    infrastructure::audio::{decoder::probe_file, device::DeviceReport},
+    infrastructure::cache::CacheConfig,
     infrastructure::config::AppConfig,
     infrastructure::dict::JsonFileDictRepo,
+    infrastructure::queue::{MAX_AUTO_RETRY_ATTEMPTS, QueueConfig, QueueRetryReport},
     ipc::{IpcCmd, send_cmd},
     load_env,
     utils::config::EnvConfig,
@@ -34,12 +42,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         prompt: None,
         copy_and_paste: false,
         copy_only: false,
+        language: None,
+        translate: None,
+        profile: None,
     }) {
         /* 録音系 → IPC */
         Cmd::Start {
             prompt,
             copy_and_paste,
             copy_only,
+            language,
+            translate,
+            profile,
         } => {
             let input_mode = resolve_input_mode(copy_and_paste, copy_only)?;
             let direct_input = input_mode == InputMode::Direct;
@@ -52,6 +66,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 paste,
                 prompt,
                 direct_input,
+                language,
+                translate,
+                profile,
             })?
         }
         Cmd::Stop => relay(IpcCmd::Stop)?,
@@ -59,6 +76,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             prompt,
             copy_and_paste,
             copy_only,
+            language,
+            translate,
+            profile,
         } => {
             let input_mode = resolve_input_mode(copy_and_paste, copy_only)?;
             let direct_input = input_mode == InputMode::Direct;
@@ -71,6 +91,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 paste,
                 prompt,
                 direct_input,
+                language,
+                translate,
+                profile,
             })?
         }
         Cmd::Status => relay(IpcCmd::Status)?,
@@ -112,6 +135,123 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
+
+        /* キャッシュ操作 → ローカルディレクトリ */
+        Cmd::Cache { action } => {
+            let config = CacheConfig::from_env();
+            let cache = config.open();
+            match action {
+                CacheCmd::Stats => {
+                    let stats = cache.stats()?;
+                    let status = if config.enabled {
+                        "enabled"
+                    } else {
+                        "disabled"
+                    };
+                    println!("─ Transcription cache ──────");
+                    println!("• status : {status}");
+                    println!("• entries: {}", stats.entries);
+                    println!(
+                        "• size   : {:.1} MB / {:.1} MB",
+                        stats.bytes as f64 / (1024.0 * 1024.0),
+                        cache.max_bytes() as f64 / (1024.0 * 1024.0)
+                    );
+                    println!("• path   : {}", cache.dir().display());
+                }
+                CacheCmd::Clear => {
+                    let removed = cache.clear()?;
+                    println!("🗑️  Removed {removed} cached transcripts");
+                }
+            }
+        }
+        Cmd::Queue { action } => {
+            let queue = QueueConfig::from_env().open();
+            match action {
+                QueueCmd::List => {
+                    let jobs = queue.list()?;
+                    if jobs.is_empty() {
+                        println!("Queue is empty");
+                    }
+                    for job in jobs {
+                        let created = chrono::DateTime::from_timestamp(job.created_at as i64, 0)
+                            .map(|t| {
+                                t.with_timezone(&chrono::Local)
+                                    .format("%Y-%m-%d %H:%M")
+                                    .to_string()
+                            })
+                            .unwrap_or_default();
+                        println!(
+                            "{}  {}  {:.1}s  output={}  attempts={}",
+                            job.id,
+                            created,
+                            job.duration_ms as f64 / 1000.0,
+                            job.output,
+                            job.attempts
+                        );
+                        println!("    last error: {}", job.last_error);
+                        if !job.retryable || job.attempts >= MAX_AUTO_RETRY_ATTEMPTS {
+                            println!(
+                                "    not retried automatically (run `voice_input queue retry`)"
+                            );
+                        }
+                    }
+                }
+                // 結果はデーモンのスタックに保存するため IPC 経由で実行
+                QueueCmd::Retry { id } => {
+                    let resp = send_cmd(&IpcCmd::QueueRetry { id })?;
+                    if !resp.ok {
+                        eprintln!("Error: {}", resp.msg);
+                    } else {
+                        let report: QueueRetryReport = resp.payload()?;
+                        println!("{}", report);
+                    }
+                }
+                QueueCmd::Drop { id, all } => {
+                    if all {
+                        let removed = queue.clear()?;
+                        println!("🗑️  Removed {removed} queued recordings");
+                    } else if let Some(id) = id {
+                        if queue.remove(&id)? {
+                            println!("🗑️  Removed {id}");
+                        } else {
+                            eprintln!("Error: queued recording not found: {id}");
+                        }
+                    }
+                }
+            }
+        }
+        // 料金表と予算はデーモンの設定を使うため IPC 経由で集計
+        Cmd::Usage { days } => {
+            let resp = send_cmd(&IpcCmd::Usage { days: Some(days) })?;
+            if !resp.ok {
+                eprintln!("Error: {}", resp.msg);
+            } else {
+                let report: UsageReport = resp.payload()?;
+                println!("{}", report);
+            }
+        }
+        // 段の構成・プロファイル・辞書はデーモンの設定を使うため IPC 経由で実行
+        Cmd::Pipeline { action } => match action {
+            PipelineCmd::Test {
+                text,
+                language,
+                translate,
+                profile,
+            } => {
+                let resp = send_cmd(&IpcCmd::PipelineTest {
+                    text,
+                    language,
+                    translate,
+                    profile,
+                })?;
+                if !resp.ok {
+                    eprintln!("Error: {}", resp.msg);
+                } else {
+                    let report: PipelineReport = resp.payload()?;
+                    println!("{}", report);
+                }
+            }
+        },
         Cmd::Config { action } => match action {
             ConfigCmd::Set { field } => match field {
                 ConfigField::DictPath { path } => {
@@ -130,6 +270,85 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Cmd::Paste { number } => relay(IpcCmd::PasteStack { number })?,
         Cmd::ListStacks => relay(IpcCmd::ListStacks)?,
         Cmd::ClearStacks => relay(IpcCmd::ClearStacks)?,
+
+        /* push-to-talk → IPC */
+        Cmd::Ptt { action } => match action {
+            PttCmd::Start {
+                prompt,
+                copy_and_paste,
+                copy_only,
+                language,
+                translate,
+                profile,
+            } => {
+                let input_mode = resolve_input_mode(copy_and_paste, copy_only)?;
+                relay(IpcCmd::StartPtt {
+                    paste: input_mode != InputMode::CopyOnly,
+                    prompt,
+                    direct_input: input_mode == InputMode::Direct,
+                    language,
+                    translate,
+                    profile,
+                })?
+            }
+            PttCmd::Stop => relay(IpcCmd::StopPtt)?,
+        },
+
+        /* 連続音声入力 → IPC */
+        Cmd::Continuous { action } => match action {
+            ContinuousCmd::Start {
+                prompt,
+                copy_and_paste,
+                copy_only,
+                language,
+                translate,
+                profile,
+            } => {
+                let input_mode = resolve_input_mode(copy_and_paste, copy_only)?;
+                relay(IpcCmd::StartContinuous {
+                    paste: input_mode != InputMode::CopyOnly,
+                    prompt,
+                    direct_input: input_mode == InputMode::Direct,
+                    language,
+                    translate,
+                    profile,
+                })?
+            }
+            ContinuousCmd::Stop => relay(IpcCmd::StopContinuous)?,
+        },
+
+        /* デバイス一覧 → IPC */
+        Cmd::Devices { verbose: false } => relay(IpcCmd::ListDevices)?,
+        Cmd::Devices { verbose: true } => {
+            let resp = send_cmd(&IpcCmd::ListDevicesVerbose)?;
+            if !resp.ok {
+                eprintln!("Error: {}", resp.msg);
+            } else {
+                let report: DeviceReport = resp.payload()?;
+                println!("{}", report);
+            }
+        }
+
+        /* ファイル文字起こし → IPC */
+        Cmd::Transcribe {
+            file,
+            language,
+            translate,
+            profile,
+        } => {
+            // デーモンとカレントディレクトリが異なるため絶対パスで渡す
+            let path =
+                std::fs::canonicalize(&file).map_err(|e| format!("{}: {}", file.display(), e))?;
+            // 対応形式かをローカルで先に確認し、コーデック情報を表示
+            let info = probe_file(&path)?;
+            eprintln!("{}: {}", file.display(), info);
+            relay(IpcCmd::TranscribeFile {
+                path: path.to_string_lossy().into_owned(),
+                language,
+                translate,
+                profile,
+            })?
+        }
     }
     Ok(())
 }
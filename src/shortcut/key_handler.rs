@@ -6,15 +6,144 @@ use rdev::{Event, EventType, Key, grab};
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 
+/// push-to-talk に使用するデフォルトキー（macOSでは右Option）
+pub const DEFAULT_PTT_KEY: Key = Key::AltGr;
+
+/// 設定で指定可能なキー名（`{:?}` 表記と一致させる）
+const NAMED_KEYS: &[Key] = &[
+    Key::Alt,
+    Key::AltGr,
+    Key::ControlLeft,
+    Key::ControlRight,
+    Key::ShiftLeft,
+    Key::ShiftRight,
+    Key::MetaLeft,
+    Key::MetaRight,
+    Key::Function,
+    Key::CapsLock,
+    Key::F1,
+    Key::F2,
+    Key::F3,
+    Key::F4,
+    Key::F5,
+    Key::F6,
+    Key::F7,
+    Key::F8,
+    Key::F9,
+    Key::F10,
+    Key::F11,
+    Key::F12,
+    Key::Insert,
+    Key::Pause,
+    Key::ScrollLock,
+    Key::PrintScreen,
+    Key::Space,
+];
+
+/// キー名を `rdev::Key` に変換する
+///
+/// `AltGr` や `F5` などの名前（大文字小文字は区別しない）、
+/// またはフットペダル等のための数値キーコード（例: `105`）を受け付ける。
+pub fn parse_key(name: &str) -> Option<Key> {
+    let name = name.trim();
+    if let Ok(code) = name.parse::<u32>() {
+        return Some(Key::Unknown(code));
+    }
+    NAMED_KEYS
+        .iter()
+        .find(|k| format!("{:?}", k).eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// 環境変数から push-to-talk キーを取得
+///
+/// `VOICE_INPUT_HOTKEY_MODE=ptt` の場合のみ `Some` を返す。
+/// キーは `VOICE_INPUT_PTT_KEY` で指定し、未指定・不正な場合は [`DEFAULT_PTT_KEY`]。
+pub fn push_to_talk_key_from_env() -> Option<Key> {
+    let mode = std::env::var("VOICE_INPUT_HOTKEY_MODE").ok()?;
+    if !matches!(mode.trim(), "ptt" | "push-to-talk") {
+        return None;
+    }
+
+    match std::env::var("VOICE_INPUT_PTT_KEY") {
+        Ok(name) => parse_key(&name).or_else(|| {
+            eprintln!(
+                "⚠️  Unknown VOICE_INPUT_PTT_KEY '{}', falling back to {:?}",
+                name, DEFAULT_PTT_KEY
+            );
+            Some(DEFAULT_PTT_KEY)
+        }),
+        Err(_) => Some(DEFAULT_PTT_KEY),
+    }
+}
+
+/// push-to-talk キーイベントの分類結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PttEvent {
+    /// 押下開始（録音開始）
+    Pressed,
+    /// キーリピート（無視して抑制）
+    Repeat,
+    /// 解放（録音停止）
+    Released,
+}
+
+/// push-to-talk キーの押下状態を追跡する
+#[derive(Debug)]
+pub struct PushToTalk {
+    key: Key,
+    held: bool,
+}
+
+impl PushToTalk {
+    pub fn new(key: Key) -> Self {
+        Self { key, held: false }
+    }
+
+    /// キーイベントを分類する。対象キー以外のイベントは `None`
+    pub fn handle(&mut self, event_type: &EventType) -> Option<PttEvent> {
+        match event_type {
+            EventType::KeyPress(key) if *key == self.key => {
+                if self.held {
+                    Some(PttEvent::Repeat)
+                } else {
+                    self.held = true;
+                    Some(PttEvent::Pressed)
+                }
+            }
+            EventType::KeyRelease(key) if *key == self.key => {
+                if self.held {
+                    self.held = false;
+                    Some(PttEvent::Released)
+                } else {
+                    // 押下を取りこぼした解放は無視
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
 /// キーイベントを処理してIPCコマンドに変換するハンドラー
 pub struct KeyHandler {
     ipc_sender: mpsc::UnboundedSender<IpcCmd>,
+    ptt_key: Option<Key>,
 }
 
 impl KeyHandler {
     /// 新しいKeyHandlerインスタンスを作成
     pub fn new(ipc_sender: mpsc::UnboundedSender<IpcCmd>) -> Self {
-        Self { ipc_sender }
+        Self {
+            ipc_sender,
+            ptt_key: None,
+        }
+    }
+
+    /// push-to-talk モードを有効化する（Cmd+R トグルの代わりに `key` の押下中だけ録音）
+    pub fn with_push_to_talk(mut self, key: Key) -> Self {
+        self.ptt_key = Some(key);
+        self
     }
 
     /// キーイベントの抑制を開始
@@ -23,6 +152,7 @@ impl KeyHandler {
 
         // 状態変数を共有するためのArc<Mutex<T>>
         let ipc_sender = self.ipc_sender;
+        let ptt = self.ptt_key.map(|key| Mutex::new(PushToTalk::new(key)));
         let cmd_pressed = Arc::new(Mutex::new(false));
         let enabled = Arc::new(Mutex::new(true));
 
@@ -36,6 +166,35 @@ impl KeyHandler {
                 return Some(event);
             }
 
+            // push-to-talk キーの押下・解放
+            if let Some(ptt) = &ptt {
+                let ptt_event = ptt
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .handle(&event.event_type);
+                if let Some(ptt_event) = ptt_event {
+                    match ptt_event {
+                        PttEvent::Pressed => {
+                            let _ = ipc_sender.send(IpcCmd::StartPtt {
+                                paste: false,
+                                prompt: None,
+                                direct_input: false,
+                                language: None,
+                                translate: None,
+                                profile: None,
+                            });
+                            println!("Sent StartPtt command");
+                        }
+                        PttEvent::Released => {
+                            let _ = ipc_sender.send(IpcCmd::StopPtt);
+                            println!("Sent StopPtt command");
+                        }
+                        PttEvent::Repeat => {}
+                    }
+                    return None; // イベント抑制
+                }
+            }
+
             match event.event_type {
                 EventType::KeyPress(key) => {
                     // Cmdキー押下
@@ -47,12 +206,15 @@ impl KeyHandler {
                     // Cmdが押されている時のみ処理
                     if *cmd_pressed_clone.lock().unwrap_or_else(|e| e.into_inner()) {
                         match key {
-                            Key::KeyR => {
+                            Key::KeyR if ptt.is_none() => {
                                 // 録音開始/停止
                                 let _ = ipc_sender.send(IpcCmd::Toggle {
                                     paste: false,
                                     prompt: None,
                                     direct_input: false,
+                                    language: None,
+                                    translate: None,
+                                    profile: None,
                                 });
                                 println!("Sent Toggle command (Cmd+R)");
                                 return None; // イベント抑制
@@ -162,6 +324,9 @@ mod tests {
                 paste: false,
                 prompt: None,
                 direct_input: false,
+                language: None,
+                translate: None,
+                profile: None,
             })
             .unwrap();
 
@@ -211,4 +376,42 @@ mod tests {
         // Rキー単独では何も送信されない
         assert!(rx.try_recv().is_err());
     }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("AltGr"), Some(Key::AltGr));
+        assert_eq!(parse_key("f5"), Some(Key::F5));
+        assert_eq!(parse_key(" controlRight "), Some(Key::ControlRight));
+        assert_eq!(parse_key("105"), Some(Key::Unknown(105)));
+        assert_eq!(parse_key("NoSuchKey"), None);
+    }
+
+    #[test]
+    fn test_push_to_talk_press_and_release() {
+        let mut ptt = PushToTalk::new(Key::F5);
+
+        assert_eq!(
+            ptt.handle(&EventType::KeyPress(Key::F5)),
+            Some(PttEvent::Pressed)
+        );
+        // キーリピートは録音を再開しない
+        assert_eq!(
+            ptt.handle(&EventType::KeyPress(Key::F5)),
+            Some(PttEvent::Repeat)
+        );
+        assert_eq!(
+            ptt.handle(&EventType::KeyRelease(Key::F5)),
+            Some(PttEvent::Released)
+        );
+        // 押下を伴わない解放は無視
+        assert_eq!(ptt.handle(&EventType::KeyRelease(Key::F5)), None);
+    }
+
+    #[test]
+    fn test_push_to_talk_ignores_other_keys() {
+        let mut ptt = PushToTalk::new(Key::F5);
+
+        assert_eq!(ptt.handle(&EventType::KeyPress(Key::KeyR)), None);
+        assert_eq!(ptt.handle(&EventType::KeyRelease(Key::KeyR)), None);
+    }
 }
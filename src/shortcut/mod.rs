@@ -98,7 +98,11 @@ impl ShortcutService {
         }
 
         // KeyHandlerを非同期タスクで起動
-        let key_handler = KeyHandler::new(ipc_sender);
+        let mut key_handler = KeyHandler::new(ipc_sender);
+        if let Some(key) = key_handler::push_to_talk_key_from_env() {
+            println!("Push-to-talk mode enabled (key: {:?})", key);
+            key_handler = key_handler.with_push_to_talk(key);
+        }
 
         let handle = tokio::task::spawn_blocking(move || key_handler.start_grab());
 
@@ -20,6 +20,10 @@ static TEST_LOCK: Mutex<()> = Mutex::new(());
 pub struct EnvConfig {
     /// OpenAI APIキー
     pub openai_api_key: Option<String>,
+    /// OpenAI 互換 API のベース URL（未設定なら公式 API）
+    pub openai_base_url: Option<String>,
+    /// API キーを送る認証ヘッダー名（未設定なら `Authorization: Bearer`）
+    pub openai_auth_header: Option<String>,
     /// XDG Data Home ディレクトリ
     pub xdg_data_home: Option<String>,
     /// 環境変数ファイルのパス
@@ -38,6 +42,8 @@ impl EnvConfig {
 
         let config = EnvConfig {
             openai_api_key: std::env::var("OPENAI_API_KEY").ok(),
+            openai_base_url: std::env::var("OPENAI_BASE_URL").ok(),
+            openai_auth_header: std::env::var("OPENAI_AUTH_HEADER").ok(),
             xdg_data_home: std::env::var("XDG_DATA_HOME").ok(),
             env_path: std::env::var("VOICE_INPUT_ENV_PATH").ok(),
         };
@@ -82,6 +88,8 @@ impl EnvConfig {
             // テスト用のデフォルト設定
             let config = EnvConfig {
                 openai_api_key: std::env::var("OPENAI_API_KEY").ok(),
+                openai_base_url: std::env::var("OPENAI_BASE_URL").ok(),
+                openai_auth_header: std::env::var("OPENAI_AUTH_HEADER").ok(),
                 xdg_data_home: std::env::var("XDG_DATA_HOME").ok(),
                 env_path: std::env::var("VOICE_INPUT_ENV_PATH").ok(),
             };
@@ -0,0 +1,147 @@
+//! ローカル STT コマンドによる転写のテスト
+//!
+//! whisper.cpp などの代わりにスタブのシェルスクリプトを実行し、
+//! 一時ファイル/標準入力での受け渡し・出力の解釈・エラー処理を確認します。
+
+mod common;
+
+use common::silent_wav;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use voice_input::application::TranscriptionOptions;
+use voice_input::application::traits::TranscriptionClient;
+use voice_input::error::VoiceInputError;
+use voice_input::infrastructure::external::command_stt::{
+    CommandSttConfig, CommandTranscriptionClient, OutputFormat, WHISPER_CPP_COMMAND,
+};
+
+/// スタブスクリプトを書き出す（実行は `sh <script>` で行う）
+fn write_script(dir: &TempDir, name: &str, body: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    std::fs::write(&path, body).unwrap();
+    path
+}
+
+fn sh(script: &Path, args: &str) -> CommandSttConfig {
+    CommandSttConfig::new(&format!("sh '{}' {}", script.display(), args)).unwrap()
+}
+
+fn japanese() -> TranscriptionOptions {
+    TranscriptionOptions {
+        language: Some("ja".to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn passes_wav_via_temp_file() {
+    let dir = TempDir::new().unwrap();
+    let script = write_script(
+        &dir,
+        "stt.sh",
+        "test -f \"$1\" || exit 3\nhead -c 4 \"$1\"\necho\necho \"lang=$2 threads=$3 input=$1\"\n",
+    );
+    let client = CommandTranscriptionClient::new(
+        sh(&script, "{input} {language} {threads}").with_threads(2),
+    )
+    .unwrap();
+
+    let transcript = client.transcribe(silent_wav(), &japanese()).await.unwrap();
+    let (text, input) = transcript.text.split_once(" input=").unwrap();
+    assert_eq!(text, "RIFF lang=ja threads=2");
+    // 一時ファイルは実行後に削除される
+    assert!(!Path::new(input).exists());
+}
+
+#[tokio::test]
+async fn passes_wav_via_stdin() {
+    let dir = TempDir::new().unwrap();
+    let script = write_script(
+        &dir,
+        "stt.sh",
+        "bytes=$(wc -c | tr -d ' ')\necho \"stdin $bytes bytes, lang=$1\"\n",
+    );
+    let client = CommandTranscriptionClient::new(sh(&script, "{language}")).unwrap();
+
+    let audio = silent_wav();
+    let expected = format!("stdin {} bytes, lang=auto", audio.0.len());
+    let transcript = client
+        .transcribe(audio, &TranscriptionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(transcript.text, expected);
+}
+
+#[tokio::test]
+async fn parses_json_output() {
+    let dir = TempDir::new().unwrap();
+    let script = write_script(
+        &dir,
+        "stt.sh",
+        "cat > /dev/null\necho '{\"text\":\" ローカルで転写しました \",\"language\":\"ja\"}'\n",
+    );
+    let client =
+        CommandTranscriptionClient::new(sh(&script, "").with_output(OutputFormat::Json)).unwrap();
+
+    let transcript = client.transcribe(silent_wav(), &japanese()).await.unwrap();
+    assert_eq!(transcript.text, "ローカルで転写しました");
+    assert_eq!(transcript.language.as_deref(), Some("ja"));
+}
+
+#[tokio::test]
+async fn runs_whisper_cpp_style_command() {
+    let dir = TempDir::new().unwrap();
+    let model = write_script(&dir, "ggml-base.bin", "");
+    // whisper-cli と同じ引数を受け取り、セグメントごとに 1 行出力する
+    let script = write_script(
+        &dir,
+        "whisper-cli.sh",
+        "[ \"$1\" = -m ] && [ -f \"$2\" ] || exit 4\necho ' こんにちは。'\necho ' 音声入力です。'\n",
+    );
+
+    let mut config = CommandSttConfig::new(WHISPER_CPP_COMMAND)
+        .unwrap()
+        .with_model(model.to_string_lossy());
+    config.command.splice(
+        0..1,
+        ["sh".to_string(), script.to_string_lossy().into_owned()],
+    );
+    assert!(config.check().is_ok());
+
+    let client = CommandTranscriptionClient::new(config).unwrap();
+    let transcript = client.transcribe(silent_wav(), &japanese()).await.unwrap();
+    assert_eq!(transcript.text, "こんにちは。音声入力です。");
+}
+
+#[tokio::test]
+async fn reports_command_failure() {
+    let dir = TempDir::new().unwrap();
+    let script = write_script(&dir, "stt.sh", "echo 'failed to load model' >&2\nexit 2\n");
+    let client = CommandTranscriptionClient::new(sh(&script, "{input}")).unwrap();
+
+    let err = client
+        .transcribe(silent_wav(), &japanese())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, VoiceInputError::TranscriptionFailed(_)));
+    assert!(err.to_string().contains("failed to load model"), "{}", err);
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn reports_missing_executable() {
+    let client = CommandTranscriptionClient::new(
+        CommandSttConfig::new("voice-input-no-such-stt {input}").unwrap(),
+    )
+    .unwrap();
+
+    let err = client
+        .transcribe(silent_wav(), &japanese())
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("voice-input-no-such-stt"),
+        "{}",
+        err
+    );
+}
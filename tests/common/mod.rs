@@ -1,6 +1,20 @@
+// テストクレートごとに使うヘルパーが異なるため、未使用の警告を抑制する
+#![allow(dead_code)]
+
+pub mod realtime_server;
+pub mod stub_server;
+
+use voice_input::infrastructure::audio::{AudioData, CpalAudioBackend};
+
+/// 0.1 秒の無音 WAV
+pub fn silent_wav() -> AudioData {
+    let samples = vec![0i16; 1600];
+    AudioData(CpalAudioBackend::combine_wav_data(&samples, 16000, 1).unwrap())
+}
+
 // CI環境で実行可能なテストを示すマーカー
 #[cfg(feature = "ci-test")]
 pub const CI_TEST_MODE: bool = true;
 
 #[cfg(not(feature = "ci-test"))]
-pub const CI_TEST_MODE: bool = false;
\ No newline at end of file
+pub const CI_TEST_MODE: bool = false;
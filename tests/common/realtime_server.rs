@@ -0,0 +1,94 @@
+//! テスト用の Realtime 形式 WebSocket スタブサーバー
+//!
+//! プロセス内で起動し、受け取ったイベントとハンドシェイクのヘッダーを記録しながら、
+//! テストで指定した応答イベントを返します。
+
+use std::sync::{Arc, Mutex};
+
+use futures::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+type Responder = dyn Fn(&Value) -> Vec<Value> + Send + Sync;
+
+/// プロセス内 WebSocket スタブサーバー
+pub struct RealtimeServer {
+    url: String,
+    events: Arc<Mutex<Vec<Value>>>,
+    headers: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl RealtimeServer {
+    /// 受信イベントごとに `respond` で返すイベントを決めるサーバーを起動
+    pub async fn start(respond: impl Fn(&Value) -> Vec<Value> + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let headers = Arc::new(Mutex::new(Vec::new()));
+        let respond: Arc<Responder> = Arc::new(respond);
+
+        let (recorded, recorded_headers) = (events.clone(), headers.clone());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let (recorded, recorded_headers) = (recorded.clone(), recorded_headers.clone());
+                let respond = respond.clone();
+                tokio::spawn(async move {
+                    let callback = |request: &Request, response: Response| {
+                        *recorded_headers.lock().unwrap() = request
+                            .headers()
+                            .iter()
+                            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+                            .collect();
+                        Ok(response)
+                    };
+                    let Ok(mut socket) =
+                        tokio_tungstenite::accept_hdr_async(stream, callback).await
+                    else {
+                        return;
+                    };
+                    while let Some(Ok(message)) = socket.next().await {
+                        let Message::Text(text) = message else {
+                            continue;
+                        };
+                        let event: Value = serde_json::from_str(&text).unwrap();
+                        let replies = respond(&event);
+                        recorded.lock().unwrap().push(event);
+                        for reply in replies {
+                            if socket.send(Message::Text(reply.to_string())).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        Self {
+            url: format!("ws://{}/v1/realtime?intent=transcription", addr),
+            events,
+            headers,
+        }
+    }
+
+    /// 接続 URL（`ws://127.0.0.1:<port>/v1/realtime?intent=transcription`）
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// これまでに受け取ったイベント
+    pub fn events(&self) -> Vec<Value> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// ハンドシェイク時のヘッダー
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.headers
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+    }
+}
@@ -0,0 +1,210 @@
+//! テスト用の HTTP スタブサーバー
+//!
+//! プロセス内で起動し、受け取ったリクエストを記録しながら
+//! テストで指定した応答（ステータス・ヘッダー・遅延・切断）を返します。
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// スタブサーバーが受け取ったリクエスト
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// スタブサーバーの応答
+#[derive(Debug, Clone)]
+pub struct StubResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+    delay: Duration,
+    disconnect: bool,
+}
+
+impl StubResponse {
+    /// JSON ボディの応答
+    pub fn json(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+            delay: Duration::ZERO,
+            disconnect: false,
+        }
+    }
+
+    /// 応答を返さずに接続を切る
+    pub fn disconnect() -> Self {
+        Self {
+            disconnect: true,
+            ..Self::json(0, "")
+        }
+    }
+
+    /// ヘッダーを追加
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// 応答を遅らせる
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+type Responder = dyn Fn(&RecordedRequest) -> StubResponse + Send + Sync;
+
+/// プロセス内 HTTP スタブサーバー
+pub struct StubServer {
+    base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl StubServer {
+    /// リクエストごとに `respond` で応答を決めるサーバーを起動
+    pub async fn start(
+        respond: impl Fn(&RecordedRequest) -> StubResponse + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let respond: Arc<Responder> = Arc::new(respond);
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let recorded = recorded.clone();
+                let respond = respond.clone();
+                tokio::spawn(async move {
+                    handle_connection(stream, recorded, respond).await;
+                });
+            }
+        });
+
+        Self {
+            base_url: format!("http://{}/v1", addr),
+            requests,
+        }
+    }
+
+    /// 受信順に `script` の応答を返すサーバーを起動（尽きたら最後の応答を繰り返す）
+    pub async fn scripted(script: Vec<StubResponse>) -> Self {
+        let script = Mutex::new(VecDeque::from(script));
+        Self::start(move |_| {
+            let mut script = script.lock().unwrap();
+            if script.len() > 1 {
+                script.pop_front().unwrap()
+            } else {
+                script.front().cloned().expect("empty script")
+            }
+        })
+        .await
+    }
+
+    /// ベース URL（`http://127.0.0.1:<port>/v1`）
+    pub fn base_url(&self) -> String {
+        self.base_url.clone()
+    }
+
+    /// これまでに受け取ったリクエスト
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    recorded: Arc<Mutex<Vec<RecordedRequest>>>,
+    respond: Arc<Responder>,
+) {
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+    let response = respond(&request);
+    recorded.lock().unwrap().push(request);
+
+    tokio::time::sleep(response.delay).await;
+    if response.disconnect {
+        return;
+    }
+
+    let mut head = format!(
+        "HTTP/1.1 {} STUB\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    let raw = format!("{}\r\n{}", head, response.body);
+    let _ = stream.write_all(raw.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<RecordedRequest> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    // ヘッダー終端まで読む
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    while buf.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&buf[header_end..]).into_owned(),
+    })
+}
@@ -41,6 +41,9 @@ fn test_direct_input_flag_e2e() {
         paste: true,
         prompt: None,
         direct_input: true,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     match send_ipc_cmd(&cmd) {
@@ -64,6 +67,9 @@ fn test_no_direct_input_flag_e2e() {
         paste: true,
         prompt: None,
         direct_input: false,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     match send_ipc_cmd(&cmd) {
@@ -85,6 +91,9 @@ fn test_toggle_with_direct_input_e2e() {
         paste: true,
         prompt: Some("test prompt".to_string()),
         direct_input: true,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     match send_ipc_cmd(&cmd) {
@@ -124,6 +133,9 @@ fn test_ipc_cmd_json_format() {
         paste: true,
         prompt: Some("test".to_string()),
         direct_input: true,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     let json = serde_json::to_string(&cmd).unwrap();
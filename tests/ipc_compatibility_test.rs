@@ -29,6 +29,7 @@ fn test_backward_compatibility_with_default() {
             paste,
             prompt,
             direct_input,
+            ..
         } => {
             assert!(paste);
             assert_eq!(prompt, Some("test".to_string()));
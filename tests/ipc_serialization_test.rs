@@ -7,6 +7,9 @@ fn test_ipccmd_serialization_with_direct_input() {
         paste: true,
         prompt: Some("test prompt".to_string()),
         direct_input: true,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     let json = serde_json::to_string(&start_cmd).unwrap();
@@ -17,6 +20,7 @@ fn test_ipccmd_serialization_with_direct_input() {
             paste,
             prompt,
             direct_input,
+            ..
         } => {
             assert!(paste);
             assert_eq!(prompt, Some("test prompt".to_string()));
@@ -33,6 +37,9 @@ fn test_ipccmd_serialization_toggle() {
         paste: false,
         prompt: None,
         direct_input: false,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     let json = serde_json::to_string(&toggle_cmd).unwrap();
@@ -43,6 +50,7 @@ fn test_ipccmd_serialization_toggle() {
             paste,
             prompt,
             direct_input,
+            ..
         } => {
             assert!(!paste);
             assert_eq!(prompt, None);
@@ -60,16 +68,25 @@ fn test_ipccmd_json_roundtrip() {
             paste: true,
             prompt: None,
             direct_input: true,
+            language: None,
+            translate: None,
+            profile: None,
         },
         IpcCmd::Start {
             paste: false,
             prompt: Some("hello".to_string()),
             direct_input: false,
+            language: None,
+            translate: None,
+            profile: None,
         },
         IpcCmd::Toggle {
             paste: true,
             prompt: Some("world".to_string()),
             direct_input: true,
+            language: None,
+            translate: None,
+            profile: None,
         },
         IpcCmd::Stop,
         IpcCmd::Status,
@@ -94,6 +111,9 @@ fn test_ipccmd_json_format() {
         paste: true,
         prompt: Some("test".to_string()),
         direct_input: true,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     let json = serde_json::to_string(&cmd).unwrap();
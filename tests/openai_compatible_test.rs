@@ -0,0 +1,157 @@
+//! OpenAI 互換サーバーへの接続テスト
+//!
+//! プロセス内で起動したモック HTTP サーバーに対して `OpenAiClient` を実行し、
+//! ベース URL・モデル・認証ヘッダーの設定がリクエストに反映されることを確認します。
+
+mod common;
+
+use common::silent_wav;
+use common::stub_server::{StubResponse, StubServer};
+use voice_input::application::TranscriptionOptions;
+use voice_input::application::traits::TranscriptionClient;
+use voice_input::infrastructure::external::openai::{OpenAiClient, OpenAiConfig};
+use voice_input::infrastructure::external::openai_adapter::OpenAiTranscriptionAdapter;
+
+/// モックサーバーを起動
+///
+/// - `/denied/*` は 401 を返す
+/// - `*/audio/transcriptions` は転写結果の JSON を返す
+/// - `*/models` はモデル一覧の JSON を返す
+async fn spawn_mock_server() -> StubServer {
+    StubServer::start(|request| {
+        if request.path.starts_with("/denied") {
+            StubResponse::json(401, r#"{"error":"invalid api key"}"#)
+        } else if request.path.ends_with("/audio/transcriptions") {
+            StubResponse::json(200, r#"{"text":"ローカルで転写しました"}"#)
+        } else if request.path.ends_with("/models") {
+            StubResponse::json(200, r#"{"object":"list","data":[]}"#)
+        } else {
+            StubResponse::json(404, r#"{"error":"not found"}"#)
+        }
+    })
+    .await
+}
+
+#[tokio::test]
+async fn transcribes_against_compatible_server() {
+    let server = spawn_mock_server().await;
+    let client = OpenAiClient::with_config(OpenAiConfig {
+        base_url: server.base_url(),
+        api_key: Some("local-key".to_string()),
+        model: "Systran/faster-whisper-large-v3".to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let response = client
+        .transcribe_audio(silent_wav(), Some("ja"), Some("議事録"))
+        .await
+        .unwrap();
+    assert_eq!(response.text, "ローカルで転写しました");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/v1/audio/transcriptions");
+    assert_eq!(request.header("authorization"), Some("Bearer local-key"));
+    assert!(request.body.contains("Systran/faster-whisper-large-v3"));
+    assert!(request.body.contains("name=\"language\""));
+    assert!(request.body.contains("議事録"));
+}
+
+#[tokio::test]
+async fn sends_key_in_custom_auth_header() {
+    let server = spawn_mock_server().await;
+    let client = OpenAiClient::with_config(OpenAiConfig {
+        base_url: server.base_url(),
+        api_key: Some("proxy-secret".to_string()),
+        auth_header: "api-key".to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    client.check_connection().await.unwrap();
+
+    let requests = server.requests();
+    let request = &requests[0];
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/v1/models");
+    assert_eq!(request.header("api-key"), Some("proxy-secret"));
+    assert_eq!(request.header("authorization"), None);
+}
+
+#[tokio::test]
+async fn works_without_api_key_for_self_hosted_server() {
+    let server = spawn_mock_server().await;
+    let client = OpenAiClient::with_config(OpenAiConfig {
+        base_url: server.base_url(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let response = client
+        .transcribe_audio(silent_wav(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(response.text, "ローカルで転写しました");
+
+    let requests = server.requests();
+    assert_eq!(requests[0].header("authorization"), None);
+    // 言語指定なしの場合は language パラメータを送らない
+    assert!(!requests[0].body.contains("name=\"language\""));
+}
+
+#[tokio::test]
+async fn reports_error_status_from_server() {
+    let server = spawn_mock_server().await;
+    let client = OpenAiClient::with_config(OpenAiConfig {
+        base_url: server.base_url().replace("/v1", "/denied"),
+        api_key: Some("wrong".to_string()),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let err = client
+        .transcribe_audio(silent_wav(), Some("ja"), None)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("401"), "{}", err);
+
+    let err = client.check_connection().await.unwrap_err();
+    assert!(err.contains("401"), "{}", err);
+}
+
+#[tokio::test]
+async fn requests_segment_and_word_timestamps() {
+    let server = StubServer::scripted(vec![StubResponse::json(
+        200,
+        r#"{"task":"transcribe","language":"japanese","duration":2.0,"text":"音声入力です",
+            "segments":[{"id":0,"start":0.0,"end":2.0,"text":"音声入力です","avg_logprob":-0.3,"no_speech_prob":0.02}],
+            "words":[{"word":"音声","start":0.2,"end":0.8},{"word":"入力です","start":0.8,"end":1.9}]}"#,
+    )])
+    .await;
+    let adapter = OpenAiTranscriptionAdapter::with_config(OpenAiConfig {
+        base_url: server.base_url(),
+        model: "whisper-1".to_string(),
+        word_timestamps: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    let transcript = adapter
+        .transcribe(silent_wav(), &TranscriptionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(transcript.text, "音声入力です");
+    assert_eq!(transcript.language.as_deref(), Some("japanese"));
+    assert_eq!(transcript.segments.len(), 1);
+    assert_eq!(transcript.segments[0].avg_logprob, Some(-0.3));
+    assert_eq!(transcript.words.len(), 2);
+    assert_eq!(transcript.words[1].start, 0.8);
+
+    let body = &server.requests()[0].body;
+    assert!(body.contains("verbose_json"));
+    assert!(body.contains("timestamp_granularities[]"));
+    assert!(body.contains("word"));
+}
@@ -53,7 +53,9 @@ async fn measure_performance() -> Result<PerformanceMetrics, Box<dyn Error>> {
     // OpenAI API呼び出し
     let client = OpenAiClient::new()?;
     let transcription_start = Instant::now();
-    let _result = client.transcribe_audio(audio_data).await?;
+    let _result = client
+        .transcribe_audio(audio_data, Some("ja"), None)
+        .await?;
 
     let total_end = Instant::now();
 
@@ -0,0 +1,131 @@
+//! LLM による書き直しのテスト
+//!
+//! プロセス内で起動したモック HTTP サーバーを Chat Completions 互換エンドポイントとして使い、
+//! 転写結果の書き直しと、失敗・タイムアウト時に転写結果へ戻ることを確認します。
+
+mod common;
+
+use std::io;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common::stub_server::{StubResponse, StubServer};
+use serde_json::Value;
+use voice_input::application::traits::TranscriptionClient;
+use voice_input::application::{TranscriptionOptions, TranscriptionService};
+use voice_input::domain::dict::{DictRepository, WordEntry};
+use voice_input::domain::transcript::Transcript;
+use voice_input::error::Result;
+use voice_input::infrastructure::audio::cpal_backend::AudioData;
+use voice_input::infrastructure::external::chat::{ChatClient, ChatConfig};
+
+const RAW: &str = "えーと明日の会議はあの十時からです";
+
+/// 固定の転写結果を返すクライアント
+struct FixedClient;
+
+#[async_trait]
+impl TranscriptionClient for FixedClient {
+    async fn transcribe(
+        &self,
+        _audio: AudioData,
+        _options: &TranscriptionOptions,
+    ) -> Result<Transcript> {
+        Ok(Transcript::new(RAW))
+    }
+}
+
+/// 空の辞書
+struct EmptyDict;
+
+impl DictRepository for EmptyDict {
+    fn load(&self) -> io::Result<Vec<WordEntry>> {
+        Ok(Vec::new())
+    }
+
+    fn save(&self, _all: &[WordEntry]) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn completion(content: &str) -> String {
+    serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "choices": [{"index": 0, "message": {"role": "assistant", "content": content}, "finish_reason": "stop"}]
+    })
+    .to_string()
+}
+
+fn service(server: &StubServer, timeout: Duration) -> TranscriptionService {
+    let rewriter = ChatClient::new(ChatConfig {
+        base_url: server.base_url(),
+        model: "local-llm".to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+    TranscriptionService::new(Box::new(FixedClient), Box::new(EmptyDict), 1)
+        .with_rewriter(Box::new(rewriter))
+        .with_rewrite_instructions(Some("Remove fillers.".to_string()))
+        .with_rewrite_timeout(timeout)
+}
+
+async fn transcribe(service: &TranscriptionService) -> String {
+    service
+        .transcribe(AudioData(vec![0u8; 64]), TranscriptionOptions::default())
+        .await
+        .unwrap()
+        .text
+}
+
+#[tokio::test]
+async fn rewrites_transcript_with_instructions() {
+    let server = StubServer::scripted(vec![StubResponse::json(
+        200,
+        &completion("明日の会議は10時からです。\n"),
+    )])
+    .await;
+
+    let text = transcribe(&service(&server, Duration::from_secs(2))).await;
+    assert_eq!(text, "明日の会議は10時からです。");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/chat/completions");
+    let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["model"], "local-llm");
+    assert!(
+        body["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains("Remove fillers.")
+    );
+    assert_eq!(body["messages"][1]["content"], RAW);
+}
+
+#[tokio::test]
+async fn falls_back_to_raw_text_on_error() {
+    let server = StubServer::scripted(vec![
+        StubResponse::json(500, r#"{"error":"boom"}"#),
+        StubResponse::disconnect(),
+    ])
+    .await;
+    let service = service(&server, Duration::from_secs(2));
+
+    assert_eq!(transcribe(&service).await, RAW);
+    assert_eq!(transcribe(&service).await, RAW);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn falls_back_to_raw_text_on_timeout() {
+    let server = StubServer::scripted(vec![
+        StubResponse::json(200, &completion("late")).delayed(Duration::from_secs(5)),
+    ])
+    .await;
+
+    let started = std::time::Instant::now();
+    let text = transcribe(&service(&server, Duration::from_millis(200))).await;
+    assert_eq!(text, RAW);
+    assert!(started.elapsed() < Duration::from_secs(3));
+}
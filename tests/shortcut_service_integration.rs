@@ -29,6 +29,9 @@ async fn test_ipc_channel_integration() {
         paste: false,
         prompt: None,
         direct_input: false,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     let send_result = tx.send(test_cmd);
@@ -43,6 +46,7 @@ async fn test_ipc_channel_integration() {
             paste,
             prompt,
             direct_input,
+            ..
         } => {
             assert!(!paste);
             assert!(prompt.is_none());
@@ -126,6 +130,9 @@ async fn test_multiple_ipc_commands() {
             paste: true,
             prompt: Some("test".to_string()),
             direct_input: false,
+            language: None,
+            translate: None,
+            profile: None,
         },
         IpcCmd::PasteStack { number: 1 },
         IpcCmd::PasteStack { number: 9 },
@@ -133,6 +140,9 @@ async fn test_multiple_ipc_commands() {
             paste: false,
             prompt: None,
             direct_input: true,
+            language: None,
+            translate: None,
+            profile: None,
         },
     ];
 
@@ -152,11 +162,13 @@ async fn test_multiple_ipc_commands() {
                     paste: p1,
                     prompt: pr1,
                     direct_input: d1,
+                    ..
                 },
                 IpcCmd::Toggle {
                     paste: p2,
                     prompt: pr2,
                     direct_input: d2,
+                    ..
                 },
             ) => {
                 assert_eq!(p1, p2);
@@ -185,6 +197,9 @@ fn test_cli_args_parsing() {
         paste: false,
         prompt: None,
         direct_input: false,
+        language: None,
+        translate: None,
+        profile: None,
     };
 
     match cmd {
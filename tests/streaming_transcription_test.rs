@@ -0,0 +1,185 @@
+//! ストリーミング転写のテスト
+//!
+//! プロセス内で起動した Realtime 形式の WebSocket スタブサーバーに対して
+//! `RealtimeTranscriptionClient` を実行し、音声の逐次送信・途中結果・停止後の確定結果を確認します。
+
+mod common;
+
+use base64::Engine;
+use common::realtime_server::RealtimeServer;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use voice_input::application::TranscriptionOptions;
+use voice_input::application::traits::StreamingTranscriptionClient;
+use voice_input::error::VoiceInputError;
+use voice_input::infrastructure::audio::PcmChunk;
+use voice_input::infrastructure::external::realtime::{
+    RealtimeConfig, RealtimeTranscriptionClient,
+};
+
+fn client(server: &RealtimeServer) -> RealtimeTranscriptionClient {
+    RealtimeTranscriptionClient::new(RealtimeConfig {
+        url: server.url(),
+        api_key: Some("realtime-key".to_string()),
+        timeout: Duration::from_secs(5),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+/// 48kHz ステレオで 0.1 秒分の音声
+fn chunk() -> PcmChunk {
+    PcmChunk {
+        samples: vec![1000; 9600],
+        sample_rate: 48_000,
+        channels: 2,
+    }
+}
+
+/// 発話 1 件分の転写イベント（committed → delta → completed）
+fn utterance(item: &str, partial: &str, text: &str) -> Vec<Value> {
+    vec![
+        json!({"type": "input_audio_buffer.committed", "item_id": item}),
+        json!({"type": "conversation.item.input_audio_transcription.delta", "item_id": item, "delta": partial}),
+        json!({"type": "conversation.item.input_audio_transcription.completed", "item_id": item, "transcript": text}),
+    ]
+}
+
+/// サーバー側 VAD が区切った発話のイベント（speech_stopped に続いてコミットされる）
+fn vad_utterance(item: &str, partial: &str, text: &str) -> Vec<Value> {
+    let mut events = vec![json!({"type": "input_audio_buffer.speech_stopped", "item_id": item})];
+    events.extend(utterance(item, partial, text));
+    events
+}
+
+#[tokio::test]
+async fn streams_audio_and_returns_final_transcript() {
+    // 2 回目の送信でサーバー側 VAD が発話を区切り、残りは停止時のコミットで確定する
+    let appended = AtomicUsize::new(0);
+    let server = RealtimeServer::start(move |event| match event["type"].as_str() {
+        Some("input_audio_buffer.append") if appended.fetch_add(1, Ordering::SeqCst) == 1 => {
+            vad_utterance("item_1", "こんにち", "こんにちは。")
+        }
+        Some("input_audio_buffer.commit") => utterance("item_2", "音声", "音声入力です。"),
+        _ => Vec::new(),
+    })
+    .await;
+
+    let (partial_tx, mut partial_rx) = mpsc::unbounded_channel();
+    let options = TranscriptionOptions {
+        language: Some("ja".to_string()),
+        prompt: Some("議事録".to_string()),
+        ..Default::default()
+    };
+    let mut stream = client(&server).open(&options, partial_tx).await.unwrap();
+    for _ in 0..3 {
+        stream.send_audio(&chunk()).await.unwrap();
+    }
+    let transcript = stream.finish().await.unwrap();
+    assert_eq!(transcript.text, "こんにちは。音声入力です。");
+    assert_eq!(transcript.provider.as_deref(), Some("realtime"));
+
+    // 途中結果は確定済みの発話を含む全文で届く
+    let mut partials = Vec::new();
+    while let Ok(partial) = partial_rx.try_recv() {
+        partials.push(partial);
+    }
+    assert_eq!(partials.first().map(String::as_str), Some("こんにち"));
+    assert!(
+        partials.contains(&"こんにちは。音声".to_string()),
+        "{:?}",
+        partials
+    );
+
+    let events = server.events();
+    let session = &events[0]["session"];
+    assert_eq!(events[0]["type"], "transcription_session.update");
+    assert_eq!(session["input_audio_format"], "pcm16");
+    assert_eq!(session["input_audio_transcription"]["language"], "ja");
+    assert_eq!(session["input_audio_transcription"]["prompt"], "議事録");
+
+    // 24kHz モノラルの pcm16 に変換して送る
+    let appends: Vec<&Value> = events
+        .iter()
+        .filter(|e| e["type"] == "input_audio_buffer.append")
+        .collect();
+    assert_eq!(appends.len(), 3);
+    let audio = base64::engine::general_purpose::STANDARD
+        .decode(appends[0]["audio"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(audio.len(), 2400 * 2);
+    assert_eq!(events.last().unwrap()["type"], "input_audio_buffer.commit");
+
+    assert_eq!(
+        server.header("authorization").as_deref(),
+        Some("Bearer realtime-key")
+    );
+    assert_eq!(server.header("openai-beta").as_deref(), Some("realtime=v1"));
+}
+
+#[tokio::test]
+async fn finishes_when_nothing_is_left_to_commit() {
+    // 発話は録音中に確定済みで、停止時のコミットは空になる
+    let server = RealtimeServer::start(|event| match event["type"].as_str() {
+        Some("input_audio_buffer.append") => vad_utterance("item_1", "Fix", "Fix typo"),
+        Some("input_audio_buffer.commit") => vec![json!({
+            "type": "error",
+            "error": {"code": "input_audio_buffer_commit_empty", "message": "buffer too small"}
+        })],
+        _ => Vec::new(),
+    })
+    .await;
+
+    let (partial_tx, _partial_rx) = mpsc::unbounded_channel();
+    let mut stream = client(&server)
+        .open(&TranscriptionOptions::default(), partial_tx)
+        .await
+        .unwrap();
+    stream.send_audio(&chunk()).await.unwrap();
+    let transcript = stream.finish().await.unwrap();
+    assert_eq!(transcript.text, "Fix typo");
+    // 自動判定時は言語を送らない
+    assert!(server.events()[0]["session"]["input_audio_transcription"]["language"].is_null());
+}
+
+#[tokio::test]
+async fn reports_server_error() {
+    let server = RealtimeServer::start(|event| match event["type"].as_str() {
+        Some("input_audio_buffer.commit") => vec![json!({
+            "type": "error",
+            "error": {"code": "model_not_found", "message": "unknown model"}
+        })],
+        _ => Vec::new(),
+    })
+    .await;
+
+    let (partial_tx, _partial_rx) = mpsc::unbounded_channel();
+    let mut stream = client(&server)
+        .open(&TranscriptionOptions::default(), partial_tx)
+        .await
+        .unwrap();
+    stream.send_audio(&chunk()).await.unwrap();
+    let err = stream.finish().await.unwrap_err();
+    assert!(matches!(err, VoiceInputError::TranscriptionFailed(_)));
+    assert!(err.to_string().contains("unknown model"), "{}", err);
+}
+
+#[tokio::test]
+async fn reports_unreachable_server() {
+    let client = RealtimeTranscriptionClient::new(RealtimeConfig {
+        url: "ws://127.0.0.1:1/v1/realtime".to_string(),
+        timeout: Duration::from_secs(5),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let (partial_tx, _partial_rx) = mpsc::unbounded_channel();
+    let err = client
+        .open(&TranscriptionOptions::default(), partial_tx)
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, VoiceInputError::NetworkError(_)), "{}", err);
+}
@@ -0,0 +1,131 @@
+//! 転写プロバイダーのフォールバックテスト
+//!
+//! 障害を返すスタブサーバー（クラウド）からスタブスクリプト（ローカル CLI）へ
+//! 切り替わること、切り替えない失敗では切り替わらないことを確認します。
+
+mod common;
+
+use common::silent_wav;
+use common::stub_server::{StubResponse, StubServer};
+use std::time::Duration;
+use tempfile::TempDir;
+use voice_input::application::traits::TranscriptionClient;
+use voice_input::application::{
+    FallbackTranscriptionClient, RetryPolicy, RetryingTranscriptionClient, TranscriptionOptions,
+};
+use voice_input::error::VoiceInputError;
+use voice_input::infrastructure::external::command_stt::{
+    CommandSttConfig, CommandTranscriptionClient,
+};
+use voice_input::infrastructure::external::openai::OpenAiConfig;
+use voice_input::infrastructure::external::openai_adapter::OpenAiTranscriptionAdapter;
+
+fn cloud(server: &StubServer) -> Box<dyn TranscriptionClient> {
+    let adapter = OpenAiTranscriptionAdapter::with_config(OpenAiConfig {
+        base_url: server.base_url(),
+        ..Default::default()
+    })
+    .unwrap();
+    Box::new(RetryingTranscriptionClient::new(
+        Box::new(adapter),
+        RetryPolicy {
+            max_attempts: 2,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(10),
+            request_timeout: Duration::from_secs(2),
+            max_total: Duration::from_secs(5),
+        },
+    ))
+}
+
+fn local(dir: &TempDir) -> Box<dyn TranscriptionClient> {
+    let script = dir.path().join("stt.sh");
+    std::fs::write(&script, "cat > /dev/null\necho 'ローカルで転写しました'\n").unwrap();
+    let config = CommandSttConfig::new(&format!("sh '{}'", script.display())).unwrap();
+    Box::new(CommandTranscriptionClient::new(config).unwrap())
+}
+
+#[tokio::test]
+async fn falls_back_to_local_command_on_outage() {
+    let server = StubServer::scripted(vec![StubResponse::json(
+        503,
+        r#"{"error":"service unavailable"}"#,
+    )])
+    .await;
+    let dir = TempDir::new().unwrap();
+    let client = FallbackTranscriptionClient::new()
+        .with_provider("openai", cloud(&server))
+        .with_provider("command", local(&dir));
+
+    let transcript = client
+        .transcribe(silent_wav(), &TranscriptionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(transcript.text, "ローカルで転写しました");
+    assert_eq!(transcript.provider.as_deref(), Some("command"));
+    // クラウド側は再試行を使い切ってから切り替わる
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn falls_back_when_quota_is_exhausted() {
+    let server = StubServer::scripted(vec![StubResponse::json(
+        402,
+        r#"{"error":"billing hard limit reached"}"#,
+    )])
+    .await;
+    let dir = TempDir::new().unwrap();
+    let client = FallbackTranscriptionClient::new()
+        .with_provider("openai", cloud(&server))
+        .with_provider("command", local(&dir));
+
+    let transcript = client
+        .transcribe(silent_wav(), &TranscriptionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(transcript.provider.as_deref(), Some("command"));
+    // 402 は再試行しない
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn keeps_primary_result_and_skips_fallback() {
+    let server = StubServer::scripted(vec![StubResponse::json(
+        200,
+        r#"{"text":"クラウドで転写"}"#,
+    )])
+    .await;
+    let dir = TempDir::new().unwrap();
+    let client = FallbackTranscriptionClient::new()
+        .with_provider("openai", cloud(&server))
+        .with_provider("command", local(&dir));
+
+    let transcript = client
+        .transcribe(silent_wav(), &TranscriptionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(transcript.text, "クラウドで転写");
+    assert_eq!(transcript.provider.as_deref(), Some("openai"));
+}
+
+#[tokio::test]
+async fn does_not_fall_back_on_rejected_audio() {
+    let server = StubServer::scripted(vec![StubResponse::json(
+        400,
+        r#"{"error":"audio file is too short"}"#,
+    )])
+    .await;
+    let dir = TempDir::new().unwrap();
+    let client = FallbackTranscriptionClient::new()
+        .with_provider("openai", cloud(&server))
+        .with_provider("command", local(&dir));
+
+    let err = client
+        .transcribe(silent_wav(), &TranscriptionOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        VoiceInputError::ApiStatus { status: 400, .. }
+    ));
+}
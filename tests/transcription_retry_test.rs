@@ -0,0 +1,205 @@
+//! 転写リクエストの再試行テスト
+//!
+//! スクリプトどおりに失敗を返すスタブサーバーに対して
+//! `OpenAiTranscriptionAdapter` を `RetryingTranscriptionClient` で包んで実行し、
+//! バックオフ・`Retry-After`・タイムアウト・期限の扱いを確認します。
+
+mod common;
+
+use common::silent_wav;
+use common::stub_server::{StubResponse, StubServer};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use voice_input::application::traits::TranscriptionClient;
+use voice_input::application::{
+    RetryEvent, RetryPolicy, RetryingTranscriptionClient, TranscriptionOptions,
+};
+use voice_input::error::VoiceInputError;
+use voice_input::infrastructure::external::openai::OpenAiConfig;
+use voice_input::infrastructure::external::openai_adapter::OpenAiTranscriptionAdapter;
+
+const SUCCESS: &str = r#"{"text":"再試行で転写しました"}"#;
+
+fn policy() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: 4,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(50),
+        request_timeout: Duration::from_millis(500),
+        max_total: Duration::from_secs(10),
+    }
+}
+
+fn client(
+    server: &StubServer,
+    policy: RetryPolicy,
+) -> (
+    RetryingTranscriptionClient,
+    mpsc::UnboundedReceiver<RetryEvent>,
+) {
+    let adapter = OpenAiTranscriptionAdapter::with_config(OpenAiConfig {
+        base_url: server.base_url(),
+        ..Default::default()
+    })
+    .unwrap();
+    let (tx, rx) = mpsc::unbounded_channel();
+    (
+        RetryingTranscriptionClient::new(Box::new(adapter), policy).with_events(tx),
+        rx,
+    )
+}
+
+fn options() -> TranscriptionOptions {
+    TranscriptionOptions {
+        language: Some("ja".to_string()),
+        ..Default::default()
+    }
+}
+
+fn scheduled(events: &[RetryEvent]) -> Vec<(Duration, String)> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            RetryEvent::RetryScheduled { delay, reason, .. } => Some((*delay, reason.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn drain(rx: &mut mpsc::UnboundedReceiver<RetryEvent>) -> Vec<RetryEvent> {
+    std::iter::from_fn(|| rx.try_recv().ok()).collect()
+}
+
+#[tokio::test]
+async fn retries_server_errors_and_honours_retry_after() {
+    let server = StubServer::scripted(vec![
+        StubResponse::json(503, r#"{"error":"overloaded"}"#),
+        StubResponse::json(429, r#"{"error":"rate limited"}"#).with_header("Retry-After", "1"),
+        StubResponse::json(200, SUCCESS),
+    ])
+    .await;
+    let (client, mut events) = client(&server, policy());
+
+    let started = Instant::now();
+    let transcript = client.transcribe(silent_wav(), &options()).await.unwrap();
+    assert_eq!(transcript.text, "再試行で転写しました");
+    assert_eq!(server.requests().len(), 3);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+
+    let events = drain(&mut events);
+    let retries = scheduled(&events);
+    assert_eq!(retries.len(), 2);
+    assert!(retries[0].1.contains("503"), "{}", retries[0].1);
+    assert!(retries[1].1.contains("429"), "{}", retries[1].1);
+    assert_eq!(retries[1].0, Duration::from_secs(1));
+    assert!(matches!(
+        events.last(),
+        Some(RetryEvent::Succeeded { attempt: 3, .. })
+    ));
+}
+
+#[tokio::test]
+async fn does_not_retry_bad_request() {
+    let server = StubServer::scripted(vec![
+        StubResponse::json(400, r#"{"error":"invalid file format"}"#),
+        StubResponse::json(200, SUCCESS),
+    ])
+    .await;
+    let (client, mut events) = client(&server, policy());
+
+    let err = client
+        .transcribe(silent_wav(), &options())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, VoiceInputError::ApiStatus { status: 400, .. }),
+        "{}",
+        err
+    );
+    assert_eq!(server.requests().len(), 1);
+    assert!(matches!(
+        drain(&mut events).last(),
+        Some(RetryEvent::GaveUp { attempt: 1, .. })
+    ));
+}
+
+#[tokio::test]
+async fn retries_dropped_connection() {
+    let server = StubServer::scripted(vec![
+        StubResponse::disconnect(),
+        StubResponse::json(200, SUCCESS),
+    ])
+    .await;
+    let (client, mut events) = client(&server, policy());
+
+    let transcript = client.transcribe(silent_wav(), &options()).await.unwrap();
+    assert_eq!(transcript.text, "再試行で転写しました");
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(scheduled(&drain(&mut events)).len(), 1);
+}
+
+#[tokio::test]
+async fn times_out_slow_request_and_retries() {
+    let server = StubServer::scripted(vec![
+        StubResponse::json(200, SUCCESS).delayed(Duration::from_secs(3)),
+        StubResponse::json(200, SUCCESS),
+    ])
+    .await;
+    let (client, mut events) = client(&server, policy());
+
+    let started = Instant::now();
+    let transcript = client.transcribe(silent_wav(), &options()).await.unwrap();
+    assert_eq!(transcript.text, "再試行で転写しました");
+    assert!(started.elapsed() < Duration::from_secs(3));
+
+    let retries = scheduled(&drain(&mut events));
+    assert_eq!(retries.len(), 1);
+    assert!(retries[0].1.contains("timed out"), "{}", retries[0].1);
+}
+
+#[tokio::test]
+async fn gives_up_at_total_deadline() {
+    let server = StubServer::scripted(vec![
+        StubResponse::json(503, r#"{"error":"overloaded"}"#).with_header("Retry-After", "30"),
+    ])
+    .await;
+    let (client, mut events) = client(
+        &server,
+        RetryPolicy {
+            max_total: Duration::from_secs(5),
+            ..policy()
+        },
+    );
+
+    let started = Instant::now();
+    let err = client
+        .transcribe(silent_wav(), &options())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, VoiceInputError::ApiStatus { status: 503, .. }),
+        "{}",
+        err
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(server.requests().len(), 1);
+    match drain(&mut events).last() {
+        Some(RetryEvent::GaveUp { reason, .. }) => {
+            assert!(reason.contains("deadline"), "{}", reason)
+        }
+        other => panic!("unexpected event: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let server = StubServer::scripted(vec![StubResponse::json(500, r#"{"error":"boom"}"#)]).await;
+    let (client, _events) = client(&server, policy());
+
+    let err = client
+        .transcribe(silent_wav(), &options())
+        .await
+        .unwrap_err();
+    assert!(err.is_retryable());
+    assert_eq!(server.requests().len(), 4);
+}
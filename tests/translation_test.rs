@@ -0,0 +1,110 @@
+//! 翻訳クライアントのテスト
+//!
+//! プロセス内で起動したモック HTTP サーバーに対して `ChatClient` を実行し、
+//! Chat Completions 形式のリクエストと応答の解釈、エラーの扱いを確認します。
+
+mod common;
+
+use std::time::Duration;
+
+use common::stub_server::{StubResponse, StubServer};
+use serde_json::Value;
+use voice_input::application::traits::Translator;
+use voice_input::error::VoiceInputError;
+use voice_input::infrastructure::external::chat::{ChatClient, ChatConfig};
+
+fn client(server: &StubServer) -> ChatClient {
+    ChatClient::new(ChatConfig {
+        base_url: server.base_url(),
+        api_key: Some("llm-key".to_string()),
+        model: "local-llm".to_string(),
+        timeout: Duration::from_secs(2),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+fn completion(content: &str) -> String {
+    serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "choices": [{"index": 0, "message": {"role": "assistant", "content": content}, "finish_reason": "stop"}]
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn translates_with_chat_completions() {
+    let server = StubServer::scripted(vec![StubResponse::json(
+        200,
+        &completion(" Fix the typo in the README. \n"),
+    )])
+    .await;
+
+    let text = client(&server)
+        .translate("README の誤字を直す。", "en")
+        .await
+        .unwrap();
+    assert_eq!(text, "Fix the typo in the README.");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/v1/chat/completions");
+    assert_eq!(requests[0].header("authorization"), Some("Bearer llm-key"));
+
+    let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["model"], "local-llm");
+    assert_eq!(body["messages"][0]["role"], "system");
+    assert!(
+        body["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains("\"en\"")
+    );
+    assert_eq!(body["messages"][1]["role"], "user");
+    assert_eq!(body["messages"][1]["content"], "README の誤字を直す。");
+}
+
+#[tokio::test]
+async fn reports_api_errors() {
+    let server = StubServer::scripted(vec![
+        StubResponse::json(429, r#"{"error":"rate limited"}"#).with_header("Retry-After", "3"),
+        StubResponse::json(200, r#"{"choices":[]}"#),
+    ])
+    .await;
+    let client = client(&server);
+
+    let err = client.translate("こんにちは", "en").await.unwrap_err();
+    match err {
+        VoiceInputError::ApiStatus {
+            status,
+            retry_after,
+            ..
+        } => {
+            assert_eq!(status, 429);
+            assert_eq!(retry_after, Some(Duration::from_secs(3)));
+        }
+        other => panic!("unexpected error: {}", other),
+    }
+
+    // 応答が空の場合もエラー
+    assert!(client.translate("こんにちは", "en").await.is_err());
+}
+
+#[tokio::test]
+async fn times_out() {
+    let server = StubServer::scripted(vec![
+        StubResponse::json(200, &completion("late")).delayed(Duration::from_secs(5)),
+    ])
+    .await;
+    let client = ChatClient::new(ChatConfig {
+        base_url: server.base_url(),
+        timeout: Duration::from_millis(200),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let err = client.translate("こんにちは", "en").await.unwrap_err();
+    assert!(matches!(err, VoiceInputError::RequestTimeout(_)), "{}", err);
+}